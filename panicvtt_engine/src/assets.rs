@@ -1,3 +1,4 @@
 pub mod asset;
 pub(crate) mod asset_manager;
+pub mod duplicate_policy;
 pub mod load_asset_result;
\ No newline at end of file
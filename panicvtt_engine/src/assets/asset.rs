@@ -5,4 +5,12 @@ pub trait Asset {
     /// 
     /// Returns None if this Asset is global to the VTT system.  
     fn get_owning_campaign(&self) -> Option<u128>;
+
+    /// Set by the asset manager when it loads or creates the Asset, and by a Campaign when a Scene is added to it. 
+    fn set_owning_campaign(&mut self, campaign: Option<u128>);
+
+    /// The UUID of the global Asset this one replaces within its owning campaign, if any. 
+    fn get_overridden_asset(&self) -> Option<u128> {
+        None
+    }
 }
\ No newline at end of file
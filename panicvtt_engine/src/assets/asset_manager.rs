@@ -1,24 +1,68 @@
-use std::{collections::HashMap, fs, io::{Error, ErrorKind}, path::Path};
+use std::{collections::{HashMap, HashSet}, fs::{self, File}, io::{Error, ErrorKind, Write}, path::{Path, PathBuf}};
 
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 use uuid::Uuid;
 
-use crate::{campaigns::{campaign::Campaign, campaign_description::CampaignDescription, scene::Scene}, entities::{abilities::AbilityScores, class::Class, entity::Entity, race::Race}, mechanics::dice::{Dice, Rng}, util::asset_key_error::AssetKeyError};
+use crate::{campaigns::{campaign::Campaign, campaign_description::CampaignDescription, lighting::LightAnchor}, entities::{abilities::{Ability, AbilityScores}, background::Background, class::Class, entity::Entity, feat::{Feat, FeatEffect, FeatPrerequisite}, improvement::AbilityScoreImprovement, modifier::ModifierSource, monster::{ChallengeRating, CreatureType, HitPointMode, Monster}, race::Race, skills::{Skill, SkillDefinition}}, mechanics::dice::{Dice, Rng}, util::{asset_key_error::AssetKeyError, dangling_reference::DanglingReference, duplicate_asset_error::DuplicateAssetError}};
+
+use super::{asset::Asset, duplicate_policy::DuplicatePolicy, load_asset_result::LoadAssetResult};
+
+/// The kinds of Asset the manager holds, each kept in its own directory. 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum AssetKind {
+    Background, 
+    Campaign, 
+    Class, 
+    Entity, 
+    Feat, 
+    Monster, 
+    Race, 
+    Skill, 
+}
 
-use super::{asset::Asset, load_asset_result::LoadAssetResult};
+impl AssetKind {
+    fn get_dir_name(&self) -> &'static str {
+        match self {
+            AssetKind::Background => "backgrounds",
+            AssetKind::Campaign => "campaigns",
+            AssetKind::Class => "classes",
+            AssetKind::Entity => "entities",
+            AssetKind::Feat => "feats",
+            AssetKind::Monster => "monsters",
+            AssetKind::Race => "races",
+            AssetKind::Skill => "skills",
+        }
+    }
+}
 
 pub struct AssetManager {
     asset_root: String, 
+    /// The file each Asset was read from or last saved to, so it is written back in place. 
+    asset_paths: HashMap<(AssetKind, u128), PathBuf>,
+    /// Assets created or changed since they were last saved. 
+    changed: HashSet<(AssetKind, u128)>,
+    /// Lookups only see global Assets and those local to this Campaign. 
+    active_campaign: Option<u128>,
+    /// The local Asset standing in for a global one, by kind, owning Campaign and global UUID. 
+    overrides: HashMap<(AssetKind, u128, u128), u128>,
+    duplicate_policy: DuplicatePolicy,
+    /// Clashing UUIDs found while loading and resolved by keeping one of the Assets. 
+    duplicates: Vec<DuplicateAssetError>,
+    backgrounds: HashMap<u128, Background>,
     campaign_descriptions: HashMap<u128, CampaignDescription>,
     campaigns: HashMap<u128, Campaign>,
     classes: HashMap<u128, Class>,
     entities: HashMap<u128, Entity>,
+    feats: HashMap<u128, Feat>,
+    monsters: HashMap<u128, Monster>,
     races: HashMap<u128, Race>, 
-    scenes: HashMap<u128, Scene>,
+    skills: HashMap<u128, SkillDefinition>,
 }
 
+// Add a new Asset belonging to the Campaign `$owner`, or to no Campaign if it is None 
 macro_rules! create_and_check_dups {
-    ($asset:ident, $map:ident, $message:literal) => {{
+    ($asset:ident, $map:ident, $message:literal, $owner:expr) => {{
+        $asset.set_owning_campaign($owner);
         let uuid = $asset.get_uuid();
         match $map.insert($asset.get_uuid(), $asset) {
             Some(previous_asset) => {
@@ -62,7 +106,7 @@ impl AssetManager {
     }
 
     fn parse_asset<T>(asset_path: &Path) -> Result<T, Error> where T : Asset + DeserializeOwned {
-        let data = fs::read_to_string(&asset_path)?;
+        let data = fs::read_to_string(asset_path)?;
         serde_yaml::from_str(&data).map_err(|e| Error::new(ErrorKind::InvalidData, e))
         
         // from_reader throws Errs about string borrow stuff that doesn't make sense. Changing uuid fields to serde::simple caused the issue. no idea. 
@@ -70,73 +114,145 @@ impl AssetManager {
         // serde_yaml::from_reader(f).map_err(|e| Error::new(ErrorKind::InvalidData, e))
     }
 
-    fn parse_asset_dir<T>(asset_dir: &Path) -> Result<HashMap<u128, T>, Error> where T : Asset + DeserializeOwned {
-        let mut map: HashMap<u128, T> = HashMap::new();
+    /// Parse every Asset in a directory and its subdirectories, in path order, along with the file each came from. 
+    fn parse_asset_dir<T>(asset_dir: &Path, owner: Option<u128>) -> Result<Vec<(T, PathBuf)>, Error> where T : Asset + DeserializeOwned {
+        let mut assets = Vec::new();
 
-        // Iterate over everything in the directory 
-        for obj in fs::read_dir(asset_dir)? {
-            let obj = obj?;
-            let path = obj.path();
+        // Iterate over everything in the directory in a stable order, so "first" and "last" mean something 
+        let mut paths = fs::read_dir(asset_dir)?
+            .map(|obj| obj.map(|o| o.path()))
+            .collect::<Result<Vec<PathBuf>, Error>>()?;
+        paths.sort();
 
+        for path in paths {
             // Recur into directories and aggregate everything found 
             if path.is_dir() {
-                let subdir = Self::parse_asset_dir(&path)?;
-                map.extend(subdir);
+                assets.extend(Self::parse_asset_dir(&path, owner)?);
             } else if path.extension().unwrap_or_default() == "panic" {
                 // Attempt to parse this asset and quit if we can't (for now)
-                let asset = Self::parse_asset::<T>(&path)?;
-                map.insert(asset.get_uuid(), asset);
+                let mut asset = Self::parse_asset::<T>(&path)?;
+                asset.set_owning_campaign(owner);
+                assets.push((asset, path));
             }
         }
 
-        Ok(map)
+        Ok(assets)
     }
 
-    
-    //TODO Might want to eventually look for duplicate UUIDs when deserializing... 
+    /// Add parsed Assets to their map. An Asset sharing a UUID with one already read from another file is handled by 
+    /// the duplicate policy, except that a Campaign's local Asset may never replace one it doesn't own. Built-in 
+    /// Assets such as the standard skills may be replaced freely. 
+    fn merge_assets<T>(&mut self, kind: AssetKind, assets: Vec<(T, PathBuf)>, map: fn(&mut Self) -> &mut HashMap<u128, T>) -> Result<(), Error> where T : Asset {
+        for (asset, path) in assets {
+            let uuid = asset.get_uuid();
+            // Reading the same file again, as when reloading a Campaign, is not a clash 
+            let first = self.asset_paths.get(&(kind, uuid)).filter(|first| **first != path).cloned();
+            let first_owner = map(self).get(&uuid).map(|a| a.get_owning_campaign());
+            if let (Some(first), Some(first_owner)) = (first, first_owner) {
+                let e = DuplicateAssetError::new(format!("Duplicate UUID in {}", kind.get_dir_name()), uuid, first, path.clone());
+                if asset.get_owning_campaign().is_some_and(|c| first_owner != Some(c)) {
+                    return Err(Error::new(ErrorKind::InvalidData, e));
+                }
+
+                match self.duplicate_policy {
+                    DuplicatePolicy::Error => return Err(Error::new(ErrorKind::InvalidData, e)),
+                    DuplicatePolicy::KeepFirst => {
+                        self.duplicates.push(e);
+                        continue;
+                    },
+                    DuplicatePolicy::KeepLast => self.duplicates.push(e),
+                }
+            }
+
+            self.asset_paths.insert((kind, uuid), path);
+            map(self).insert(uuid, asset);
+        }
+
+        Ok(())
+    }
+
+    /// Load a directory of Assets if its name says what they are. Returns false for any other directory. 
+    fn load_asset_dir(&mut self, path: &Path, owner: Option<u128>) -> Result<bool, Error> {
+        let dir_name = path.file_name().unwrap_or_default();
+        if dir_name == "classes" {
+            self.merge_assets(AssetKind::Class, Self::parse_asset_dir(path, owner)?, |am| &mut am.classes)?;
+        } else if dir_name == "races" {
+            self.merge_assets(AssetKind::Race, Self::parse_asset_dir(path, owner)?, |am| &mut am.races)?;
+        } else if dir_name == "entities" {
+            self.merge_assets(AssetKind::Entity, Self::parse_asset_dir(path, owner)?, |am| &mut am.entities)?;
+        } else if dir_name == "feats" {
+            self.merge_assets(AssetKind::Feat, Self::parse_asset_dir(path, owner)?, |am| &mut am.feats)?;
+        } else if dir_name == "skills" {
+            self.merge_assets(AssetKind::Skill, Self::parse_asset_dir(path, owner)?, |am| &mut am.skills)?;
+        } else if dir_name == "backgrounds" {
+            self.merge_assets(AssetKind::Background, Self::parse_asset_dir(path, owner)?, |am| &mut am.backgrounds)?;
+        } else if dir_name == "monsters" {
+            self.merge_assets(AssetKind::Monster, Self::parse_asset_dir(path, owner)?, |am| &mut am.monsters)?;
+        } else {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// Load the global Assets under `asset_root`, failing if two files share a UUID. 
     pub fn new(asset_root: &Path) -> Result<Self, Error> {
+        Self::with_duplicate_policy(asset_root, DuplicatePolicy::default())
+    }
+
+    /// Load the global Assets under `asset_root`, handling files that share a UUID with `duplicate_policy`. 
+    /// The policy also applies when Campaigns are loaded. 
+    pub fn with_duplicate_policy(asset_root: &Path, duplicate_policy: DuplicatePolicy) -> Result<Self, Error> {
         let default_class = Class::default();
         let default_race = Race::default();
 
+        let mut am = Self {
+            // Non-Unicode root is an engine error 
+            asset_root: String::from(asset_root.to_str().unwrap()), 
+            asset_paths: HashMap::new(),
+            changed: HashSet::new(),
+            active_campaign: None,
+            overrides: HashMap::new(),
+            duplicate_policy,
+            duplicates: Vec::new(),
+            backgrounds: HashMap::new(),
+            campaign_descriptions: HashMap::new(),
+            campaigns: HashMap::new(),
+            classes: HashMap::from([(default_class.get_uuid(), default_class)]),
+            races: HashMap::from([(default_race.get_uuid(), default_race)]), 
+            entities: HashMap::new(), 
+            feats: HashMap::new(),
+            monsters: HashMap::new(),
+            skills: Self::standard_skills(),
+        };
         let mut campaign_descriptions: Option<HashMap<u128, CampaignDescription>> = None; 
 
-        let mut classes: HashMap<u128, Class> = HashMap::from([(default_class.get_uuid(), default_class)]);
-        let mut races: HashMap<u128, Race> = HashMap::from([(default_race.get_uuid(), default_race)]);
-        let mut entities: HashMap<u128, Entity> = HashMap::new();
-
         // Attempt to open the directory provided
         for obj in fs::read_dir(asset_root)? {
             let obj = obj?;
             let path = obj.path();
             
-            // Ignore non-directory objects in the root 
+            // Ignore non-directory objects in the root, and directories that don't match 
             if path.is_dir() {
-                // Determine which asset folder we're about to load 
-                let dir_name = path.file_name().unwrap_or_default();
-                if dir_name == "campaigns" {
+                if path.file_name().unwrap_or_default() == "campaigns" {
                     // Load campaign descriptions, not the campaigns themselves 
                     campaign_descriptions = Some(Self::load_campaign_descriptions(&path)?);
-                } else if dir_name == "classes" {
-                    classes.extend(Self::parse_asset_dir(&path)?);
-                } else if dir_name == "races" {
-                    races.extend(Self::parse_asset_dir(&path)?);
-                } else if dir_name == "entities" {
-                    entities.extend(Self::parse_asset_dir(&path)?);
-                } // Ignore directories that don't match
+                } else {
+                    am.load_asset_dir(&path, None)?;
+                }
             }
         }
 
-        Ok(Self {
-            // Non-Unicode root is an engine error 
-            asset_root: String::from(asset_root.to_str().unwrap()), 
-            // Not having a campaign subdirectory is an engine error for now 
-            campaign_descriptions: campaign_descriptions.unwrap(),
-            campaigns: HashMap::new(),
-            classes,
-            races, 
-            entities, 
-            scenes: HashMap::new()
-        })
+        // Not having a campaign subdirectory is an engine error for now 
+        am.campaign_descriptions = campaign_descriptions.unwrap();
+        Ok(am)
+    }
+
+    /// The standard skills are always available, though asset directories may redefine them. 
+    fn standard_skills() -> HashMap<u128, SkillDefinition> {
+        SkillDefinition::standard().into_iter()
+            .map(|s| (s.get_uuid(), s))
+            .collect()
     }
 
     // Most tests don't need assets - no need to load anything in this case 
@@ -150,12 +266,21 @@ impl AssetManager {
         
         Self {
             asset_root: String::new(),
+            asset_paths: HashMap::new(),
+            changed: HashSet::new(),
+            active_campaign: None,
+            overrides: HashMap::new(),
+            duplicate_policy: DuplicatePolicy::default(),
+            duplicates: Vec::new(),
+            backgrounds: HashMap::new(),
             campaign_descriptions: HashMap::new(),
             campaigns: HashMap::new(),
             classes: HashMap::from([(c.get_uuid(), c)]), 
             races: HashMap::from([(r.get_uuid(), r)]),
             entities: HashMap::new(),
-            scenes: HashMap::new()
+            feats: HashMap::new(),
+            monsters: HashMap::new(),
+            skills: Self::standard_skills(),
         }
     }
 
@@ -169,20 +294,315 @@ impl AssetManager {
         self.races.iter().next().unwrap().1
     }
 
+    /// Whether lookups can see an Asset: it must be global or local to the active Campaign. 
+    fn is_in_scope<T>(&self, asset: &T) -> bool where T : Asset {
+        asset.get_owning_campaign().is_none_or(|c| self.active_campaign == Some(c))
+    }
+
+    /// The UUID a lookup really finds: the active Campaign's override of a global Asset, or the UUID itself. 
+    fn resolve(&self, kind: AssetKind, uuid: u128) -> u128 {
+        self.resolve_in(kind, self.active_campaign, uuid)
+    }
+
+    /// The UUID a lookup made from `scope`, a Campaign or global Assets, really finds. 
+    fn resolve_in(&self, kind: AssetKind, scope: Option<u128>, uuid: u128) -> u128 {
+        scope
+            .and_then(|c| self.overrides.get(&(kind, c, uuid)))
+            .copied()
+            .unwrap_or(uuid)
+    }
+
+    /// Whether listings show an Asset: it must be in scope and not replaced by an override in the active Campaign. 
+    fn is_listed<T>(&self, kind: AssetKind, asset: &T) -> bool where T : Asset {
+        self.is_in_scope(asset) && self.resolve(kind, asset.get_uuid()) == asset.get_uuid()
+    }
+
+    /// Record which global Assets a newly loaded Campaign's local Assets override. 
+    fn index_overrides(&mut self, campaign: u128) {
+        let mut overrides = Vec::new();
+        overrides.extend(Self::find_overrides(&self.backgrounds, AssetKind::Background, campaign));
+        overrides.extend(Self::find_overrides(&self.classes, AssetKind::Class, campaign));
+        overrides.extend(Self::find_overrides(&self.entities, AssetKind::Entity, campaign));
+        overrides.extend(Self::find_overrides(&self.feats, AssetKind::Feat, campaign));
+        overrides.extend(Self::find_overrides(&self.monsters, AssetKind::Monster, campaign));
+        overrides.extend(Self::find_overrides(&self.races, AssetKind::Race, campaign));
+        overrides.extend(Self::find_overrides(&self.skills, AssetKind::Skill, campaign));
+        self.overrides.extend(overrides);
+    }
+
+    fn find_overrides<T>(map: &HashMap<u128, T>, kind: AssetKind, campaign: u128) -> Vec<((AssetKind, u128, u128), u128)> where T : Asset {
+        map.values()
+            .filter(|a| a.get_owning_campaign() == Some(campaign))
+            .filter_map(|a| Some(((kind, campaign, a.get_overridden_asset()?), a.get_uuid())))
+            .collect()
+    }
+
+    /// Clashing UUIDs found while loading that the duplicate policy resolved by keeping one Asset. 
+    pub fn get_duplicates(&self) -> &[DuplicateAssetError] {
+        &self.duplicates
+    }
+
+    /// Check every reference between loaded Assets, returning those that don't lead anywhere, ordered by file. 
+    /// 
+    /// References are followed the way lookups from the referring Asset's Campaign would be, so a global Asset 
+    /// referring to one local to a Campaign is reported too. Overridden Assets must be global. 
+    pub fn validate(&self) -> Vec<DanglingReference> {
+        let mut report = Vec::new();
+        for background in self.backgrounds.values() {
+            let references = Self::skill_references("skills", background.get_skills().iter().copied());
+            self.check_references(&mut report, AssetKind::Background, background, references);
+        }
+        for class in self.classes.values() {
+            self.check_references(&mut report, AssetKind::Class, class, Vec::new());
+        }
+        for entity in self.entities.values() {
+            let mut references = vec![
+                (String::from("class"), AssetKind::Class, entity.get_class_uuid()), 
+                (String::from("race"), AssetKind::Race, entity.get_race_uuid()), 
+            ];
+            references.extend(entity.get_background_uuid().map(|b| (String::from("background"), AssetKind::Background, b)));
+            references.extend(entity.get_monster_uuid().map(|m| (String::from("monster"), AssetKind::Monster, m)));
+            references.extend(Self::skill_references("skills", entity.get_skills().keys().copied()));
+            references.extend(entity.get_improvements().iter().filter_map(|i| match i.get_improvement() {
+                AbilityScoreImprovement::Feat { feat } => Some((String::from("improvements"), AssetKind::Feat, *feat)),
+                _ => None,
+            }));
+            references.extend(entity.get_modifiers().iter().filter_map(|m| match m.get_source() {
+                ModifierSource::Race { race } => Some((String::from("modifiers"), AssetKind::Race, *race)),
+                ModifierSource::Class { class } => Some((String::from("modifiers"), AssetKind::Class, *class)),
+                ModifierSource::Feat { feat } => Some((String::from("modifiers"), AssetKind::Feat, *feat)),
+                _ => None,
+            }));
+            self.check_references(&mut report, AssetKind::Entity, entity, references);
+        }
+        for feat in self.feats.values() {
+            let mut references: Vec<(String, AssetKind, u128)> = feat.get_prerequisites().iter().filter_map(|p| match p {
+                FeatPrerequisite::Race { race } => Some((String::from("prerequisites"), AssetKind::Race, *race)),
+                FeatPrerequisite::SkillProficiency { skill } => Some((String::from("prerequisites"), AssetKind::Skill, skill.get_uuid())),
+                _ => None,
+            }).collect();
+            references.extend(Self::skill_references("effects", feat.get_effects().iter().filter_map(|e| match e {
+                FeatEffect::SkillProficiency { skill, .. } => Some(*skill),
+                _ => None,
+            })));
+            self.check_references(&mut report, AssetKind::Feat, feat, references);
+        }
+        for monster in self.monsters.values() {
+            let references = Self::skill_references("skills", monster.get_skills().keys().copied());
+            self.check_references(&mut report, AssetKind::Monster, monster, references);
+        }
+        for race in self.races.values() {
+            self.check_references(&mut report, AssetKind::Race, race, Vec::new());
+        }
+        for skill in self.skills.values() {
+            self.check_references(&mut report, AssetKind::Skill, skill, Vec::new());
+        }
+        for campaign in self.campaigns.values() {
+            let entity = |field: String, uuid: u128| (field, AssetKind::Entity, uuid);
+            let mut references: Vec<(String, AssetKind, u128)> = campaign.get_party().into_iter()
+                .map(|e| entity(String::from("party"), e))
+                .collect();
+            for player in campaign.get_players() {
+                references.extend(campaign.get_player_entities(player).into_iter().map(|e| entity(format!("players.{}", player), e)));
+            }
+            for scene in campaign.get_scenes() {
+                let field = |name: &str| format!("scenes.{:032x}.{}", scene.get_uuid(), name);
+                references.extend(scene.get_entities().iter().map(|e| entity(field("entities"), *e)));
+                references.extend(scene.get_tokens().iter().map(|t| entity(field("tokens"), t.get_entity())));
+                references.extend(scene.get_encounter().into_iter()
+                    .flat_map(|e| e.get_combatants())
+                    .map(|c| entity(field("encounter"), c.get_entity())));
+                references.extend(scene.get_lights().iter().filter_map(|l| match l.get_anchor() {
+                    LightAnchor::Entity { entity: e } => Some(entity(field("lights"), e.as_u128())),
+                    LightAnchor::Position { .. } => None,
+                }));
+            }
+
+            // Campaigns see their own local Assets 
+            let path = self.asset_paths.get(&(AssetKind::Campaign, campaign.get_uuid())).cloned();
+            report.extend(references.into_iter()
+                .filter(|(_, kind, target)| !self.is_visible(*kind, Some(campaign.get_uuid()), *target))
+                .map(|(field, kind, target)| {
+                    DanglingReference::new(path.clone(), campaign.get_uuid(), field, target, self.get_owner(kind, target).is_some())
+                }));
+        }
+
+        report.sort();
+        report
+    }
+
+    fn skill_references(field: &str, skills: impl Iterator<Item = Skill>) -> Vec<(String, AssetKind, u128)> {
+        skills.map(|s| (String::from(field), AssetKind::Skill, s.get_uuid())).collect()
+    }
+
+    /// Report an Asset's references, and the Asset it overrides, that can't be found from its Campaign. 
+    fn check_references<T>(&self, report: &mut Vec<DanglingReference>, kind: AssetKind, asset: &T, references: Vec<(String, AssetKind, u128)>) where T : Asset {
+        let scope = asset.get_owning_campaign();
+        let dangling = references.into_iter()
+            .filter(|(_, kind, target)| !self.is_visible(*kind, scope, *target))
+            .chain(asset.get_overridden_asset()
+                .filter(|target| !self.is_visible(kind, None, *target))
+                .map(|target| (String::from("overrides"), kind, target)));
+
+        let path = self.asset_paths.get(&(kind, asset.get_uuid()));
+        report.extend(dangling.map(|(field, kind, target)| {
+            DanglingReference::new(path.cloned(), asset.get_uuid(), field, target, self.get_owner(kind, target).is_some())
+        }));
+    }
+
+    /// Whether a lookup made from `scope`, a Campaign or global Assets, would find an Asset. 
+    fn is_visible(&self, kind: AssetKind, scope: Option<u128>, uuid: u128) -> bool {
+        let uuid = self.resolve_in(kind, scope, uuid);
+        self.get_owner(kind, uuid).is_some_and(|o| o.is_none() || o == scope)
+    }
+
+    /// The Campaign a loaded Asset belongs to, or Some(None) if it is global. Returns None if it isn't loaded. 
+    fn get_owner(&self, kind: AssetKind, uuid: u128) -> Option<Option<u128>> {
+        match kind {
+            AssetKind::Background => self.backgrounds.get(&uuid).map(|a| a.get_owning_campaign()),
+            AssetKind::Campaign => self.campaigns.get(&uuid).map(|a| a.get_owning_campaign()),
+            AssetKind::Class => self.classes.get(&uuid).map(|a| a.get_owning_campaign()),
+            AssetKind::Entity => self.entities.get(&uuid).map(|a| a.get_owning_campaign()),
+            AssetKind::Feat => self.feats.get(&uuid).map(|a| a.get_owning_campaign()),
+            AssetKind::Monster => self.monsters.get(&uuid).map(|a| a.get_owning_campaign()),
+            AssetKind::Race => self.races.get(&uuid).map(|a| a.get_owning_campaign()),
+            AssetKind::Skill => self.skills.get(&uuid).map(|a| a.get_owning_campaign()),
+        }
+    }
+
+    /// The Campaign whose local Assets lookups can see, alongside the global ones. 
+    pub fn get_active_campaign(&self) -> Option<u128> {
+        self.active_campaign
+    }
+
+    /// Scope lookups to a loaded Campaign, or to global Assets only. Returns false if the Campaign isn't loaded. 
+    pub(crate) fn set_active_campaign(&mut self, campaign: Option<u128>) -> bool {
+        if campaign.is_some_and(|c| !self.campaigns.contains_key(&c)) {
+            return false;
+        }
+
+        self.active_campaign = campaign;
+        true
+    }
+
+    /// Drop a loaded Campaign and every Asset local to it. Unsaved changes to them are lost. 
+    /// Returns false if the Campaign isn't loaded. 
+    pub(crate) fn unload_campaign(&mut self, campaign: u128) -> bool {
+        if self.campaigns.remove(&campaign).is_none() {
+            return false;
+        }
+
+        self.unload_local_assets(campaign);
+        if self.active_campaign == Some(campaign) {
+            self.active_campaign = None;
+        }
+        true
+    }
+
+    /// Forget a Campaign's local Assets and overrides, and where its file was read from. 
+    fn unload_local_assets(&mut self, campaign: u128) {
+        let mut unloaded = vec![(AssetKind::Campaign, campaign)];
+        unloaded.extend(Self::unload_owned(&mut self.backgrounds, AssetKind::Background, campaign));
+        unloaded.extend(Self::unload_owned(&mut self.classes, AssetKind::Class, campaign));
+        unloaded.extend(Self::unload_owned(&mut self.entities, AssetKind::Entity, campaign));
+        unloaded.extend(Self::unload_owned(&mut self.feats, AssetKind::Feat, campaign));
+        unloaded.extend(Self::unload_owned(&mut self.monsters, AssetKind::Monster, campaign));
+        unloaded.extend(Self::unload_owned(&mut self.races, AssetKind::Race, campaign));
+        unloaded.extend(Self::unload_owned(&mut self.skills, AssetKind::Skill, campaign));
+        for key in unloaded {
+            self.asset_paths.remove(&key);
+            self.changed.remove(&key);
+        }
+        self.overrides.retain(|(_, c, _), _| *c != campaign);
+    }
+
+    fn unload_owned<T>(map: &mut HashMap<u128, T>, kind: AssetKind, campaign: u128) -> Vec<(AssetKind, u128)> where T : Asset {
+        let owned: Vec<u128> = map.values()
+            .filter(|a| a.get_owning_campaign() == Some(campaign))
+            .map(|a| a.get_uuid())
+            .collect();
+        owned.into_iter().filter_map(|uuid| map.remove(&uuid).map(|_| (kind, uuid))).collect()
+    }
+
+    pub fn get_background(&self, uuid: u128) -> Option<&Background> {
+        self.backgrounds.get(&self.resolve(AssetKind::Background, uuid)).filter(|b| self.is_in_scope(*b))
+    }
+
     pub fn get_campaign(&self, uuid: u128) -> Option<&Campaign> {
         self.campaigns.get(&uuid)
     }
 
+    /// Campaigns borrowed mutably are saved again on the next `save`. 
+    pub(crate) fn get_campaign_mut(&mut self, uuid: u128) -> Option<&mut Campaign> {
+        let campaign = self.campaigns.get_mut(&uuid)?;
+        self.changed.insert((AssetKind::Campaign, uuid));
+        Some(campaign)
+    }
+
     pub fn get_class(&self, uuid: u128) -> Option<&Class> {
-        self.classes.get(&uuid)
+        self.classes.get(&self.resolve(AssetKind::Class, uuid)).filter(|c| self.is_in_scope(*c))
+    }
+
+    /// Every class in scope, ordered by UUID. Overrides are listed in place of what they override. 
+    pub fn get_classes(&self) -> Vec<&Class> {
+        let mut classes: Vec<&Class> = self.classes.values().filter(|c| self.is_listed(AssetKind::Class, *c)).collect();
+        classes.sort_by_key(|c| c.get_uuid());
+        classes
     }
 
     pub fn get_race(&self, uuid: u128) -> Option<&Race> {
-        self.races.get(&uuid)
+        self.races.get(&self.resolve(AssetKind::Race, uuid)).filter(|r| self.is_in_scope(*r))
+    }
+
+    /// Every race in scope, ordered by UUID. Overrides are listed in place of what they override. 
+    pub fn get_races(&self) -> Vec<&Race> {
+        let mut races: Vec<&Race> = self.races.values().filter(|r| self.is_listed(AssetKind::Race, *r)).collect();
+        races.sort_by_key(|r| r.get_uuid());
+        races
     }
 
     pub fn get_entity(&self, uuid: u128) -> Option<&Entity> {
-        self.entities.get(&uuid)
+        self.entities.get(&self.resolve(AssetKind::Entity, uuid)).filter(|e| self.is_in_scope(*e))
+    }
+
+    pub fn get_feat(&self, uuid: u128) -> Option<&Feat> {
+        self.feats.get(&self.resolve(AssetKind::Feat, uuid)).filter(|f| self.is_in_scope(*f))
+    }
+
+    pub fn get_monster(&self, uuid: u128) -> Option<&Monster> {
+        self.monsters.get(&self.resolve(AssetKind::Monster, uuid)).filter(|m| self.is_in_scope(*m))
+    }
+
+    /// Every monster stat block in scope, ordered by UUID. Overrides are listed in place of what they override. 
+    pub fn get_monsters(&self) -> Vec<&Monster> {
+        let mut monsters: Vec<&Monster> = self.monsters.values().filter(|m| self.is_listed(AssetKind::Monster, *m)).collect();
+        monsters.sort_by_key(|m| m.get_uuid());
+        monsters
+    }
+
+    pub fn get_skill(&self, skill: Skill) -> Option<&SkillDefinition> {
+        self.skills.get(&self.resolve(AssetKind::Skill, skill.get_uuid())).filter(|s| self.is_in_scope(*s))
+    }
+
+    /// Every skill definition in scope, standard skills first. Overrides are listed in place of what they override. 
+    pub fn get_skills(&self) -> Vec<&SkillDefinition> {
+        let mut skills: Vec<&SkillDefinition> = self.skills.values().filter(|s| self.is_listed(AssetKind::Skill, *s)).collect();
+        skills.sort_by_key(|s| s.get_uuid());
+        skills
+    }
+
+    /// Run a mutation on an Entity that also needs to read other Assets. 
+    /// 
+    /// The Entity is taken out of the manager for the duration of `f` so both borrows can coexist. 
+    /// Like lookups, this changes the active Campaign's override of the Entity if it has one. 
+    pub(crate) fn update_entity<F, R>(&mut self, uuid: u128, f: F) -> Option<R> where F : FnOnce(&mut Entity, &AssetManager) -> R {
+        self.get_entity(uuid)?;
+        let uuid = self.resolve(AssetKind::Entity, uuid);
+        let mut entity = self.entities.remove(&uuid)?;
+        let result = f(&mut entity, self);
+        self.entities.insert(uuid, entity);
+        self.changed.insert((AssetKind::Entity, uuid));
+        Some(result)
     }
     
     pub fn get_default_class(&self) -> &Class {
@@ -203,92 +623,282 @@ impl AssetManager {
         let Some(campaign_dir) = self.campaign_descriptions.get(&campaign_id) else {
             return LoadAssetResult::UuidNotFoundError;
         };
-
-        let mut campaign: Option<Campaign> = None;
-
-        // Iterate the directory, provided it exists 
         let path = Path::new(self.asset_root.as_str())
             .join(Path::new("campaigns/"))
             .join(campaign_dir.get_path()); 
-        let dir = match fs::read_dir(path) {
-            Ok(dir) => dir,
-            Err(e) => { return LoadAssetResult::IoError { e }; }
+
+        // A Campaign that fails to load leaves nothing behind, unless it was already loaded 
+        let was_loaded = self.campaigns.contains_key(&campaign_id);
+        let campaign = match self.read_campaign_dir(&path, campaign_id) {
+            Ok(campaign) => campaign,
+            Err(e) => {
+                if !was_loaded {
+                    self.unload_local_assets(campaign_id);
+                }
+                return LoadAssetResult::IoError { e };
+            },
         };
 
-        for obj in dir {
-            let obj = match obj {
-                Ok(o) => o,
-                Err(e) => { return LoadAssetResult::IoError { e }; }
-            };
-            let path = obj.path(); 
+        match campaign {
+            Some(mut c) => {
+                self.index_overrides(campaign_id);
+                let campaigns = &mut self.campaigns;
+                match create_and_check_dups!(c, campaigns, "C", None) {
+                    Ok(c) => {
+                        self.active_campaign = Some(campaign_id);
+                        LoadAssetResult::Ok { asset: c }
+                    },
+                    Err(e) => LoadAssetResult::UuidDuplicateError { e },
+                } 
+            },
+            None => {
+                if !was_loaded {
+                    self.unload_local_assets(campaign_id);
+                }
+                LoadAssetResult::NoCampaignFound
+            },
+        }
+    }
+
+    /// Read a Campaign's directory: its local Assets, and the Campaign file if there is one. 
+    fn read_campaign_dir(&mut self, path: &Path, campaign_id: u128) -> Result<Option<Campaign>, Error> {
+        let mut campaign: Option<Campaign> = None;
+
+        for obj in fs::read_dir(path)? {
+            let path = obj?.path(); 
 
             // We either have the Campaign file, a directory with local Assets, or junk 
             if path.extension().unwrap_or_default() == "panic" {
                 // Attempt to deserialize this file into our campaign definition 
-                match Self::parse_asset(&path) {
-                    Ok(c) => { campaign = Some(c); }
-                    Err(e) => { return LoadAssetResult::IoError { e }; }
-                }
+                let mut c = Self::parse_asset::<Campaign>(&path)?;
+                c.adopt_scenes();
+                self.asset_paths.insert((AssetKind::Campaign, c.get_uuid()), path);
+                campaign = Some(c); 
             } else if path.is_dir() {
-                let dir_name = path.file_name().unwrap_or_default();
-                if dir_name == "classes" {
-                    match Self::parse_asset_dir(&path) {
-                        Ok(cs) => { self.classes.extend(cs); },
-                        Err(e) => { return LoadAssetResult::IoError { e }; }
-                    };
-                } else if dir_name == "races" {
-                    match Self::parse_asset_dir(&path) {
-                        Ok(rs) => { self.races.extend(rs); },
-                        Err(e) => { return LoadAssetResult::IoError { e }; }
-                    };
-                } else if dir_name == "entities" {
-                    match Self::parse_asset_dir(&path) {
-                        Ok(es) => { self.entities.extend(es); },
-                        Err(e) => { return LoadAssetResult::IoError { e }; }
-                    };
-                } // Ignore directories that don't match
+                // Ignore directories that don't match 
+                self.load_asset_dir(&path, Some(campaign_id))?;
             } // Ignore other elements in the directory 
         }
 
-        match campaign {
-            Some(c) => {
-                let campaigns = &mut self.campaigns;
-                match create_and_check_dups!(c, campaigns, "C") {
-                    Ok(c) => LoadAssetResult::Ok { asset: c },
-                    Err(e) => LoadAssetResult::UuidDuplicateError { e },
-                } 
+        Ok(campaign)
+    }
+
+
+    /// Write every Asset created or changed since it was last saved, returning how many were written. 
+    /// 
+    /// Assets go back to the file they were read from. New ones go in their kind's directory under the asset root, 
+    /// and new Campaigns get a directory of their own and an entry in the campaign manifest. 
+    pub fn save(&mut self) -> Result<usize, Error> {
+        let mut changed: Vec<(AssetKind, u128)> = self.changed.iter().copied().collect();
+        changed.sort();
+
+        // Campaigns need a directory before they or their local Assets can be written 
+        let mut manifest_changed = false;
+        for (kind, uuid) in &changed {
+            let campaign = match kind {
+                AssetKind::Campaign => Some(*uuid),
+                _ => self.get_owner(*kind, *uuid).flatten(),
+            };
+            if let Some(campaign) = campaign {
+                manifest_changed |= self.describe_campaign(campaign);
+            }
+        }
+
+        let mut written = 0;
+        for (kind, uuid) in &changed {
+            let data = match kind {
+                AssetKind::Background => Self::to_yaml(self.backgrounds.get(uuid))?,
+                AssetKind::Campaign => Self::to_yaml(self.campaigns.get(uuid))?,
+                AssetKind::Class => Self::to_yaml(self.classes.get(uuid))?,
+                AssetKind::Entity => Self::to_yaml(self.entities.get(uuid))?,
+                AssetKind::Feat => Self::to_yaml(self.feats.get(uuid))?,
+                AssetKind::Monster => Self::to_yaml(self.monsters.get(uuid))?,
+                AssetKind::Race => Self::to_yaml(self.races.get(uuid))?,
+                AssetKind::Skill => Self::to_yaml(self.skills.get(uuid))?,
+            };
+
+            // Anything no longer loaded has nothing to save 
+            if let Some(data) = data {
+                let path = self.get_asset_path(*kind, *uuid);
+                Self::write_atomic(&path, &data)?;
+                self.asset_paths.insert((*kind, *uuid), path);
+                written += 1;
+            }
+            self.changed.remove(&(*kind, *uuid));
+        }
+
+        // Only list new Campaigns once their files are in place 
+        if manifest_changed {
+            let mut descriptions: Vec<&CampaignDescription> = self.campaign_descriptions.values().collect();
+            descriptions.sort_by_key(|d| d.get_uuid());
+            let data = serde_yaml::to_string(&descriptions).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            Self::write_atomic(&Path::new(&self.asset_root).join("campaigns/manifest.panic"), &data)?;
+        }
+
+        Ok(written)
+    }
+
+    fn to_yaml<T>(asset: Option<&T>) -> Result<Option<String>, Error> where T : Serialize {
+        asset.map(|a| serde_yaml::to_string(a).map_err(|e| Error::new(ErrorKind::InvalidData, e))).transpose()
+    }
+
+    /// Write a file beside `path` and rename it into place, so a crash never leaves it half-written. 
+    fn write_atomic(path: &Path, data: &str) -> Result<(), Error> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+
+        let written = File::create(&temp).and_then(|mut file| {
+            file.write_all(data.as_bytes())?;
+            file.sync_all()?;
+            fs::rename(&temp, path)
+        });
+
+        // Don't leave a stray temporary file beside the Asset if anything failed 
+        if written.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        written?;
+
+        // The rename only survives a crash once its directory is synced too, which Windows has no way to do 
+        #[cfg(unix)]
+        if let Some(parent) = path.parent() {
+            File::open(parent)?.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// Where an Asset should be saved: Campaign-local Assets go under their Campaign's directory. Campaigns, and the 
+    /// Campaigns that own local Assets, must already have a description. 
+    fn get_asset_path(&self, kind: AssetKind, uuid: u128) -> PathBuf {
+        if let Some(path) = self.asset_paths.get(&(kind, uuid)) {
+            return path.clone();
+        }
+
+        let root = Path::new(&self.asset_root);
+        match kind {
+            AssetKind::Campaign => {
+                let dir = Path::new(self.campaign_descriptions[&uuid].get_path());
+                let file = Path::new(dir.file_name().unwrap_or_default()).with_extension("panic");
+                root.join("campaigns").join(dir).join(file)
+            },
+            _ => {
+                let dir = match self.get_owner(kind, uuid).flatten() {
+                    Some(campaign) => root.join("campaigns").join(self.campaign_descriptions[&campaign].get_path()),
+                    None => root.to_path_buf(),
+                };
+                dir.join(kind.get_dir_name()).join(format!("{}.panic", Uuid::from_u128(uuid).simple()))
             },
-            None => LoadAssetResult::NoCampaignFound,
         }
     }
 
+    /// Give a Campaign a description and directory for the manifest if it doesn't have one. Returns true if it was new. 
+    fn describe_campaign(&mut self, uuid: u128) -> bool {
+        let Some(campaign) = self.campaigns.get(&uuid) else {
+            return false;
+        };
+        if self.campaign_descriptions.contains_key(&uuid) {
+            return false;
+        }
+
+        // Name the directory after the Campaign, unless that's taken 
+        let slug: String = campaign.get_name().chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        let slug = slug.trim_matches('_');
+        let taken = slug.is_empty()
+            || Path::new(&self.asset_root).join("campaigns").join(slug).exists()
+            || self.campaign_descriptions.values().any(|d| d.get_path().trim_end_matches('/') == slug);
+        let dir = match taken {
+            true => format!("{}/", Uuid::from_u128(uuid).simple()),
+            false => format!("{}/", slug),
+        };
+
+        let description = CampaignDescription::new(uuid, String::from(campaign.get_name()), dir, String::from(campaign.get_description()));
+        self.campaign_descriptions.insert(uuid, description);
+        true
+    }
+
+    pub(crate) fn create_background(&mut self, background_name: String) -> Result<&Background, AssetKeyError<'_, Background>> {
+        let mut b = Background::new(background_name);
+        self.changed.insert((AssetKind::Background, b.get_uuid()));
+        let backgrounds = &mut self.backgrounds;
+
+        create_and_check_dups!(b, backgrounds, "b", self.active_campaign)
+    }
 
     pub(crate) fn create_campaign(&mut self, campaign_name: String, campaign_description: String) -> Result<&Campaign, AssetKeyError<'_, Campaign>> {
-        let c = Campaign::new(campaign_name, campaign_description);
+        let mut c = Campaign::new(campaign_name, campaign_description);
+        self.changed.insert((AssetKind::Campaign, c.get_uuid()));
         let campaigns = &mut self.campaigns;
 
-        create_and_check_dups!(c, campaigns, "c")
+        create_and_check_dups!(c, campaigns, "c", None)
     }
 
-    pub(crate) fn create_class(&mut self, class_name: String, hit_die: Dice) -> Result<&Class, AssetKeyError<Class>> {
-        let c = Class::new(class_name, hit_die);
+    pub(crate) fn create_class(&mut self, class_name: String, hit_die: Dice) -> Result<&Class, AssetKeyError<'_, Class>> {
+        let mut c = Class::new(class_name, hit_die);
+        self.changed.insert((AssetKind::Class, c.get_uuid()));
         let classes = &mut self.classes;
 
-        create_and_check_dups!(c, classes, "c")
+        create_and_check_dups!(c, classes, "c", self.active_campaign)
+    }
+
+    pub(crate) fn create_entity(&mut self, entity_name: String, class: u128, race: u128, abilities: AbilityScores, rng: &mut Rng) -> Result<&Entity, AssetKeyError<'_, Entity>> {
+        let mut e = Entity::new(entity_name, class, race, abilities, self, rng);
+        self.changed.insert((AssetKind::Entity, e.get_uuid()));
+        let entities = &mut self.entities;
+
+        create_and_check_dups!(e, entities, "d", self.active_campaign)
+    }
+
+    pub(crate) fn create_feat(&mut self, feat_name: String, prerequisites: Vec<FeatPrerequisite>, effects: Vec<FeatEffect>) -> Result<&Feat, AssetKeyError<'_, Feat>> {
+        let mut f = Feat::new(feat_name, prerequisites, effects);
+        self.changed.insert((AssetKind::Feat, f.get_uuid()));
+        let feats = &mut self.feats;
+
+        create_and_check_dups!(f, feats, "f", self.active_campaign)
+    }
+
+    pub(crate) fn create_monster(&mut self, monster_name: String, creature_type: CreatureType, challenge_rating: ChallengeRating) -> Result<&Monster, AssetKeyError<'_, Monster>> {
+        let mut m = Monster::new(monster_name, creature_type, challenge_rating);
+        self.changed.insert((AssetKind::Monster, m.get_uuid()));
+        let monsters = &mut self.monsters;
+
+        create_and_check_dups!(m, monsters, "m", self.active_campaign)
     }
 
-    pub(crate) fn create_entity(&mut self, entity_name: String, class: u128, race: u128, abilities: AbilityScores, rng: &mut Rng) -> Result<&Entity, AssetKeyError<Entity>> {
-        let e = Entity::new(entity_name, class, race, abilities, self, rng);
+    /// Create a new Entity belonging to `campaign` from a monster's stat block, as seen from that Campaign. 
+    /// Returns None if the monster cannot be found there. 
+    pub(crate) fn create_monster_entity(&mut self, monster: u128, campaign: Option<u128>, hp_mode: HitPointMode, rng: &mut Rng) -> Option<Result<&Entity, AssetKeyError<'_, Entity>>> {
+        if !self.is_visible(AssetKind::Monster, campaign, monster) {
+            return None;
+        }
+        let monster = self.resolve_in(AssetKind::Monster, campaign, monster);
+        let mut e = Entity::from_monster(self.monsters.get(&monster)?, hp_mode, rng);
+        self.changed.insert((AssetKind::Entity, e.get_uuid()));
         let entities = &mut self.entities;
 
-        create_and_check_dups!(e, entities, "d")
+        Some(create_and_check_dups!(e, entities, "d", campaign))
     }
 
-    pub(crate) fn create_race(&mut self, race_name: String, speed: u8) -> Result<&Race, AssetKeyError<Race>> {
-        let r = Race::new(race_name, speed);
+    pub(crate) fn create_race(&mut self, race_name: String, speed: u8) -> Result<&Race, AssetKeyError<'_, Race>> {
+        let mut r = Race::new(race_name, speed);
+        self.changed.insert((AssetKind::Race, r.get_uuid()));
         let races = &mut self.races; 
 
-        create_and_check_dups!(r, races, "r")
+        create_and_check_dups!(r, races, "r", self.active_campaign)
+    }
+
+    pub(crate) fn create_skill(&mut self, skill_name: String, ability: Ability) -> Result<&SkillDefinition, AssetKeyError<'_, SkillDefinition>> {
+        let mut s = SkillDefinition::new(skill_name, ability);
+        self.changed.insert((AssetKind::Skill, s.get_uuid()));
+        let skills = &mut self.skills;
+
+        create_and_check_dups!(s, skills, "s", self.active_campaign)
     }
 
 }
@@ -297,7 +907,7 @@ impl AssetManager {
 pub mod tests {
     use uuid::Uuid;
 
-    use crate::mechanics::dice::Dice;
+    use crate::{entities::{abilities::{Ability, SaveAttributes}, biography::Alignment, modifier::{Modifier, ModifierKind, Stat}, proficiencies::{ArmorCategory, WeaponCategory}, size::Size, skills::{Skill, SkillAttributes}}, mechanics::dice::Dice};
 
     use super::*;
 
@@ -344,6 +954,52 @@ pub mod tests {
         assert_eq!(e.get_class_name(&am).unwrap(), c1.get_name());
         assert_eq!(e.get_race_name(&am).unwrap(), r1.get_name());
 
+        // Its race and class contribute modifiers to its stats 
+        assert_eq!(e.get_armor_class(&am).unwrap(), 15);
+        assert_eq!(e.get_stat(Stat::Save(Ability::Strength), &am).unwrap(), 8);
+        assert!(e.explain_stat(Stat::Skill(Skill::PERCEPTION), &am).unwrap().has_advantage());
+
+        // Its skills were saved in the old positional format 
+        assert_eq!(e.get_skill_attribute(Skill::ANIMAL_HANDLING), SkillAttributes::HalfProficient);
+        assert_eq!(e.get_skill_attribute(Skill::ATHLETICS), SkillAttributes::Expertise);
+        assert_eq!(e.get_skill_attribute(Skill::STEALTH), SkillAttributes::Normal);
+
+        // Its saves are keyed by name, with the rest left out 
+        assert_eq!(e.get_save_attribute(Ability::Dexterity), SaveAttributes::Proficient);
+        assert_eq!(e.get_save_attribute(Ability::Strength), SaveAttributes::Normal);
+
+        // Its biography is optional and was only partly filled in 
+        assert_eq!(e.get_size(), Size::Small);
+        assert_eq!(e.get_biography().get_alignment(), Some(Alignment::LawfulNeutral));
+        assert_eq!(e.get_biography().get_age(), Some(42));
+        assert!(e.get_biography().get_flaws().is_empty());
+
+        // Its race and class also contribute proficiencies 
+        let proficiencies = e.get_proficiencies(&am).unwrap();
+        assert!(proficiencies.knows_language("Common"));
+        assert!(proficiencies.knows_language("Elvish"));
+        assert!(proficiencies.is_proficient_with_weapon(WeaponCategory::Martial));
+        assert!(proficiencies.is_proficient_with_armor(ArmorCategory::Heavy));
+        assert_eq!(e.get_tool_check("Smith's Tools", Ability::Strength, &am).unwrap(), 8);
+        assert_eq!(e.get_attack_bonus(WeaponCategory::Martial, Ability::Strength, &am).unwrap(), 8);
+
+        // We loaded one feat: make sure its prerequisites and effects are correct 
+        assert_eq!(am.feats.len(), 1);
+        let f = am.feats.get(&0x0000000000000000000000000000fea7u128).unwrap();
+        assert_eq!(f.get_name(), String::from("Global Feat 1"));
+        assert_eq!(f.get_prerequisites().len(), 1);
+        assert_eq!(f.get_effects().len(), 2);
+
+        // We loaded one background 
+        assert_eq!(am.backgrounds.len(), 1);
+        let b = am.get_background(0x00000000000000000000000000000bac).unwrap();
+        assert_eq!(b.get_name(), "Global Background 1");
+        assert_eq!(b.get_skills(), [Skill::INSIGHT, Skill::RELIGION]);
+        assert_eq!(b.get_gold(), 15);
+
+        // Only the standard skills are defined globally 
+        assert_eq!(am.get_skills().len(), 18);
+
         // We loaded 2 campaign descriptions: make sure they look right 
         assert_eq!(am.campaign_descriptions.len(), 2);
 
@@ -388,6 +1044,15 @@ pub mod tests {
 
         let bad_entity = am.get_entity(55u128);
         assert!(bad_entity.is_none());
+
+        let bad_feat = am.get_feat(55u128);
+        assert!(bad_feat.is_none());
+
+        let bad_skill = am.get_skill(Skill::from_uuid(55u128));
+        assert!(bad_skill.is_none());
+
+        let bad_background = am.get_background(55u128);
+        assert!(bad_background.is_none());
     }
 
     #[test]
@@ -424,6 +1089,26 @@ pub mod tests {
         let entity = am.get_entity(entity_uuid).unwrap();
 
         assert_eq!(entity.get_name(), "Test Entity Name");
+
+        let Ok(feat) = am.create_feat(String::from("Test Feat Name"), Vec::new(), Vec::new()) else { panic!() };
+        let feat_uuid = feat.get_uuid();
+        let feat = am.get_feat(feat_uuid).unwrap();
+
+        assert_eq!(feat.get_name(), "Test Feat Name");
+
+        let Ok(skill) = am.create_skill(String::from("Test Skill Name"), Ability::Intelligence) else { panic!() };
+        let skill = skill.get_skill();
+        let skill = am.get_skill(skill).unwrap();
+
+        assert_eq!(skill.get_name(), "Test Skill Name");
+        assert_eq!(skill.get_ability(), Ability::Intelligence);
+        assert_eq!(am.get_skills().len(), 19);
+
+        let Ok(background) = am.create_background(String::from("Test Background Name")) else { panic!() };
+        let background_uuid = background.get_uuid();
+        let background = am.get_background(background_uuid).unwrap();
+
+        assert_eq!(background.get_name(), "Test Background Name");
     }
 
     #[test]
@@ -476,9 +1161,257 @@ pub mod tests {
 
         assert_eq!(am.races.len(), 4); // One Default race, two Global, one Local to the first Campaign
 
-        assert_eq!(am.entities.len(), 2); // One Global Entity, one Local to this Campaign 
+        assert_eq!(am.entities.len(), 3); // One Global Entity, two Local to this Campaign 
         let entity = am.entities.get(&0xff00ff00ff00ff00ff00ff00ff00ff00).unwrap();
         assert_eq!(entity.get_name(), "Local Entity 1 (campaign_2)");
+        assert_eq!(entity.get_skill_attribute(Skill::ATHLETICS), SkillAttributes::Expertise);
+
+        // This Campaign defines a homebrew skill, which its other Entity is proficient in 
+        let entity = am.entities.get(&0xff00ff00ff00ff00ff00ff00ff00c00c).unwrap();
+        let cooking = Skill::from_uuid(0xc00c);
+        assert_eq!(am.get_skills().len(), 19);
+        assert_eq!(am.get_skill(cooking).unwrap().get_name(), "Cooking");
+        assert_eq!(entity.get_skill_attribute(cooking), SkillAttributes::Proficient);
+        assert_eq!(entity.get_skill_attribute(Skill::ARCANA), SkillAttributes::Proficient);
+        assert_eq!(entity.get_skill_score(cooking, &am), Some(3 + 3)); // WIS 16, proficiency +3 at level 8
+
+        // Saves can be keyed by ability as well as listed in order 
+        assert_eq!(entity.get_save_attribute(Ability::Wisdom), SaveAttributes::Proficient);
+        assert_eq!(entity.get_save_attribute(Ability::Dexterity), SaveAttributes::Normal);
+    }
+
+    #[test]
+    pub fn campaign_scoping() {
+        let mut am = AssetManager::new(Path::new("test/assets")).unwrap();
+        let (campaign_1, campaign_2) = (0x00001111222233334444123412341234, 0xffff1111222233334444123412341234);
+        let (racey, classo, goblin) = (0x12341234123412341234123412341234, 0xf0000000f000f000f000f00000000000, 0xb0b1);
+        assert_eq!(am.get_active_campaign(), None);
+        assert!(!am.set_active_campaign(Some(campaign_1)));
+
+        // Local Assets know where they came from 
+        am.load_campaign(campaign_1);
+        assert_eq!(am.get_active_campaign(), Some(campaign_1));
+        assert_eq!(am.get_race(racey).unwrap().get_owning_campaign(), Some(campaign_1));
+        assert_eq!(am.get_race(0xaaaaaaaabbbbccccddddeeeeeeeeeeee).unwrap().get_owning_campaign(), None);
+        assert_eq!(am.get_campaign(campaign_1).unwrap().get_owning_campaign(), None);
+
+        // Switching Campaigns hides the other one's local Assets 
+        am.load_campaign(campaign_2);
+        assert!(am.get_race(racey).is_none() && am.get_monster(goblin).is_none());
+        assert!(am.get_monsters().iter().all(|m| m.get_owning_campaign().is_none()));
+        assert_eq!(am.get_class(classo).unwrap().get_owning_campaign(), Some(campaign_2));
+        assert_eq!(am.get_skills().len(), 19);
+
+        assert!(am.set_active_campaign(Some(campaign_1)));
+        assert!(am.get_race(racey).is_some() && am.get_class(classo).is_none());
+        assert_eq!(am.get_skills().len(), 18);
+        assert!(am.set_active_campaign(None));
+        assert!(am.get_race(racey).is_none());
+
+        // Unloading drops the Campaign and everything local to it 
+        assert!(am.unload_campaign(campaign_2));
+        assert!(!am.unload_campaign(campaign_2));
+        assert!(am.get_campaign(campaign_2).is_none());
+        assert!(!am.classes.contains_key(&classo));
+        assert!(am.races.contains_key(&racey));
+        assert!(am.set_active_campaign(Some(campaign_1)));
+        assert!(am.unload_campaign(campaign_1));
+        assert_eq!(am.get_active_campaign(), None);
+        assert_eq!((am.classes.len(), am.races.len(), am.monsters.len()), (3, 3, 1));
+    }
+
+    /// Lay out an asset root's Campaigns: the manifest, and a directory and Campaign file named after each. 
+    fn write_campaigns(root: &Path, campaigns: &[(u128, &str)]) {
+        fs::create_dir_all(root.join("campaigns")).unwrap();
+        let manifest: String = campaigns.iter()
+            .map(|(uuid, dir)| format!("- uuid: {:032x}\n  name: {}\n  path: {}/\n  description: ''\n", uuid, dir, dir))
+            .collect();
+        fs::write(root.join("campaigns/manifest.panic"), if manifest.is_empty() { String::from("[]") } else { manifest }).unwrap();
+
+        for (uuid, dir) in campaigns {
+            fs::create_dir_all(root.join("campaigns").join(dir)).unwrap();
+            fs::write(root.join(format!("campaigns/{0}/{0}.panic", dir)), 
+                format!("uuid: {:032x}\nname: {}\ndescription: ''\nscenes: {{}}\nactive_scene: null\n", uuid, dir)).unwrap();
+        }
+    }
+
+    /// A level 1 Entity file with the given class and race, and ordinary ability scores. 
+    fn entity_file(uuid: u128, name: &str, class: u128, race: u128) -> String {
+        format!("uuid: {:032x}\nname: {}\nhp: 10\nhp_max: 10\nhp_temp: 0\nlevel: 1\nclass: 0x{:032x}\nrace: 0x{:032x}\n\
+            abilities:\n  strength: 10\n  dexterity: 10\n  constitution: 10\n  intelligence: 10\n  wisdom: 10\n  charisma: 10\n\
+            skills: {{}}\nsaves: {{}}\n", uuid, name, class, race)
+    }
+
+    #[test]
+    pub fn campaign_overrides() {
+        let root = tempdir::TempDir::new("panicvtt_overrides").unwrap();
+        let global_class = 0x00000000111122223333444444444444u128;
+        let (homebrew, plain) = (0x00000000000000000000000000000001u128, 0x00000000000000000000000000000002u128);
+        write_campaigns(root.path(), &[(homebrew, "homebrew"), (plain, "plain")]);
+        fs::create_dir_all(root.path().join("classes")).unwrap();
+        fs::create_dir_all(root.path().join("campaigns/homebrew/classes")).unwrap();
+        fs::write(root.path().join("classes/fighter.panic"), format!("uuid: {:032x}\nname: Fighter\nhit_die: D10\n", global_class)).unwrap();
+        fs::write(root.path().join("campaigns/homebrew/classes/fighter.panic"), 
+            format!("uuid: {:032x}\noverrides: 0x{:032x}\nname: Tough Fighter\nhit_die: D12\n", 0xf16u128, global_class)).unwrap();
+        let global_hero = 0x4e4fu128;
+        fs::create_dir_all(root.path().join("entities")).unwrap();
+        fs::create_dir_all(root.path().join("campaigns/homebrew/entities")).unwrap();
+        fs::write(root.path().join("entities/hero.panic"), entity_file(global_hero, "Hero", global_class, 0)).unwrap();
+        fs::write(root.path().join("campaigns/homebrew/entities/hero.panic"), 
+            format!("{}overrides: 0x{:032x}\n", entity_file(0x4e40, "Homebrew Hero", global_class, 0), global_hero)).unwrap();
+        let (global_goblin, homebrew_goblin) = (0x90b1u128, 0x90b2u128);
+        let goblin = |uuid: u128, name: &str| format!("uuid: {:032x}\nname: {}\ncreature_type: Humanoid\nchallenge_rating: 1/4\n\
+            armor_class: 15\nhit_dice: 2d6\nspeed: 30\nabilities: {{ strength: 8, dexterity: 14, constitution: 10, intelligence: 10, wisdom: 8, charisma: 8 }}\n", uuid, name);
+        fs::create_dir_all(root.path().join("monsters")).unwrap();
+        fs::create_dir_all(root.path().join("campaigns/homebrew/monsters")).unwrap();
+        fs::write(root.path().join("monsters/goblin.panic"), goblin(global_goblin, "Goblin")).unwrap();
+        fs::write(root.path().join("campaigns/homebrew/monsters/goblin.panic"), 
+            format!("{}overrides: 0x{:032x}\n", goblin(homebrew_goblin, "Homebrew Goblin"), global_goblin)).unwrap();
+
+        // The homebrew Campaign sees its own Fighter under the global UUID 
+        let mut am = AssetManager::new(root.path()).unwrap();
+        am.load_campaign(homebrew);
+        let class = am.get_class(global_class).unwrap();
+        assert_eq!((class.get_name(), class.get_hit_die()), ("Tough Fighter", Dice::D12));
+        assert_eq!(class.get_overridden_asset(), Some(global_class));
+        let names: Vec<&str> = am.get_classes().iter().map(|c| c.get_name()).collect();
+        assert_eq!(names, ["", "Tough Fighter"]);
+
+        // Changes to the Fighter's Entity land on the homebrew copy, which levels up with the homebrew hit die 
+        let mut rng = Rng::new(0, 1);
+        let gained = am.update_entity(global_hero, |e, am| e.level_up(am, &mut rng)).unwrap().unwrap();
+        assert!((1..=12).contains(&gained));
+        assert_eq!(am.get_entity(global_hero).unwrap().get_level(), 2);
+        assert_eq!(am.entities[&global_hero].get_level(), 1);
+        assert!(am.changed.contains(&(AssetKind::Entity, 0x4e40)));
+        assert!(!am.changed.contains(&(AssetKind::Entity, global_hero)));
+
+        // Every other Campaign sees the original 
+        am.load_campaign(plain);
+        assert_eq!(am.get_class(global_class).unwrap().get_hit_die(), Dice::D10);
+        assert!(am.get_class(0xf16).is_none());
+        let names: Vec<&str> = am.get_classes().iter().map(|c| c.get_name()).collect();
+        assert_eq!(names, ["", "Fighter"]);
+
+        // Monsters placed in a Campaign use its stat blocks, even while another Campaign is active 
+        let spawn = am.create_monster_entity(global_goblin, Some(homebrew), HitPointMode::Average, &mut rng).unwrap().unwrap();
+        assert_eq!((spawn.get_name(), spawn.get_owning_campaign()), ("Homebrew Goblin", Some(homebrew)));
+        let spawn = am.create_monster_entity(global_goblin, Some(plain), HitPointMode::Average, &mut rng).unwrap().unwrap();
+        assert_eq!((spawn.get_name(), spawn.get_owning_campaign()), ("Goblin", Some(plain)));
+        assert!(am.create_monster_entity(homebrew_goblin, Some(plain), HitPointMode::Average, &mut rng).is_none());
+
+        // Unloading the homebrew Campaign forgets its overrides 
+        assert_eq!(am.overrides.len(), 3);
+        assert!(am.unload_campaign(homebrew));
+        assert!(am.overrides.is_empty());
+    }
+
+    #[test]
+    pub fn duplicate_uuids() {
+        let root = tempdir::TempDir::new("panicvtt_duplicates").unwrap();
+        let (uuid, campaign) = (0x00000000000000000000000000000c1au128, 0x00000000000000000000000000000ca1u128);
+        let class = |name: &str| format!("uuid: {:032x}\nname: {}\nhit_die: D8\n", uuid, name);
+        write_campaigns(root.path(), &[(campaign, "clash")]);
+        fs::create_dir_all(root.path().join("classes/nested")).unwrap();
+        fs::create_dir_all(root.path().join("campaigns/clash/classes")).unwrap();
+        fs::write(root.path().join("classes/a.panic"), class("First")).unwrap();
+        fs::write(root.path().join("classes/nested/b.panic"), class("Second")).unwrap();
+        fs::write(root.path().join("campaigns/clash/classes/c.panic"), class("Local")).unwrap();
+
+        // By default loading fails, naming both files 
+        let Err(e) = AssetManager::new(root.path()) else { panic!() };
+        let message = e.to_string();
+        assert!(message.contains("a.panic") && message.contains("b.panic"));
+        let e = e.get_ref().unwrap().downcast_ref::<DuplicateAssetError>().unwrap();
+        assert_eq!(e.get_uuid(), uuid);
+        assert!(e.get_first_path().ends_with("classes/a.panic"));
+
+        // Or the first or last file in path order wins 
+        let am = AssetManager::with_duplicate_policy(root.path(), DuplicatePolicy::KeepFirst).unwrap();
+        assert_eq!(am.get_class(uuid).unwrap().get_name(), "First");
+        assert_eq!(am.get_duplicates().len(), 1);
+        assert!(am.get_duplicates()[0].get_second_path().ends_with("classes/nested/b.panic"));
+
+        let mut am = AssetManager::with_duplicate_policy(root.path(), DuplicatePolicy::KeepLast).unwrap();
+        assert_eq!(am.get_class(uuid).unwrap().get_name(), "Second");
+
+        // Campaign-local Assets may never replace global ones, and a failed load leaves nothing behind 
+        let other = uuid + 1;
+        fs::write(root.path().join("campaigns/clash/classes/a.panic"), format!("uuid: {:032x}\nname: Other\nhit_die: D6\n", other)).unwrap();
+        match am.load_campaign(campaign) {
+            LoadAssetResult::IoError { e } => assert!(e.to_string().contains("c.panic")),
+            _ => panic!(),
+        };
+        assert_eq!(am.get_class(uuid).unwrap().get_name(), "Second");
+        assert!(am.get_class(other).is_none());
+        assert!(am.get_campaign(campaign).is_none());
+        assert_eq!(am.get_duplicates().len(), 1);
+
+        fs::remove_file(root.path().join("classes/nested/b.panic")).unwrap();
+        let mut am = AssetManager::new(root.path()).unwrap();
+        match am.load_campaign(campaign) {
+            LoadAssetResult::IoError { e } => assert!(e.to_string().contains("c.panic")),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    pub fn dangling_references() {
+        // The test assets are all consistent 
+        let mut am = AssetManager::new(Path::new("test/assets")).unwrap();
+        for campaign in [0x00001111222233334444123412341234, 0xffff1111222233334444123412341234] {
+            assert!(matches!(am.load_campaign(campaign), LoadAssetResult::Ok { .. }));
+        }
+        assert_eq!(am.validate(), []);
+
+        let root = tempdir::TempDir::new("panicvtt_dangling").unwrap();
+        let (fighter, local, missing, hero, campaign) = (0xf1u128, 0x10cau128, 0xdeadu128, 0x4e40u128, 0xca1u128);
+        write_campaigns(root.path(), &[(campaign, "loose")]);
+        fs::create_dir_all(root.path().join("classes")).unwrap();
+        fs::create_dir_all(root.path().join("entities")).unwrap();
+        fs::create_dir_all(root.path().join("campaigns/loose/classes")).unwrap();
+        fs::write(root.path().join("classes/fighter.panic"), format!("uuid: {:032x}\nname: Fighter\nhit_die: D10\n", fighter)).unwrap();
+        fs::write(root.path().join("entities/hero.panic"), entity_file(hero, "Hero", local, missing)).unwrap();
+        let campaign_file = root.path().join("campaigns/loose/loose.panic");
+        let party = format!("party: ['{:032x}', '{:032x}']\n", hero, missing);
+        fs::write(&campaign_file, fs::read_to_string(&campaign_file).unwrap() + &party).unwrap();
+        fs::write(root.path().join("campaigns/loose/classes/local.panic"), 
+            format!("uuid: {:032x}\nname: Local\nhit_die: D6\noverrides: 0x{:032x}\n", local, missing)).unwrap();
+
+        // A global Entity can't see a Campaign's classes, even while it is loaded 
+        let mut am = AssetManager::new(root.path()).unwrap();
+        assert!(matches!(am.load_campaign(campaign), LoadAssetResult::Ok { .. }));
+        let report = am.validate();
+        let found: Vec<(&str, u128)> = report.iter().map(|r| (r.get_field(), r.get_target())).collect();
+        assert_eq!(found, [("overrides", missing), ("party", missing), ("class", local), ("race", missing)]);
+        assert!(report[1].get_path().unwrap().ends_with("campaigns/loose/loose.panic"));
+        assert!(report[2].to_string().ends_with(&format!("entities/hero.panic: class refers to {:032x}, which belongs to another Campaign", local)));
+        assert!(report[3].to_string().ends_with(&format!("entities/hero.panic: race refers to {:032x}, which is not loaded", missing)));
+        assert!(report[2].is_loaded() && !report[3].is_loaded());
+
+        // Newly created Assets haven't got a file yet 
+        let feat = am.create_feat(String::from("Unsaved"), vec![FeatPrerequisite::Race { race: missing }], Vec::new()).unwrap().get_uuid();
+        let report = am.validate();
+        assert_eq!(report.len(), 5);
+        assert!(report.iter().any(|r| r.get_asset() == feat && r.get_field() == "prerequisites" && r.get_path().is_none()));
+    }
+
+    #[test]
+    pub fn class_modifiers_apply_to_scores() {
+        let mut am = AssetManager::from_test_config();
+        let monk = Class::new(String::from("Monk"), Dice::D8).with_modifiers(vec![
+            Modifier::new(Stat::Save(Ability::Wisdom), ModifierKind::Flat(2)),
+            Modifier::new(Stat::Skill(Skill::STEALTH), ModifierKind::Proficiency(SkillAttributes::Proficient)),
+        ]);
+        let monk = am.classes.entry(monk.get_uuid()).or_insert(monk).get_uuid();
+        let race = am.get_testing_race().get_uuid();
+        let mut rng = Rng::new(0, 1);
+        let entity = Entity::new(String::new(), monk, race, AbilityScores::from_defaults(), &am, &mut rng);
+
+        assert_eq!(entity.get_save_score(Ability::Wisdom, &am), Some(2));
+        assert_eq!(entity.get_save_score(Ability::Strength, &am), Some(0));
+        assert_eq!(entity.get_skill_score(Skill::STEALTH, &am), Some(2));
+        assert_eq!(entity.get_skill_scores(&am).unwrap()[&Skill::STEALTH], 2);
     }
 
     #[test]
@@ -503,4 +1436,123 @@ pub mod tests {
         };
     }
 
+
+    #[test]
+    pub fn monsters() {
+        let mut am = AssetManager::new(Path::new("test/assets")).unwrap();
+        assert_eq!(am.monsters.len(), 1);
+
+        let dragon = am.get_monster(0xd4a6).unwrap();
+        assert_eq!(dragon.get_size(), Size::Large);
+        assert_eq!(dragon.get_alignment(), Some(Alignment::ChaoticEvil));
+        assert_eq!(dragon.get_xp(), 5900);
+        assert_eq!(dragon.get_hit_dice().to_string(), "17d10+85");
+        assert_eq!(dragon.get_saves().len(), 4);
+        assert_eq!(dragon.get_skills().get(&Skill::PERCEPTION), Some(&SkillAttributes::Expertise));
+        assert_eq!(dragon.get_actions()[1].get_description(), "");
+        assert_eq!(dragon.get_legendary_actions()[1].get_cost(), 2);
+
+        // Campaign 1 brings a goblin 
+        am.load_campaign(0x00001111222233334444123412341234);
+        let goblin = am.get_monster(0xb0b1).unwrap();
+        assert_eq!(goblin.get_challenge_rating(), ChallengeRating::QUARTER);
+        assert_eq!(goblin.get_xp(), 50);
+    }
+
+    #[test]
+    pub fn created_assets_belong_to_active_campaign() {
+        let mut am = AssetManager::from_test_config();
+        let mut rng = Rng::new(0, 0);
+        let global = am.create_race(String::from("Global Race"), 30).unwrap().get_uuid();
+        let campaign = am.create_campaign(String::from("Local"), String::new()).unwrap().get_uuid();
+        assert!(am.set_active_campaign(Some(campaign)));
+
+        let background = am.create_background(String::from("Local Background")).unwrap().get_uuid();
+        let class = am.create_class(String::from("Local Class"), Dice::D8).unwrap().get_uuid();
+        let race = am.create_race(String::from("Local Race"), 25).unwrap().get_uuid();
+        let entity = am.create_entity(String::from("Local Entity"), class, race, AbilityScores::from_defaults(), &mut rng).unwrap().get_uuid();
+        let feat = am.create_feat(String::from("Local Feat"), Vec::new(), Vec::new()).unwrap().get_uuid();
+        let monster = am.create_monster(String::from("Local Monster"), CreatureType::Beast, ChallengeRating::HALF).unwrap().get_uuid();
+        let spawn = am.create_monster_entity(monster, Some(campaign), HitPointMode::Average, &mut rng).unwrap().unwrap().get_uuid();
+        let skill = am.create_skill(String::from("Local Skill"), Ability::Wisdom).unwrap().get_uuid();
+
+        let owners = [
+            am.get_background(background).unwrap().get_owning_campaign(),
+            am.get_class(class).unwrap().get_owning_campaign(),
+            am.get_race(race).unwrap().get_owning_campaign(),
+            am.get_entity(entity).unwrap().get_owning_campaign(),
+            am.get_feat(feat).unwrap().get_owning_campaign(),
+            am.get_monster(monster).unwrap().get_owning_campaign(),
+            am.get_entity(spawn).unwrap().get_owning_campaign(),
+            am.get_skill(Skill::from_uuid(skill)).unwrap().get_owning_campaign(),
+        ];
+        assert!(owners.iter().all(|o| *o == Some(campaign)));
+
+        // Campaigns and Assets made before it was active stay global, and the rest are hidden once it isn't 
+        assert_eq!(am.get_campaign(campaign).unwrap().get_owning_campaign(), None);
+        assert_eq!(am.get_race(global).unwrap().get_owning_campaign(), None);
+        am.set_active_campaign(None);
+        assert!(am.get_class(class).is_none());
+        assert!(am.get_entity(spawn).is_none());
+        assert!(am.get_race(global).is_some());
+    }
+
+    #[test]
+    pub fn save_and_reload() {
+        let root = tempdir::TempDir::new("panicvtt_save").unwrap();
+        write_campaigns(root.path(), &[]);
+
+        // Create one of a few Assets and save them 
+        let mut am = AssetManager::new(root.path()).unwrap();
+        let mut rng = Rng::new(0, 0);
+        let class = am.create_class(String::from("Saved Class"), Dice::D10).unwrap().get_uuid();
+        let race = am.create_race(String::from("Saved Race"), 30).unwrap().get_uuid();
+        let entity = am.create_entity(String::from("Saved Entity"), class, race, AbilityScores::from_defaults(), &mut rng).unwrap().get_uuid();
+        let campaign = am.create_campaign(String::from("Saved Campaign!"), String::from("Kept on disk")).unwrap().get_uuid();
+        am.get_campaign_mut(campaign).unwrap().create_scene(String::from("Saved Scene"));
+        am.set_active_campaign(Some(campaign));
+        let local = am.create_class(String::from("Local Class"), Dice::D6).unwrap().get_uuid();
+        assert_eq!(am.save().unwrap(), 5);
+        assert_eq!(am.save().unwrap(), 0);
+        assert!(root.path().join("campaigns/saved_campaign/saved_campaign.panic").is_file());
+        assert!(root.path().join(format!("campaigns/saved_campaign/classes/{}.panic", Uuid::from_u128(local).simple())).is_file());
+
+        // Nothing is left half-written 
+        let temp_files = fs::read_dir(root.path().join("entities")).unwrap()
+            .filter(|f| f.as_ref().unwrap().path().extension().unwrap_or_default() == "tmp")
+            .count();
+        assert_eq!(temp_files, 0);
+
+        // Everything comes back, and changes are written over the same file 
+        let mut am = AssetManager::new(root.path()).unwrap();
+        assert_eq!(am.get_class(class).unwrap().get_name(), "Saved Class");
+        assert_eq!(am.get_entity(entity).unwrap().get_race_name(&am).unwrap(), "Saved Race");
+        am.update_entity(entity, |e, _| e.set_size(Size::Large));
+        assert_eq!(am.save().unwrap(), 1);
+        assert_eq!(fs::read_dir(root.path().join("entities")).unwrap().count(), 1);
+        assert_eq!(AssetManager::new(root.path()).unwrap().get_entity(entity).unwrap().get_size(), Size::Large);
+
+        let description = am.get_campaign_descriptions()[0];
+        assert_eq!((description.get_name(), description.get_path()), ("Saved Campaign!", "saved_campaign/"));
+        match am.load_campaign(campaign) {
+            LoadAssetResult::Ok { asset } => assert_eq!(asset.get_scenes()[0].get_name(), "Saved Scene"),
+            _ => panic!(),
+        };
+        assert_eq!(am.get_class(local).unwrap().get_owning_campaign(), Some(campaign));
+
+        // Only files actually written are counted 
+        am.changed.insert((AssetKind::Class, 0xdead));
+        assert_eq!(am.save().unwrap(), 0);
+
+        // A failed write doesn't leave its temporary file behind 
+        let path = am.asset_paths.get(&(AssetKind::Entity, entity)).unwrap().clone();
+        fs::remove_file(&path).unwrap();
+        fs::create_dir(&path).unwrap();
+        am.update_entity(entity, |e, _| e.set_size(Size::Medium));
+        assert!(am.save().is_err());
+        let temp_files = fs::read_dir(root.path().join("entities")).unwrap()
+            .filter(|f| f.as_ref().unwrap().path().extension().unwrap_or_default() == "tmp")
+            .count();
+        assert_eq!(temp_files, 0);
+    }
 }
@@ -0,0 +1,11 @@
+/// What to do when two Asset files share a UUID. 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Stop loading with an error naming both files. 
+    #[default]
+    Error, 
+    /// Keep the Asset read first, in path order, and record the clash. 
+    KeepFirst, 
+    /// Keep the Asset read last, in path order, and record the clash. 
+    KeepLast, 
+}
@@ -1,3 +1,11 @@
+pub mod area;
+pub mod attack;
 pub mod campaign;
 pub mod campaign_description;
-pub mod scene;
\ No newline at end of file
+pub mod difficulty;
+pub mod encounter;
+pub mod grid;
+pub mod lighting;
+pub mod scene;
+pub mod vision;
+pub mod walls;
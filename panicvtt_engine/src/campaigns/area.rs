@@ -0,0 +1,264 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::modifier::StatIntType;
+
+use super::grid::{Grid, GridPosition, GridType, Token};
+
+/// Slack for cells whose centres fall exactly on the edge of an area.
+const EPSILON: f32 = 0.001;
+
+/// The shape of an area of effect, with sizes in feet.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum AreaShape {
+    /// Spreads in every direction from the top left corner of the origin cell, or its centre on a hex grid.
+    Sphere { radius: u16 },
+    /// `size` feet on a side, extending right and down from the top left corner of the origin cell.
+    Cube { size: u16 },
+    /// Widens from the origin cell toward the target, as wide as it is long at any point. The origin cell is not included.
+    Cone { length: u16 },
+    /// Runs from the origin cell toward the target. The origin cell is not included.
+    Line { length: u16, width: u16 },
+    /// Covers the map like a Sphere, rising `height` feet from the area's elevation.
+    Cylinder { radius: u16, height: u16 },
+}
+
+/// An area of effect placed on a Scene's grid.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AreaOfEffect {
+    shape: AreaShape,
+    origin: GridPosition,
+    /// The cell a Cone or Line points toward.
+    #[serde(default)]
+    target: GridPosition,
+    #[serde(default)]
+    elevation: i16,
+}
+
+impl AreaOfEffect {
+    pub fn new(shape: AreaShape, origin: GridPosition) -> Self {
+        Self { shape, origin, target: origin, elevation: 0 }
+    }
+
+    pub fn with_target(mut self, target: GridPosition) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_elevation(mut self, elevation: i16) -> Self {
+        self.elevation = elevation;
+        self
+    }
+
+    pub fn get_shape(&self) -> AreaShape {
+        self.shape
+    }
+
+    pub fn get_origin(&self) -> GridPosition {
+        self.origin
+    }
+
+    pub fn get_target(&self) -> GridPosition {
+        self.target
+    }
+
+    pub fn get_elevation(&self) -> i16 {
+        self.elevation
+    }
+
+    /// The top left corner of the origin cell on a square grid, or its centre on a hex grid.
+    fn get_corner(&self, grid: &Grid) -> (f32, f32) {
+        match grid.get_grid_type() {
+            GridType::Square => (self.origin.get_x() as f32, self.origin.get_y() as f32),
+            GridType::Hex => grid.get_cell_center(self.origin),
+        }
+    }
+
+    /// Whether a cell's centre lies within the area.
+    fn covers(&self, grid: &Grid, cell: GridPosition) -> bool {
+        let cells = |feet: u16| feet as f32 / grid.get_cell_size() as f32 + EPSILON;
+        let (cx, cy) = grid.get_cell_center(cell);
+
+        match self.shape {
+            AreaShape::Sphere { radius } | AreaShape::Cylinder { radius, .. } => {
+                let (ox, oy) = self.get_corner(grid);
+                grid.measure(cx - ox, cy - oy) <= cells(radius)
+            },
+            AreaShape::Cube { size } => {
+                let (ox, oy) = self.get_corner(grid);
+                let (dx, dy) = (cx - ox, cy - oy);
+                dx >= -EPSILON && dy >= -EPSILON && dx < cells(size) - 2.0 * EPSILON && dy < cells(size) - 2.0 * EPSILON
+            },
+            AreaShape::Cone { length } | AreaShape::Line { length, .. } => {
+                let (ox, oy) = grid.get_cell_center(self.origin);
+                let (tx, ty) = grid.get_cell_center(self.target);
+                let axis = (tx - ox).hypot(ty - oy);
+                if axis == 0.0 {
+                    return false;
+                }
+
+                // Distance along the axis and out to either side of it
+                let (dx, dy) = (cx - ox, cy - oy);
+                let along = (dx * (tx - ox) + dy * (ty - oy)) / axis;
+                let across = (dx * (ty - oy) - dy * (tx - ox)).abs() / axis;
+                let half_width = match self.shape {
+                    AreaShape::Line { width, .. } => cells(width) / 2.0,
+                    _ => along / 2.0 + EPSILON,
+                };
+
+                along > EPSILON && grid.measure(dx, dy) <= cells(length) && across <= half_width
+            },
+        }
+    }
+
+    /// Every cell on the grid the area covers.
+    pub fn get_cells(&self, grid: &Grid) -> BTreeSet<GridPosition> {
+        (0..grid.get_height())
+            .flat_map(|y| (0..grid.get_width()).map(move |x| GridPosition::new(x, y)))
+            .filter(|cell| self.covers(grid, *cell))
+            .collect()
+    }
+
+    /// Whether something at `elevation` is within the area's vertical reach.
+    pub fn covers_elevation(&self, elevation: i16) -> bool {
+        let height = elevation as i32 - self.elevation as i32;
+        match self.shape {
+            AreaShape::Sphere { radius } => height.abs() <= radius as i32,
+            AreaShape::Cube { size } => (0..size as i32).contains(&height),
+            AreaShape::Cone { length } | AreaShape::Line { length, .. } => height.abs() <= length as i32,
+            AreaShape::Cylinder { height: top, .. } => (0..=top as i32).contains(&height),
+        }
+    }
+
+    /// The Entities whose tokens stand in any covered cell at a covered elevation.
+    pub fn get_tokens(&self, grid: &Grid, tokens: &[Token]) -> Vec<u128> {
+        let cells = self.get_cells(grid);
+        tokens.iter()
+            .filter(|t| self.covers_elevation(t.get_elevation()) && t.get_cells().any(|c| cells.contains(&c)))
+            .map(|t| t.get_entity())
+            .collect()
+    }
+}
+
+/// How one target fared against a saving throw for half damage.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct SaveResult {
+    #[serde(with = "uuid::serde::simple")]
+    entity: Uuid,
+    /// The d20 kept after advantage or disadvantage.
+    roll: u8,
+    total: StatIntType,
+    success: bool,
+    /// Hit points actually lost, after temporary hit points.
+    damage: u16,
+}
+
+impl SaveResult {
+    pub(crate) fn new(entity: u128, roll: u8, total: StatIntType, success: bool, damage: u16) -> Self {
+        Self { entity: Uuid::from_u128(entity), roll, total, success, damage }
+    }
+
+    pub fn get_entity(&self) -> u128 {
+        self.entity.as_u128()
+    }
+
+    pub fn get_roll(&self) -> u8 {
+        self.roll
+    }
+
+    pub fn get_total(&self) -> StatIntType {
+        self.total
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.success
+    }
+
+    pub fn get_damage(&self) -> u16 {
+        self.damage
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::campaigns::grid::DiagonalRule;
+
+    use super::*;
+
+    fn square(rule: DiagonalRule) -> Grid {
+        Grid::new(GridType::Square, 20, 20).with_diagonal_rule(rule)
+    }
+
+    #[test]
+    pub fn spheres() {
+        // A 20 ft. radius from an intersection covers an 8 by 8 square when diagonals cost 5 ft.
+        let fireball = AreaOfEffect::new(AreaShape::Sphere { radius: 20 }, GridPosition::new(10, 10));
+        let cells = fireball.get_cells(&square(DiagonalRule::Simple));
+        assert_eq!(cells.len(), 64);
+        assert!(cells.contains(&GridPosition::new(6, 6)) && cells.contains(&GridPosition::new(13, 13)));
+        assert!(!cells.contains(&GridPosition::new(14, 10)));
+
+        // The other rules round off the corners
+        let alternating = fireball.get_cells(&square(DiagonalRule::Alternating));
+        let euclidean = fireball.get_cells(&square(DiagonalRule::Euclidean));
+        assert!(!alternating.contains(&GridPosition::new(6, 6)) && !euclidean.contains(&GridPosition::new(6, 6)));
+        assert!(alternating.len() < 64 && euclidean.len() < 64);
+
+        // Clipped at the edge of the map
+        let corner = AreaOfEffect::new(AreaShape::Sphere { radius: 10 }, GridPosition::new(0, 0));
+        assert_eq!(corner.get_cells(&square(DiagonalRule::Simple)).len(), 4);
+
+        // On a hex grid, a 5 ft. radius covers the centre and its six neighbours
+        let hex = Grid::new(GridType::Hex, 10, 10);
+        let cells = AreaOfEffect::new(AreaShape::Sphere { radius: 5 }, GridPosition::new(4, 4)).get_cells(&hex);
+        assert_eq!(cells.len(), 7);
+        assert!(cells.iter().all(|c| hex.get_distance(*c, GridPosition::new(4, 4)) <= 5));
+    }
+
+    #[test]
+    pub fn cubes_cones_and_lines() {
+        let grid = square(DiagonalRule::Simple);
+        let cube = AreaOfEffect::new(AreaShape::Cube { size: 10 }, GridPosition::new(3, 3)).get_cells(&grid);
+        assert_eq!(cube.into_iter().collect::<Vec<_>>(), [(3, 3), (3, 4), (4, 3), (4, 4)].map(|(x, y)| GridPosition::new(x, y)));
+
+        // A 15 ft. cone pointing right is 1, 3 and 3 cells wide
+        let cone = AreaOfEffect::new(AreaShape::Cone { length: 15 }, GridPosition::new(5, 5)).with_target(GridPosition::new(9, 5));
+        let cells = cone.get_cells(&grid);
+        assert_eq!(cells.len(), 7);
+        assert!(!cells.contains(&GridPosition::new(5, 5)));
+        assert!(cells.contains(&GridPosition::new(8, 4)) && !cells.contains(&GridPosition::new(9, 5)));
+
+        let line = AreaOfEffect::new(AreaShape::Line { length: 30, width: 5 }, GridPosition::new(5, 5)).with_target(GridPosition::new(5, 0));
+        assert_eq!(line.get_cells(&grid).into_iter().collect::<Vec<_>>(), (0..5).map(|y| GridPosition::new(5, y)).collect::<Vec<_>>());
+        let diagonal = AreaOfEffect::new(AreaShape::Line { length: 15, width: 5 }, GridPosition::new(0, 0)).with_target(GridPosition::new(9, 9));
+        assert_eq!(diagonal.get_cells(&grid).into_iter().collect::<Vec<_>>(), (1..4).map(|i| GridPosition::new(i, i)).collect::<Vec<_>>());
+
+        // Pointing a cone at its own origin covers nothing
+        assert!(AreaOfEffect::new(AreaShape::Cone { length: 15 }, GridPosition::new(5, 5)).get_cells(&grid).is_empty());
+    }
+
+    #[test]
+    pub fn tokens() {
+        let grid = square(DiagonalRule::Simple);
+        let mut flying = Token::new(3, GridPosition::new(9, 9), 1);
+        flying.set_elevation(30);
+        let tokens = [Token::new(1, GridPosition::new(10, 10), 1), Token::new(2, GridPosition::new(14, 8), 2), flying];
+
+        // The Large token only has one cell inside
+        let sphere = AreaOfEffect::new(AreaShape::Sphere { radius: 20 }, GridPosition::new(11, 11));
+        assert_eq!(sphere.get_tokens(&grid, &tokens), [1, 2]);
+
+        let pillar = AreaOfEffect::new(AreaShape::Cylinder { radius: 20, height: 40 }, GridPosition::new(11, 11));
+        assert_eq!(pillar.get_tokens(&grid, &tokens), [1, 2, 3]);
+        assert!(!pillar.with_elevation(35).covers_elevation(30));
+    }
+
+    #[test]
+    pub fn serde() {
+        let area = AreaOfEffect::new(AreaShape::Line { length: 60, width: 5 }, GridPosition::new(1, 2)).with_target(GridPosition::new(8, 2));
+        let de: AreaOfEffect = serde_yaml::from_str(&serde_yaml::to_string(&area).unwrap()).unwrap();
+        assert_eq!(de, area);
+    }
+}
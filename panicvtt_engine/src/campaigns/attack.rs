@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::modifier::StatIntType;
+
+use super::walls::Cover;
+
+/// The outcome of one attack roll against a target.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AttackResult {
+    #[serde(with = "uuid::serde::simple")]
+    target: Uuid,
+    cover: Cover,
+    /// The d20 rolled, or None if total cover stopped the attack being made.
+    roll: Option<u8>,
+    total: StatIntType,
+    /// The target's armor class, including its cover bonus.
+    armor_class: StatIntType,
+}
+
+impl AttackResult {
+    pub(crate) fn new(target: u128, cover: Cover, roll: Option<u8>, total: StatIntType, armor_class: StatIntType) -> Self {
+        Self { target: Uuid::from_u128(target), cover, roll, total, armor_class }
+    }
+
+    pub fn get_target(&self) -> u128 {
+        self.target.as_u128()
+    }
+
+    pub fn get_cover(&self) -> Cover {
+        self.cover
+    }
+
+    pub fn get_roll(&self) -> Option<u8> {
+        self.roll
+    }
+
+    pub fn get_total(&self) -> StatIntType {
+        self.total
+    }
+
+    pub fn get_armor_class(&self) -> StatIntType {
+        self.armor_class
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.roll == Some(20)
+    }
+
+    /// A natural 20 always hits and a natural 1 always misses.
+    pub fn is_hit(&self) -> bool {
+        match self.roll {
+            Some(20) => true,
+            Some(1) | None => false,
+            Some(_) => self.total >= self.armor_class,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn hits() {
+        assert!(AttackResult::new(1, Cover::Clear, Some(10), 15, 15).is_hit());
+        assert!(!AttackResult::new(1, Cover::Half, Some(10), 15, 17).is_hit());
+        assert!(!AttackResult::new(1, Cover::Clear, Some(1), 30, 10).is_hit());
+
+        let critical = AttackResult::new(1, Cover::ThreeQuarters, Some(20), 21, 30);
+        assert!(critical.is_hit() && critical.is_critical());
+        assert!(!AttackResult::new(1, Cover::Total, None, 0, 15).is_hit());
+    }
+}
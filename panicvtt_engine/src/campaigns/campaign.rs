@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -14,7 +14,15 @@ pub struct Campaign {
     name: String, 
     description: String, 
     scenes: HashMap<u128, Scene>, 
+    /// The order Scenes are listed in. Scenes missing from it are listed last. 
+    #[serde(default, with = "crate::util::simple_uuids")]
+    scene_order: Vec<Uuid>,
     active_scene: Option<u128>, 
+    #[serde(default, with = "crate::util::simple_uuids")]
+    party: Vec<Uuid>,
+    /// The Entities each player controls, by player name. 
+    #[serde(default, with = "crate::util::simple_uuids::map")]
+    players: BTreeMap<String, Vec<Uuid>>,
 }
 
 impl Campaign {
@@ -24,7 +32,10 @@ impl Campaign {
             name, 
             description,
             scenes: HashMap::new(),
-            active_scene: None
+            scene_order: Vec::new(),
+            active_scene: None,
+            party: Vec::new(),
+            players: BTreeMap::new(),
         }
     }
 
@@ -44,18 +55,172 @@ impl Campaign {
     pub fn create_scene(&mut self, scene_name: String) -> Option<&Scene> {
         let scene = Scene::new(scene_name);
         let uuid = scene.get_uuid();
-        self.scenes.insert(uuid, scene);
+        self.add_scene(scene);
 
         self.scenes.get(&uuid)
     }
 
-    pub fn add_scene(&mut self, scene: Scene) {
-        self.scenes.insert(scene.get_uuid(), scene);
+    /// Add a Scene to the end of the list, or replace the Scene with the same UUID in place. 
+    pub fn add_scene(&mut self, mut scene: Scene) {
+        let uuid = Uuid::from_u128(scene.get_uuid());
+        if !self.scene_order.contains(&uuid) {
+            self.scene_order.push(uuid);
+        }
+
+        scene.set_owning_campaign(Some(self.get_uuid()));
+        self.scenes.insert(uuid.as_u128(), scene);
+    }
+
+    /// Claim every Scene read in with this Campaign and list any missing from the order. 
+    pub(crate) fn adopt_scenes(&mut self) {
+        let uuid = self.get_uuid();
+        let mut unordered: Vec<u128> = self.scenes.keys()
+            .filter(|s| !self.scene_order.contains(&Uuid::from_u128(**s)))
+            .copied()
+            .collect();
+        unordered.sort();
+
+        self.scene_order.retain(|s| self.scenes.contains_key(&s.as_u128()));
+        self.scene_order.extend(unordered.into_iter().map(Uuid::from_u128));
+        self.scenes.values_mut().for_each(|s| s.set_owning_campaign(Some(uuid)));
     }
 
     pub fn get_scene(&self, id: u128) -> Option<&Scene> {
         self.scenes.get(&id)
     }
+
+    pub fn get_scene_mut(&mut self, id: u128) -> Option<&mut Scene> {
+        self.scenes.get_mut(&id)
+    }
+
+    /// Every Scene, in list order. 
+    pub fn get_scenes(&self) -> Vec<&Scene> {
+        self.scene_order.iter().filter_map(|s| self.scenes.get(&s.as_u128())).collect()
+    }
+
+    /// Copy a Scene under a new name, listed straight after the original. Returns None if the Scene is unknown. 
+    pub fn duplicate_scene(&mut self, id: u128, scene_name: String) -> Option<&Scene> {
+        let scene = self.scenes.get(&id)?.duplicate(scene_name);
+        let uuid = scene.get_uuid();
+        self.add_scene(scene);
+        self.move_scene(uuid, self.get_scene_index(id)? + 1);
+
+        self.scenes.get(&uuid)
+    }
+
+    /// Remove a Scene, deactivating it if it was active. 
+    pub fn remove_scene(&mut self, id: u128) -> Option<Scene> {
+        let mut scene = self.scenes.remove(&id)?;
+        self.scene_order.retain(|s| s.as_u128() != id);
+        if self.active_scene == Some(id) {
+            self.active_scene = None;
+        }
+
+        scene.set_owning_campaign(None);
+        Some(scene)
+    }
+
+    /// Where a Scene is in the list. 
+    pub fn get_scene_index(&self, id: u128) -> Option<usize> {
+        self.scene_order.iter().position(|s| s.as_u128() == id)
+    }
+
+    /// Move a Scene to `index` in the list, or the end if `index` is past it. Returns false if the Scene is unknown. 
+    pub fn move_scene(&mut self, id: u128, index: usize) -> bool {
+        let Some(from) = self.get_scene_index(id) else {
+            return false;
+        };
+
+        let uuid = self.scene_order.remove(from);
+        self.scene_order.insert(index.min(self.scene_order.len()), uuid);
+        true
+    }
+
+    pub fn get_active_scene(&self) -> Option<u128> {
+        self.active_scene
+    }
+
+    /// Choose the Scene the table is playing in, or none. Returns false if the Scene is unknown. 
+    pub fn set_active_scene(&mut self, id: Option<u128>) -> bool {
+        if id.is_some_and(|id| !self.scenes.contains_key(&id)) {
+            return false;
+        }
+
+        self.active_scene = id;
+        true
+    }
+
+    /// Move an Entity from one Scene to another, dropping its token and place in combat in the first. 
+    /// Returns false if either Scene is unknown or the Entity isn't in the first. 
+    pub fn move_entity(&mut self, entity: u128, from: u128, to: u128) -> bool {
+        if from == to || !self.scenes.contains_key(&to) {
+            return false;
+        }
+
+        let Some(scene) = self.scenes.get_mut(&from) else {
+            return false;
+        };
+        if !scene.remove_entity(entity) {
+            return false;
+        }
+
+        self.scenes.get_mut(&to).unwrap().add_entity(entity);
+        true
+    }
+
+    /// The Entities that make up the player party, in the order they joined. 
+    pub fn get_party(&self) -> Vec<u128> {
+        self.party.iter().map(|u| u.as_u128()).collect()
+    }
+
+    /// Add an Entity to the party. Returns false if it is already a member. 
+    pub fn add_party_member(&mut self, entity: u128) -> bool {
+        if self.party.contains(&Uuid::from_u128(entity)) {
+            return false;
+        }
+
+        self.party.push(Uuid::from_u128(entity));
+        true
+    }
+
+    /// Remove an Entity from the party. Returns false if it was not a member. 
+    pub fn remove_party_member(&mut self, entity: u128) -> bool {
+        let before = self.party.len();
+        self.party.retain(|e| e.as_u128() != entity);
+        before != self.party.len()
+    }
+
+    /// Every player who has been given control of an Entity, by name. 
+    pub fn get_players(&self) -> Vec<&str> {
+        self.players.keys().map(|p| p.as_str()).collect()
+    }
+
+    /// The Entities a player controls. Unknown players control nothing. 
+    pub fn get_player_entities(&self, player: &str) -> Vec<u128> {
+        self.players.get(player).map(|e| e.iter().map(|u| u.as_u128()).collect()).unwrap_or_default()
+    }
+
+    /// Give a player control of an Entity. Returns false if they already control it. 
+    pub fn assign_entity(&mut self, player: &str, entity: u128) -> bool {
+        let entities = self.players.entry(String::from(player)).or_default();
+        if entities.contains(&Uuid::from_u128(entity)) {
+            return false;
+        }
+
+        entities.push(Uuid::from_u128(entity));
+        true
+    }
+
+    /// Take control of an Entity away from a player. Returns false if they didn't control it. 
+    pub fn unassign_entity(&mut self, player: &str, entity: u128) -> bool {
+        let Some(entities) = self.players.get_mut(player) else {
+            return false;
+        };
+
+        let before = entities.len();
+        entities.retain(|e| e.as_u128() != entity);
+        before != entities.len()
+    }
 }
  
 impl Asset for Campaign {
@@ -67,6 +232,10 @@ impl Asset for Campaign {
         // Campaigns are not owned 
         None
     }
+
+    fn set_owning_campaign(&mut self, _campaign: Option<u128>) {
+        // Campaigns are not owned 
+    }
 }
 
 #[cfg(test)]
@@ -105,4 +274,102 @@ pub mod tests {
         assert!(c.get_scene(55u128).is_none());
         assert!(c.get_scene(u128::MAX).is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn party() {
+        let mut c = Campaign::with_no_description(String::from("Party Time"));
+        assert!(c.get_party().is_empty());
+
+        assert!(c.add_party_member(1));
+        assert!(c.add_party_member(2));
+        assert!(!c.add_party_member(1));
+        assert_eq!(c.get_party(), [1, 2]);
+
+        assert!(c.remove_party_member(1));
+        assert!(!c.remove_party_member(1));
+        assert_eq!(c.get_party(), [2]);
+
+        // Members are stored as simple UUIDs, like the players' Entities, not as numbers too large for JSON 
+        assert!(c.add_party_member(u128::MAX));
+        let yaml = serde_yaml::to_string(&c).unwrap();
+        assert!(yaml.contains("- ffffffffffffffffffffffffffffffff"));
+        let de: Campaign = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(de.get_party(), [2, u128::MAX]);
+    }
+
+    #[test]
+    pub fn players() {
+        let mut c = Campaign::with_no_description(String::from("Table Talk"));
+        assert!(c.get_player_entities("alice").is_empty());
+
+        assert!(c.assign_entity("alice", 1));
+        assert!(c.assign_entity("alice", 2));
+        assert!(!c.assign_entity("alice", 1));
+        assert!(c.assign_entity("bob", 1));
+        assert_eq!(c.get_player_entities("alice"), [1, 2]);
+
+        assert!(c.unassign_entity("alice", 1));
+        assert!(!c.unassign_entity("alice", 1));
+        assert!(!c.unassign_entity("carol", 1));
+        assert_eq!(c.get_player_entities("alice"), [2]);
+        assert_eq!(c.get_player_entities("bob"), [1]);
+
+        let yaml = serde_yaml::to_string(&c).unwrap();
+        assert!(yaml.contains("alice:\n  - '00000000000000000000000000000002'"));
+        let de: Campaign = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(de.get_player_entities("alice"), [2]);
+        assert_eq!(de.get_player_entities("bob"), [1]);
+    }
+
+    #[test]
+    pub fn manage_scenes() {
+        let mut c = Campaign::with_no_description(String::from("Westmarch"));
+        let town = c.create_scene(String::from("Town")).unwrap().get_uuid();
+        let road = c.create_scene(String::from("Road")).unwrap().get_uuid();
+        let cave = c.create_scene(String::from("Cave")).unwrap().get_uuid();
+        assert_eq!(c.get_scene(town).unwrap().get_owning_campaign(), Some(c.get_uuid()));
+
+        // Reordering 
+        assert!(c.move_scene(cave, 0));
+        assert!(c.move_scene(town, 99));
+        assert!(!c.move_scene(55, 0));
+        let names: Vec<&str> = c.get_scenes().iter().map(|s| s.get_name()).collect();
+        assert_eq!(names, ["Cave", "Road", "Town"]);
+
+        // Copies are listed after the original 
+        let copy = c.duplicate_scene(cave, String::from("Cave (Flooded)")).unwrap().get_uuid();
+        assert_eq!(c.get_scene_index(copy), Some(1));
+        assert!(c.duplicate_scene(55, String::from("Nowhere")).is_none());
+
+        // Activating and deleting 
+        assert!(c.set_active_scene(Some(road)));
+        assert!(!c.set_active_scene(Some(55)));
+        assert_eq!(c.get_active_scene(), Some(road));
+        let removed = c.remove_scene(road).unwrap();
+        assert_eq!(removed.get_owning_campaign(), None);
+        assert_eq!(c.get_active_scene(), None);
+        assert_eq!(c.get_scenes().len(), 3);
+        assert!(c.remove_scene(road).is_none());
+
+        // Moving Entities between Scenes 
+        c.get_scene_mut(town).unwrap().add_entity(1);
+        assert!(c.move_entity(1, town, cave));
+        assert!(!c.move_entity(1, town, cave));
+        assert!(!c.move_entity(1, cave, road));
+        assert_eq!(c.get_scene(cave).unwrap().get_entities(), [1]);
+        assert!(c.get_scene(town).unwrap().get_entities().is_empty());
+    }
+
+    #[test]
+    pub fn adopt_scenes() {
+        let mut c = Campaign::with_no_description(String::from("Old Save"));
+        let scene = Scene::new(String::from("Somewhere"));
+        let id = scene.get_uuid();
+        c.scenes.insert(id, scene);
+        assert!(c.get_scenes().is_empty());
+
+        c.adopt_scenes();
+        assert_eq!(c.get_scenes().len(), 1);
+        assert_eq!(c.get_scene(id).unwrap().get_owning_campaign(), Some(c.get_uuid()));
+    }
+}
@@ -11,6 +11,9 @@ pub struct CampaignDescription {
 }
 
 impl CampaignDescription {
+    pub(crate) fn new(uuid: u128, name: String, path: String, description: String) -> Self {
+        Self { uuid: Uuid::from_u128(uuid), name, path, description }
+    }
 
     pub fn get_uuid(&self) -> u128 {
         self.uuid.as_u128()
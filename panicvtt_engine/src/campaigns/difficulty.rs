@@ -0,0 +1,262 @@
+use serde::{Deserialize, Serialize};
+
+/// How dangerous an encounter is for a party. Trivial encounters fall below the Easy threshold.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Trivial,
+    Easy,
+    Medium,
+    Hard,
+    Deadly,
+}
+
+/// XP thresholds for each character level, indexed from level 1.
+const XP_THRESHOLDS: [[u32; 4]; 20] = [
+    [25, 50, 75, 100],
+    [50, 100, 150, 200],
+    [75, 150, 225, 400],
+    [125, 250, 375, 500],
+    [250, 500, 750, 1100],
+    [300, 600, 900, 1400],
+    [350, 750, 1100, 1700],
+    [450, 900, 1400, 2100],
+    [550, 1100, 1600, 2400],
+    [600, 1200, 1900, 2800],
+    [800, 1600, 2400, 3600],
+    [1000, 2000, 3000, 4500],
+    [1100, 2200, 3400, 5100],
+    [1250, 2500, 3800, 5700],
+    [1400, 2800, 4300, 6400],
+    [1600, 3200, 4800, 7200],
+    [2000, 3900, 5900, 8800],
+    [2100, 4200, 6300, 9500],
+    [2400, 4900, 7300, 10900],
+    [2800, 5700, 8500, 12700],
+];
+
+/// Encounter multipliers, from a lone monster facing a large party up to a horde facing a small one.
+const MULTIPLIERS: [f32; 8] = [0.5, 1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0];
+
+/// The summed XP thresholds of every party member.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct PartyThresholds {
+    easy: u32,
+    medium: u32,
+    hard: u32,
+    deadly: u32,
+}
+
+impl PartyThresholds {
+    /// Levels are clamped to 1 through 20.
+    pub fn from_levels(levels: &[u8]) -> Self {
+        levels.iter().fold(Self::default(), |t, level| {
+            let [easy, medium, hard, deadly] = XP_THRESHOLDS[(*level).clamp(1, 20) as usize - 1];
+            Self { easy: t.easy + easy, medium: t.medium + medium, hard: t.hard + hard, deadly: t.deadly + deadly }
+        })
+    }
+
+    /// The adjusted XP at which an encounter becomes this difficult.
+    pub fn get(&self, difficulty: Difficulty) -> u32 {
+        match difficulty {
+            Difficulty::Trivial => 0,
+            Difficulty::Easy => self.easy,
+            Difficulty::Medium => self.medium,
+            Difficulty::Hard => self.hard,
+            Difficulty::Deadly => self.deadly,
+        }
+    }
+
+    pub fn classify(&self, adjusted_xp: u32) -> Difficulty {
+        [Difficulty::Deadly, Difficulty::Hard, Difficulty::Medium, Difficulty::Easy].into_iter()
+            .find(|d| adjusted_xp >= self.get(*d))
+            .unwrap_or(Difficulty::Trivial)
+    }
+
+    /// The range of adjusted XP that rates as `difficulty`, with no upper bound for Deadly.
+    pub fn get_budget(&self, difficulty: Difficulty) -> (u32, Option<u32>) {
+        let upper = match difficulty {
+            Difficulty::Trivial => Some(self.easy),
+            Difficulty::Easy => Some(self.medium),
+            Difficulty::Medium => Some(self.hard),
+            Difficulty::Hard => Some(self.deadly),
+            Difficulty::Deadly => None,
+        };
+        (self.get(difficulty), upper)
+    }
+}
+
+/// The multiplier applied to monster XP for the number of monsters, shifted up for parties under three
+/// and down for parties of six or more.
+pub fn get_encounter_multiplier(monster_count: usize, party_size: usize) -> f32 {
+    let index = match monster_count {
+        0 => return 0.0,
+        1 => 1,
+        2 => 2,
+        3..=6 => 3,
+        7..=10 => 4,
+        11..=14 => 5,
+        _ => 6,
+    };
+
+    let index = match party_size {
+        0..=2 => index + 1,
+        6.. => index - 1,
+        _ => index,
+    };
+    MULTIPLIERS[index]
+}
+
+/// The adjusted XP of an encounter and how it rates against a party.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct DifficultyReport {
+    base_xp: u32,
+    multiplier: f32,
+    adjusted_xp: u32,
+    thresholds: PartyThresholds,
+    difficulty: Difficulty,
+}
+
+impl DifficultyReport {
+    pub fn new(party_levels: &[u8], monster_xp: &[u32]) -> Self {
+        let thresholds = PartyThresholds::from_levels(party_levels);
+        let base_xp = monster_xp.iter().sum();
+        let multiplier = get_encounter_multiplier(monster_xp.len(), party_levels.len());
+        let adjusted_xp = (base_xp as f32 * multiplier) as u32;
+
+        Self { base_xp, multiplier, adjusted_xp, thresholds, difficulty: thresholds.classify(adjusted_xp) }
+    }
+
+    /// Total XP the monsters award, before the multiplier.
+    pub fn get_base_xp(&self) -> u32 {
+        self.base_xp
+    }
+
+    pub fn get_multiplier(&self) -> f32 {
+        self.multiplier
+    }
+
+    pub fn get_adjusted_xp(&self) -> u32 {
+        self.adjusted_xp
+    }
+
+    pub fn get_thresholds(&self) -> &PartyThresholds {
+        &self.thresholds
+    }
+
+    pub fn get_difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+}
+
+/// A group of monsters suggested for an encounter.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct MonsterSet {
+    /// Monster UUIDs and how many of each.
+    monsters: Vec<(u128, u8)>,
+    report: DifficultyReport,
+}
+
+impl MonsterSet {
+    pub fn get_monsters(&self) -> &[(u128, u8)] {
+        &self.monsters
+    }
+
+    pub fn get_report(&self) -> &DifficultyReport {
+        &self.report
+    }
+}
+
+/// Suggest groups of up to `max_monsters` monsters that rate as `difficulty` for a party, hardest first.
+///
+/// Suggestions are either several of one monster, or one leader with several of another. `monsters` pairs each
+/// monster's UUID with its XP.
+pub fn suggest_monster_sets(party_levels: &[u8], difficulty: Difficulty, monsters: &[(u128, u32)], max_monsters: u8) -> Vec<MonsterSet> {
+    let thresholds = PartyThresholds::from_levels(party_levels);
+    let (lower, upper) = thresholds.get_budget(difficulty);
+    let fits = |report: &DifficultyReport| report.adjusted_xp >= lower && upper.is_none_or(|u| report.adjusted_xp < u);
+
+    let mut sets = Vec::new();
+    for (leader, leader_xp) in monsters {
+        for count in 1..=max_monsters {
+            let report = DifficultyReport::new(party_levels, &vec![*leader_xp; count as usize]);
+            if fits(&report) {
+                sets.push(MonsterSet { monsters: vec![(*leader, count)], report });
+            }
+        }
+
+        for (minion, minion_xp) in monsters.iter().filter(|(m, _)| m != leader) {
+            for count in 1..max_monsters {
+                let mut xp = vec![*minion_xp; count as usize];
+                xp.push(*leader_xp);
+                let report = DifficultyReport::new(party_levels, &xp);
+                if fits(&report) {
+                    sets.push(MonsterSet { monsters: vec![(*leader, 1), (*minion, count)], report });
+                }
+            }
+        }
+    }
+
+    sets.sort_by_key(|s| std::cmp::Reverse(s.report.adjusted_xp));
+    sets
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn thresholds() {
+        let t = PartyThresholds::from_levels(&[3, 3, 3, 2]);
+        assert_eq!(t.get(Difficulty::Easy), 275);
+        assert_eq!(t.get(Difficulty::Deadly), 1400);
+        assert_eq!(t.classify(274), Difficulty::Trivial);
+        assert_eq!(t.classify(550), Difficulty::Medium);
+        assert_eq!(t.classify(5000), Difficulty::Deadly);
+        assert_eq!(t.get_budget(Difficulty::Hard), (825, Some(1400)));
+
+        // Out of range levels are clamped
+        assert_eq!(PartyThresholds::from_levels(&[0, 25]), PartyThresholds::from_levels(&[1, 20]));
+    }
+
+    #[test]
+    pub fn multipliers() {
+        assert_eq!(get_encounter_multiplier(0, 4), 0.0);
+        assert_eq!(get_encounter_multiplier(1, 4), 1.0);
+        assert_eq!(get_encounter_multiplier(2, 4), 1.5);
+        assert_eq!(get_encounter_multiplier(6, 4), 2.0);
+        assert_eq!(get_encounter_multiplier(15, 4), 4.0);
+
+        // Small parties and large parties
+        assert_eq!(get_encounter_multiplier(1, 2), 1.5);
+        assert_eq!(get_encounter_multiplier(15, 1), 5.0);
+        assert_eq!(get_encounter_multiplier(1, 6), 0.5);
+    }
+
+    #[test]
+    pub fn report() {
+        // Four 3rd level characters against a bugbear and three goblins
+        let report = DifficultyReport::new(&[3, 3, 3, 3], &[200, 50, 50, 50]);
+        assert_eq!(report.get_base_xp(), 350);
+        assert_eq!(report.get_multiplier(), 2.0);
+        assert_eq!(report.get_adjusted_xp(), 700);
+        assert_eq!(report.get_difficulty(), Difficulty::Medium);
+    }
+
+    #[test]
+    pub fn suggestions() {
+        let monsters = [(1, 50), (2, 200)];
+        let sets = suggest_monster_sets(&[3, 3, 3, 3], Difficulty::Medium, &monsters, 4);
+        assert!(!sets.is_empty());
+        for set in &sets {
+            assert_eq!(set.get_report().get_difficulty(), Difficulty::Medium);
+            assert!(set.get_monsters().iter().map(|(_, n)| *n as u32).sum::<u32>() <= 4);
+        }
+        assert!(sets.windows(2).all(|w| w[0].get_report().get_adjusted_xp() >= w[1].get_report().get_adjusted_xp()));
+
+        // Two bugbears are 400 XP at x1.5, a bugbear and two goblins are 300 XP at x2
+        assert!(sets.iter().any(|s| s.get_monsters() == [(2, 2)]));
+        assert!(sets.iter().any(|s| s.get_monsters() == [(2, 1), (1, 2)]));
+        assert!(!sets.iter().any(|s| s.get_monsters() == [(1, 3)]));
+        assert!(suggest_monster_sets(&[3, 3, 3, 3], Difficulty::Deadly, &monsters, 1).is_empty());
+    }
+}
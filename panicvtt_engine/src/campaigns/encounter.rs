@@ -0,0 +1,751 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::modifier::{ModifierSource, StatIntType};
+
+/// Lair actions take place on this initiative count, losing initiative ties.
+pub const LAIR_INITIATIVE: i16 = 20;
+
+/// An Entity taking part in an Encounter.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Combatant {
+    #[serde(with = "uuid::serde::simple")]
+    entity: Uuid,
+    initiative: i16,
+    /// Ties in initiative go to the higher initiative bonus, then the higher Dexterity score. 
+    #[serde(default)]
+    initiative_bonus: StatIntType,
+    #[serde(default)]
+    dexterity: u8,
+    /// Legendary actions left to spend before this combatant's next turn.
+    #[serde(default)]
+    legendary_actions: u8,
+    #[serde(default)]
+    legendary_action_max: u8,
+    #[serde(default)]
+    has_lair_actions: bool,
+    #[serde(default)]
+    hooks: Vec<TurnHook>,
+    /// Feet moved so far this turn.
+    #[serde(default)]
+    movement_used: u32,
+    /// Diagonal steps taken this turn, for alternating diagonal costs.
+    #[serde(default)]
+    diagonals: u16,
+    #[serde(default)]
+    dashes: u8,
+    #[serde(default)]
+    disengaged: bool,
+    /// The round this combatant's turn last began in, so a delayed turn doesn't begin twice. 
+    #[serde(default)]
+    started_round: u32,
+}
+
+impl Combatant {
+    pub fn new(entity: u128, initiative: i16) -> Self {
+        Self {
+            entity: Uuid::from_u128(entity),
+            initiative,
+            initiative_bonus: 0,
+            dexterity: 0,
+            legendary_actions: 0,
+            legendary_action_max: 0,
+            has_lair_actions: false,
+            hooks: Vec::new(),
+            movement_used: 0,
+            diagonals: 0,
+            dashes: 0,
+            disengaged: false,
+            started_round: 0,
+        }
+    }
+
+    pub fn with_tiebreakers(mut self, initiative_bonus: StatIntType, dexterity: u8) -> Self {
+        self.initiative_bonus = initiative_bonus;
+        self.dexterity = dexterity;
+        self
+    }
+
+    pub fn with_legendary_actions(mut self, count: u8) -> Self {
+        self.legendary_actions = count;
+        self.legendary_action_max = count;
+        self
+    }
+
+    pub fn with_lair_actions(mut self, has_lair_actions: bool) -> Self {
+        self.has_lair_actions = has_lair_actions;
+        self
+    }
+
+    pub fn get_entity(&self) -> u128 {
+        self.entity.as_u128()
+    }
+
+    pub fn get_initiative(&self) -> i16 {
+        self.initiative
+    }
+
+    pub fn get_legendary_actions(&self) -> u8 {
+        self.legendary_actions
+    }
+
+    pub fn get_legendary_action_max(&self) -> u8 {
+        self.legendary_action_max
+    }
+
+    pub fn has_lair_actions(&self) -> bool {
+        self.has_lair_actions
+    }
+
+    pub fn get_hooks(&self) -> &[TurnHook] {
+        &self.hooks
+    }
+
+    pub fn get_movement_used(&self) -> u32 {
+        self.movement_used
+    }
+
+    pub fn get_diagonals(&self) -> u16 {
+        self.diagonals
+    }
+
+    pub fn get_dashes(&self) -> u8 {
+        self.dashes
+    }
+
+    pub fn is_disengaged(&self) -> bool {
+        self.disengaged
+    }
+
+    /// Feet of movement left this turn for a combatant with `speed`, counting each Dash as another `speed`.
+    pub fn get_remaining_movement(&self, speed: u8) -> u32 {
+        (speed as u32 * (1 + self.dashes as u32)).saturating_sub(self.movement_used)
+    }
+
+    /// Turn order, earliest first. 
+    fn cmp_turn_order(&self, other: &Combatant) -> Ordering {
+        (other.initiative, other.initiative_bonus, other.dexterity).cmp(&(self.initiative, self.initiative_bonus, self.dexterity))
+    }
+
+    /// Run this combatant's hooks for the start or end of its turn, dropping any that have run their course. 
+    fn run_hooks(&mut self, timing: TurnTiming, events: &mut Vec<TurnEvent>) {
+        let entity = self.entity;
+        self.hooks.retain_mut(|hook| match hook {
+            TurnHook::Regeneration { amount } => {
+                if timing == TurnTiming::StartOfTurn {
+                    events.push(TurnEvent::Regenerate { entity, amount: *amount });
+                }
+                true
+            },
+            TurnHook::ConditionExpiry { source, timing: expires, turns } => {
+                if *expires != timing {
+                    return true;
+                }
+
+                *turns = turns.saturating_sub(1);
+                if *turns == 0 {
+                    events.push(TurnEvent::ConditionExpired { entity, source: source.clone() });
+                }
+                *turns > 0
+            },
+        });
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum TurnTiming {
+    StartOfTurn,
+    EndOfTurn,
+}
+
+/// Something that happens automatically on a combatant's turn.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum TurnHook {
+    /// Regain hit points at the start of each turn, e.g. a troll's Regeneration.
+    Regeneration { amount: u16 },
+    /// End a condition at the start or end of the combatant's `turns`-th turn from now.
+    ConditionExpiry { source: ModifierSource, timing: TurnTiming, turns: u8 },
+}
+
+/// A change to an Entity raised by a TurnHook, for the engine to apply.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum TurnEvent {
+    Regenerate {
+        #[serde(with = "uuid::serde::simple")]
+        entity: Uuid,
+        amount: u16,
+    },
+    ConditionExpired {
+        #[serde(with = "uuid::serde::simple")]
+        entity: Uuid,
+        source: ModifierSource,
+    },
+}
+
+/// Something the GM may do on behalf of a combatant between turns.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum EncounterPrompt {
+    LegendaryActions {
+        #[serde(with = "uuid::serde::simple")]
+        entity: Uuid,
+        remaining: u8,
+    },
+    LairAction {
+        #[serde(with = "uuid::serde::simple")]
+        entity: Uuid,
+    },
+}
+
+impl EncounterPrompt {
+    pub fn get_entity(&self) -> u128 {
+        match self {
+            EncounterPrompt::LegendaryActions { entity, .. } => entity.as_u128(),
+            EncounterPrompt::LairAction { entity } => entity.as_u128(),
+        }
+    }
+}
+
+/// Turn order and per-round combat state for a Scene.
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct Encounter {
+    /// Combatants in turn order, highest initiative first.
+    combatants: Vec<Combatant>,
+    turn: usize,
+    round: u32,
+    /// Whether initiative count 20 has passed this round.
+    lair_action_taken: bool,
+    /// Prompts raised by the most recent change of turn.
+    prompts: Vec<EncounterPrompt>,
+    /// Hook results not yet applied to their Entities.
+    #[serde(default)]
+    events: Vec<TurnEvent>,
+}
+
+impl Encounter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a combatant in initiative order, after any others it ties with completely. 
+    ///
+    /// A combatant joining a round in progress ahead of the current turn acts next round. Returns false if it is already in the Encounter.
+    pub fn add_combatant(&mut self, combatant: Combatant) -> bool {
+        if self.get_combatant(combatant.get_entity()).is_some() {
+            return false;
+        }
+
+        let index = self.insertion_index(&combatant);
+        if self.round > 0 && index <= self.turn && !self.combatants.is_empty() {
+            self.turn += 1;
+        }
+        self.combatants.insert(index, combatant);
+
+        // The first combatant into a started Encounter begins its turn right away
+        if self.round > 0 && self.combatants.len() == 1 {
+            self.begin_turn();
+        }
+        true
+    }
+
+    fn insertion_index(&self, combatant: &Combatant) -> usize {
+        self.combatants.iter()
+            .position(|c| combatant.cmp_turn_order(c) == Ordering::Less)
+            .unwrap_or(self.combatants.len())
+    }
+
+    /// Remove a combatant. If it was their turn, the next combatant's turn begins. 
+    pub fn remove_combatant(&mut self, entity: u128) -> Option<Combatant> {
+        let index = self.combatants.iter().position(|c| c.get_entity() == entity)?;
+        let combatant = self.combatants.remove(index);
+        self.prompts.retain(|p| p.get_entity() != entity);
+
+        if self.round > 0 && index < self.turn {
+            self.turn -= 1;
+        } else if self.round > 0 && index == self.turn && !self.combatants.is_empty() {
+            self.prompts.clear();
+            self.advance();
+        }
+        Some(combatant)
+    }
+
+    /// Postpone the current combatant's turn until a lower initiative count this round. 
+    ///
+    /// Returns false if it isn't their turn, or nobody else would act before them at that count.
+    pub fn delay(&mut self, entity: u128, initiative: i16) -> bool {
+        if self.get_current().is_none_or(|c| c.get_entity() != entity || c.initiative <= initiative) {
+            return false;
+        }
+
+        let mut combatant = self.combatants.remove(self.turn);
+        let previous = std::mem::replace(&mut combatant.initiative, initiative);
+        let index = self.insertion_index(&combatant);
+        if index == self.turn {
+            combatant.initiative = previous;
+            self.combatants.insert(index, combatant);
+            return false;
+        }
+
+        self.combatants.insert(index, combatant);
+        self.prompts.clear();
+        self.begin_turn();
+        true
+    }
+
+    /// Attach a hook to a combatant. Returns false if it isn't in the Encounter. 
+    pub fn add_hook(&mut self, entity: u128, hook: TurnHook) -> bool {
+        let Some(combatant) = self.combatants.iter_mut().find(|c| c.get_entity() == entity) else {
+            return false;
+        };
+
+        combatant.hooks.push(hook);
+        true
+    }
+
+    /// Take the results of every hook that has run since this was last called. 
+    pub fn take_events(&mut self) -> Vec<TurnEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn get_combatants(&self) -> &[Combatant] {
+        &self.combatants
+    }
+
+    pub fn get_combatant(&self, entity: u128) -> Option<&Combatant> {
+        self.combatants.iter().find(|c| c.get_entity() == entity)
+    }
+
+    /// The combatant whose turn it is. Returns None before the Encounter starts or if it has no combatants.
+    pub fn get_current(&self) -> Option<&Combatant> {
+        if self.round == 0 {
+            return None;
+        }
+
+        self.combatants.get(self.turn)
+    }
+
+    /// The current round, starting at 1. Round 0 means the Encounter has not started.
+    pub fn get_round(&self) -> u32 {
+        self.round
+    }
+
+    pub fn get_prompts(&self) -> &[EncounterPrompt] {
+        &self.prompts
+    }
+
+    /// Begin the first round with the highest initiative.
+    pub fn start(&mut self) -> &[EncounterPrompt] {
+        self.round = 1;
+        self.turn = 0;
+        self.lair_action_taken = false;
+        self.prompts.clear();
+
+        if !self.combatants.is_empty() {
+            self.begin_turn();
+        }
+        &self.prompts
+    }
+
+    /// End the current turn and begin the next, starting a new round after the last combatant.
+    ///
+    /// Returns the legendary and lair actions now available to the GM.
+    pub fn next_turn(&mut self) -> &[EncounterPrompt] {
+        self.prompts.clear();
+        if self.round == 0 || self.combatants.is_empty() {
+            return &self.prompts;
+        }
+
+        let ended = &mut self.combatants[self.turn];
+        ended.run_hooks(TurnTiming::EndOfTurn, &mut self.events);
+
+        // Legendary creatures may act at the end of another creature's turn
+        let ended = ended.entity;
+        self.prompts.extend(self.combatants.iter()
+            .filter(|c| c.entity != ended && c.legendary_actions > 0)
+            .map(|c| EncounterPrompt::LegendaryActions { entity: c.entity, remaining: c.legendary_actions }));
+
+        self.turn += 1;
+        self.advance();
+        &self.prompts
+    }
+
+    /// Begin the turn at `turn`, wrapping into a new round past the last combatant. 
+    fn advance(&mut self) {
+        if self.turn == self.combatants.len() {
+            // If everyone rolled 20 or higher, count 20 comes at the very end of the round
+            if !self.lair_action_taken {
+                self.prompt_lair_actions();
+            }
+
+            self.turn = 0;
+            self.round += 1;
+            self.lair_action_taken = false;
+        }
+
+        self.begin_turn();
+    }
+
+    fn begin_turn(&mut self) {
+        if !self.lair_action_taken && self.combatants[self.turn].initiative < LAIR_INITIATIVE {
+            self.prompt_lair_actions();
+        }
+
+        // A combatant who delayed has already started this round's turn
+        let current = &mut self.combatants[self.turn];
+        if current.started_round == self.round {
+            return;
+        }
+        current.started_round = self.round;
+
+        // Legendary actions refresh at the start of their owner's turn
+        current.legendary_actions = current.legendary_action_max;
+        current.movement_used = 0;
+        current.diagonals = 0;
+        current.dashes = 0;
+        current.disengaged = false;
+        current.run_hooks(TurnTiming::StartOfTurn, &mut self.events);
+    }
+
+    fn prompt_lair_actions(&mut self) {
+        self.lair_action_taken = true;
+        self.prompts.extend(self.combatants.iter()
+            .filter(|c| c.has_lair_actions)
+            .map(|c| EncounterPrompt::LairAction { entity: c.entity }));
+    }
+
+    /// Spend `cost` of a combatant's legendary actions while it is prompted to use them.
+    ///
+    /// Returns false, changing nothing, if it has no outstanding prompt or too few actions left.
+    pub fn spend_legendary_action(&mut self, entity: u128, cost: u8) -> bool {
+        let Some(remaining) = self.prompts.iter_mut().find_map(|p| match p {
+            EncounterPrompt::LegendaryActions { entity: e, remaining } if e.as_u128() == entity => Some(remaining),
+            _ => None,
+        }) else {
+            return false;
+        };
+        if *remaining < cost {
+            return false;
+        }
+        *remaining -= cost;
+        let left = *remaining;
+
+        // A combatant acting just before its own turn spends from the pool it had, not the one it just regained
+        if self.get_current().is_some_and(|c| c.get_entity() != entity) {
+            if let Some(c) = self.combatants.iter_mut().find(|c| c.get_entity() == entity) {
+                c.legendary_actions = left;
+            }
+        }
+
+        if left == 0 {
+            self.prompts.retain(|p| !matches!(p, EncounterPrompt::LegendaryActions { entity: e, .. } if e.as_u128() == entity));
+        }
+        true
+    }
+
+    /// Record a move by the current combatant costing `cost` feet, leaving it at `diagonals` diagonal steps this turn.
+    ///
+    /// Returns false, changing nothing, if it isn't that combatant's turn or the move exceeds what is left of `speed`.
+    pub fn spend_movement(&mut self, entity: u128, cost: u32, diagonals: u16, speed: u8) -> bool {
+        let Some(combatant) = self.get_current_mut().filter(|c| c.get_entity() == entity) else {
+            return false;
+        };
+        if cost > combatant.get_remaining_movement(speed) {
+            return false;
+        }
+
+        combatant.movement_used += cost;
+        combatant.diagonals = diagonals;
+        true
+    }
+
+    fn get_current_mut(&mut self) -> Option<&mut Combatant> {
+        if self.round == 0 {
+            return None;
+        }
+
+        self.combatants.get_mut(self.turn)
+    }
+
+    /// The current combatant takes the Dash action, gaining its speed again in movement. Returns false if it 
+    /// isn't that combatant's turn.
+    pub fn dash(&mut self, entity: u128) -> bool {
+        match self.get_current_mut() {
+            Some(current) if current.get_entity() == entity => {
+                current.dashes += 1;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// The current combatant takes the Disengage action, so its movement provokes no opportunity attacks this 
+    /// turn. Returns false if it isn't that combatant's turn.
+    pub fn disengage(&mut self, entity: u128) -> bool {
+        match self.get_current_mut() {
+            Some(current) if current.get_entity() == entity => {
+                current.disengaged = true;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Dismiss a combatant's lair action prompt once the GM has resolved it. Returns false if there was none.
+    pub fn resolve_lair_action(&mut self, entity: u128) -> bool {
+        let before = self.prompts.len();
+        self.prompts.retain(|p| !matches!(p, EncounterPrompt::LairAction { entity: e } if e.as_u128() == entity));
+        before != self.prompts.len()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn legendary(remaining: u8) -> EncounterPrompt {
+        EncounterPrompt::LegendaryActions { entity: Uuid::from_u128(3), remaining }
+    }
+
+    fn lair() -> EncounterPrompt {
+        EncounterPrompt::LairAction { entity: Uuid::from_u128(3) }
+    }
+
+    #[test]
+    pub fn turn_order() {
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(1, 12));
+        encounter.add_combatant(Combatant::new(2, 18));
+        encounter.add_combatant(Combatant::new(4, 12));
+        assert!(encounter.get_current().is_none());
+
+        let order: Vec<u128> = encounter.get_combatants().iter().map(|c| c.get_entity()).collect();
+        assert_eq!(order, [2, 1, 4]);
+
+        encounter.start();
+        assert_eq!(encounter.get_round(), 1);
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 2);
+        encounter.next_turn();
+        encounter.next_turn();
+        encounter.next_turn();
+        assert_eq!(encounter.get_round(), 2);
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 2);
+    }
+
+    fn order(encounter: &Encounter) -> Vec<u128> {
+        encounter.get_combatants().iter().map(|c| c.get_entity()).collect()
+    }
+
+    #[test]
+    pub fn tiebreakers() {
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(1, 15).with_tiebreakers(1, 12));
+        encounter.add_combatant(Combatant::new(2, 15).with_tiebreakers(3, 16));
+        encounter.add_combatant(Combatant::new(3, 15).with_tiebreakers(1, 13));
+        encounter.add_combatant(Combatant::new(4, 15).with_tiebreakers(1, 12));
+        assert!(!encounter.add_combatant(Combatant::new(4, 20)));
+
+        // Bonus, then Dexterity, then whoever joined first
+        assert_eq!(order(&encounter), [2, 3, 1, 4]);
+    }
+
+    #[test]
+    pub fn add_remove_and_delay() {
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(1, 20));
+        encounter.add_combatant(Combatant::new(2, 15));
+        encounter.add_combatant(Combatant::new(3, 10));
+        encounter.start();
+        encounter.next_turn();
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 2);
+
+        // Joining ahead of the current turn waits for next round
+        encounter.add_combatant(Combatant::new(4, 18));
+        assert_eq!(order(&encounter), [1, 4, 2, 3]);
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 2);
+
+        // Delaying lets the next combatant go first
+        assert!(!encounter.delay(3, 5));
+        assert!(!encounter.delay(2, 15));
+        assert!(!encounter.delay(2, 12));
+        assert!(encounter.delay(2, 8));
+        assert_eq!(order(&encounter), [1, 4, 3, 2]);
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 3);
+
+        // Removing the current combatant starts the next turn; removing an earlier one keeps it
+        assert_eq!(encounter.remove_combatant(3).unwrap().get_entity(), 3);
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 2);
+        assert!(encounter.remove_combatant(1).is_some());
+        assert!(encounter.remove_combatant(1).is_none());
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 2);
+        assert_eq!(encounter.get_round(), 1);
+
+        // Removing the last in the round wraps to the next
+        encounter.remove_combatant(2);
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 4);
+        assert_eq!(encounter.get_round(), 2);
+    }
+
+    #[test]
+    pub fn hooks() {
+        let poisoned = ModifierSource::Condition { name: String::from("Poisoned") };
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(1, 15));
+        encounter.add_combatant(Combatant::new(2, 10));
+        assert!(encounter.add_hook(1, TurnHook::Regeneration { amount: 10 }));
+        assert!(encounter.add_hook(2, TurnHook::ConditionExpiry { source: poisoned.clone(), timing: TurnTiming::EndOfTurn, turns: 2 }));
+        assert!(!encounter.add_hook(55, TurnHook::Regeneration { amount: 10 }));
+
+        let regenerate = TurnEvent::Regenerate { entity: Uuid::from_u128(1), amount: 10 };
+        encounter.start();
+        assert_eq!(encounter.take_events(), std::slice::from_ref(&regenerate));
+        assert!(encounter.take_events().is_empty());
+
+        encounter.next_turn();
+        encounter.next_turn();
+        assert_eq!(encounter.take_events(), std::slice::from_ref(&regenerate));
+        assert_eq!(encounter.get_combatant(2).unwrap().get_hooks().len(), 1);
+
+        // Poison wears off at the end of the second combatant's second turn
+        encounter.next_turn();
+        assert!(encounter.take_events().is_empty());
+        encounter.next_turn();
+        assert_eq!(encounter.take_events(), [TurnEvent::ConditionExpired { entity: Uuid::from_u128(2), source: poisoned }, regenerate]);
+        assert!(encounter.get_combatant(2).unwrap().get_hooks().is_empty());
+    }
+
+    #[test]
+    pub fn delayed_hooks() {
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(1, 15));
+        encounter.add_combatant(Combatant::new(2, 10));
+        encounter.add_hook(1, TurnHook::Regeneration { amount: 10 });
+        encounter.start();
+        assert!(encounter.spend_movement(1, 20, 0, 30));
+        assert_eq!(encounter.take_events().len(), 1);
+
+        // Coming back to a delayed turn doesn't start it over
+        assert!(encounter.delay(1, 5));
+        encounter.next_turn();
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 1);
+        assert!(encounter.take_events().is_empty());
+        assert_eq!(encounter.get_combatant(1).unwrap().get_remaining_movement(30), 10);
+
+        // It starts again as usual next round
+        encounter.next_turn();
+        assert_eq!(encounter.get_round(), 2);
+        encounter.next_turn();
+        assert_eq!(encounter.take_events().len(), 1);
+        assert_eq!(encounter.get_combatant(1).unwrap().get_remaining_movement(30), 30);
+    }
+
+    #[test]
+    pub fn legendary_actions() {
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(1, 15));
+        encounter.add_combatant(Combatant::new(2, 12));
+        encounter.add_combatant(Combatant::new(3, 10).with_legendary_actions(3));
+        encounter.start();
+
+        // Spent at the end of another creature's turn, never beyond what's left
+        assert_eq!(encounter.next_turn(), [legendary(3)]);
+        assert!(encounter.spend_legendary_action(3, 2));
+        assert_eq!(encounter.get_prompts(), [legendary(1)]);
+        assert!(!encounter.spend_legendary_action(3, 2));
+        assert_eq!(encounter.get_combatant(3).unwrap().get_legendary_actions(), 1);
+
+        // Spending just before its own turn doesn't eat into the refreshed pool
+        assert_eq!(encounter.next_turn(), [legendary(1)]);
+        assert!(encounter.spend_legendary_action(3, 1));
+        assert!(encounter.get_prompts().is_empty());
+        assert!(!encounter.spend_legendary_action(3, 1));
+        assert_eq!(encounter.get_current().unwrap().get_entity(), 3);
+        assert_eq!(encounter.get_combatant(3).unwrap().get_legendary_actions(), 3);
+
+        // Not prompted at the end of its own turn
+        assert_eq!(encounter.next_turn(), []);
+        assert!(!encounter.spend_legendary_action(3, 1));
+        assert!(!encounter.spend_legendary_action(55, 1));
+    }
+
+    #[test]
+    pub fn lair_actions() {
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(1, 22));
+        encounter.add_combatant(Combatant::new(2, 20));
+        encounter.add_combatant(Combatant::new(3, 5).with_lair_actions(true));
+
+        // Count 20 comes after everyone at 20 or above
+        assert_eq!(encounter.start(), []);
+        assert_eq!(encounter.next_turn(), []);
+        assert_eq!(encounter.next_turn(), [lair()]);
+        assert!(encounter.resolve_lair_action(3));
+        assert!(!encounter.resolve_lair_action(3));
+        assert_eq!(encounter.next_turn(), []);
+
+        // Only once per round
+        assert_eq!(encounter.next_turn(), []);
+        assert_eq!(encounter.next_turn(), [lair()]);
+
+        // With nobody at 20 or above, the lair goes first
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(3, 5).with_lair_actions(true));
+        assert_eq!(encounter.start(), [lair()]);
+        assert_eq!(encounter.next_turn(), [lair()]);
+
+        // With everybody at 20 or above, the lair goes last
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(3, 21).with_lair_actions(true));
+        assert_eq!(encounter.start(), []);
+        assert_eq!(encounter.next_turn(), [lair()]);
+    }
+
+    #[test]
+    pub fn movement() {
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(1, 15));
+        encounter.add_combatant(Combatant::new(2, 10));
+
+        // Only the current combatant can move, Dash or Disengage
+        assert!(!encounter.dash(1));
+        encounter.start();
+        assert!(!encounter.dash(2));
+        assert!(!encounter.disengage(2));
+
+        assert!(encounter.spend_movement(1, 20, 1, 30));
+        assert!(!encounter.spend_movement(1, 15, 2, 30));
+        assert!(!encounter.spend_movement(3, 5, 0, 30));
+        assert!(!encounter.spend_movement(2, 5, 0, 30));
+        assert!(encounter.dash(1));
+        assert!(encounter.disengage(1));
+        assert!(encounter.spend_movement(1, 15, 2, 30));
+
+        let combatant = encounter.get_combatant(1).unwrap();
+        assert_eq!(combatant.get_movement_used(), 35);
+        assert_eq!(combatant.get_remaining_movement(30), 25);
+        assert_eq!(combatant.get_diagonals(), 2);
+        assert!(combatant.is_disengaged());
+
+        // Movement resets at the start of the combatant's next turn
+        encounter.next_turn();
+        encounter.next_turn();
+        let combatant = encounter.get_combatant(1).unwrap();
+        assert_eq!(combatant.get_remaining_movement(30), 30);
+        assert_eq!(combatant.get_dashes(), 0);
+        assert!(!combatant.is_disengaged());
+    }
+
+    #[test]
+    pub fn serde() {
+        let mut encounter = Encounter::new();
+        encounter.add_combatant(Combatant::new(1, 15));
+        encounter.add_combatant(Combatant::new(3, 10).with_legendary_actions(3).with_lair_actions(true));
+        encounter.start();
+        encounter.next_turn();
+
+        let de: Encounter = serde_yaml::from_str(&serde_yaml::to_string(&encounter).unwrap()).unwrap();
+        assert_eq!(de.get_combatants(), encounter.get_combatants());
+        assert_eq!(de.get_prompts(), encounter.get_prompts());
+        assert_eq!(de.get_current().unwrap().get_entity(), 3);
+    }
+}
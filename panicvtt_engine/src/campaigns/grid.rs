@@ -0,0 +1,514 @@
+use std::{collections::BTreeSet, error::Error, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum GridType {
+    #[default]
+    Square,
+    /// Pointy-topped hexes in offset rows, with odd rows shifted right by half a cell.
+    Hex,
+}
+
+/// How diagonal steps on a square grid are measured.
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum DiagonalRule {
+    /// Every diagonal costs one cell.
+    #[default]
+    Simple,
+    /// Diagonals alternate between one and two cells, counted across a whole turn.
+    Alternating,
+    /// True distance, so each diagonal costs about 1.41 cells.
+    Euclidean,
+}
+
+/// A Scene's map: its grid layout, size in cells and background image.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Grid {
+    #[serde(default)]
+    grid_type: GridType,
+    width: u16,
+    height: u16,
+    /// Distance across one cell in feet.
+    #[serde(default = "default_cell_size")]
+    cell_size: u16,
+    /// Width of one cell on the background image, in pixels.
+    #[serde(default = "default_cell_pixels")]
+    cell_pixels: u16,
+    #[serde(default)]
+    background: Option<String>,
+    #[serde(default)]
+    diagonal_rule: DiagonalRule,
+    /// Cells that cost double to move into.
+    #[serde(default)]
+    difficult_terrain: BTreeSet<GridPosition>,
+}
+
+fn default_cell_size() -> u16 {
+    5
+}
+
+fn default_cell_pixels() -> u16 {
+    70
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self::new(GridType::Square, 20, 20)
+    }
+}
+
+impl Grid {
+    pub fn new(grid_type: GridType, width: u16, height: u16) -> Self {
+        Self {
+            grid_type,
+            width,
+            height,
+            cell_size: default_cell_size(),
+            cell_pixels: default_cell_pixels(),
+            background: None,
+            diagonal_rule: DiagonalRule::default(),
+            difficult_terrain: BTreeSet::new(),
+        }
+    }
+
+    pub fn with_diagonal_rule(mut self, diagonal_rule: DiagonalRule) -> Self {
+        self.diagonal_rule = diagonal_rule;
+        self
+    }
+
+    pub fn with_cell_size(mut self, cell_size: u16, cell_pixels: u16) -> Self {
+        self.cell_size = cell_size;
+        self.cell_pixels = cell_pixels;
+        self
+    }
+
+    pub fn with_background(mut self, background: String) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    pub fn get_grid_type(&self) -> GridType {
+        self.grid_type
+    }
+
+    pub fn get_width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u16 {
+        self.height
+    }
+
+    pub fn get_cell_size(&self) -> u16 {
+        self.cell_size
+    }
+
+    pub fn get_cell_pixels(&self) -> u16 {
+        self.cell_pixels
+    }
+
+    pub fn get_background(&self) -> Option<&str> {
+        self.background.as_deref()
+    }
+
+    pub fn get_diagonal_rule(&self) -> DiagonalRule {
+        self.diagonal_rule
+    }
+
+    pub fn is_difficult_terrain(&self, position: GridPosition) -> bool {
+        self.difficult_terrain.contains(&position)
+    }
+
+    /// Mark or clear a cell as difficult terrain. Returns false if it was already in that state.
+    pub fn set_difficult_terrain(&mut self, position: GridPosition, difficult: bool) -> bool {
+        if difficult {
+            self.difficult_terrain.insert(position)
+        } else {
+            self.difficult_terrain.remove(&position)
+        }
+    }
+
+    /// Whether a square `footprint` cells on a side, with its top left corner at `position`, lies within the grid.
+    /// Hex grids only hold tokens a single cell across.
+    pub fn contains(&self, position: GridPosition, footprint: u8) -> bool {
+        self.supports_footprint(footprint)
+            && position.x as u32 + footprint as u32 <= self.width as u32 && position.y as u32 + footprint as u32 <= self.height as u32
+    }
+
+    /// Whether tokens `footprint` cells across can be placed on this grid. Rows of hexes don't line up, so a 
+    /// square footprint has no hex equivalent.
+    pub fn supports_footprint(&self, footprint: u8) -> bool {
+        self.grid_type == GridType::Square || footprint <= 1
+    }
+
+    /// The centre of a cell, in cells from the top left of the map. Hex rows are spaced closer than their width.
+    pub(crate) fn get_cell_center(&self, position: GridPosition) -> (f32, f32) {
+        let (x, y) = (position.x as f32, position.y as f32);
+        match self.grid_type {
+            GridType::Square => (x + 0.5, y + 0.5),
+            GridType::Hex => (x + 0.5 + 0.5 * (position.y % 2) as f32, y * 3f32.sqrt() / 2.0 + 0.5),
+        }
+    }
+
+    /// Length of an offset between two points on the map in cells, under this grid's diagonal rule.
+    pub(crate) fn measure(&self, dx: f32, dy: f32) -> f32 {
+        let (dx, dy) = (dx.abs(), dy.abs());
+        match (self.grid_type, self.diagonal_rule) {
+            (GridType::Square, DiagonalRule::Simple) => dx.max(dy),
+            (GridType::Square, DiagonalRule::Alternating) => dx.max(dy) + dx.min(dy) / 2.0,
+            (GridType::Square, DiagonalRule::Euclidean) | (GridType::Hex, _) => dx.hypot(dy),
+        }
+    }
+
+    /// Convert offset hex coordinates to axial ones.
+    fn to_axial(position: GridPosition) -> (i32, i32) {
+        let (x, y) = (position.x as i32, position.y as i32);
+        (x - (y - (y & 1)) / 2, y)
+    }
+
+    /// Distance in cells, rounding Euclidean distances to the nearest cell.
+    fn get_cell_distance(&self, from: GridPosition, to: GridPosition) -> f32 {
+        let dx = (from.x as i32 - to.x as i32).unsigned_abs();
+        let dy = (from.y as i32 - to.y as i32).unsigned_abs();
+
+        match (self.grid_type, self.diagonal_rule) {
+            (GridType::Hex, _) => {
+                let ((q1, r1), (q2, r2)) = (Self::to_axial(from), Self::to_axial(to));
+                let (dq, dr) = (q1 - q2, r1 - r2);
+                ((dq.abs() + dr.abs() + (dq + dr).abs()) / 2) as f32
+            },
+            (GridType::Square, DiagonalRule::Simple) => dx.max(dy) as f32,
+            (GridType::Square, DiagonalRule::Alternating) => (dx.max(dy) + dx.min(dy) / 2) as f32,
+            (GridType::Square, DiagonalRule::Euclidean) => ((dx * dx + dy * dy) as f32).sqrt(),
+        }
+    }
+
+    /// Distance in feet between two cells, measured with this grid's diagonal rule.
+    pub fn get_distance(&self, from: GridPosition, to: GridPosition) -> u32 {
+        (self.get_cell_distance(from, to) * self.cell_size as f32).round() as u32
+    }
+
+    /// Whether a single step leads from one cell to the other.
+    pub fn is_adjacent(&self, from: GridPosition, to: GridPosition) -> bool {
+        let dx = (from.x as i32 - to.x as i32).abs();
+        let dy = (from.y as i32 - to.y as i32).abs();
+
+        match self.grid_type {
+            GridType::Square => from != to && dx <= 1 && dy <= 1,
+            GridType::Hex => from != to && self.get_cell_distance(from, to) == 1.0,
+        }
+    }
+
+    /// The cost in feet of moving a token cell by cell from `start` along `path`, and the diagonals it has taken 
+    /// this turn afterwards, starting from `diagonals`. Steps into difficult terrain cost double.
+    ///
+    /// Returns None if a step isn't to an adjacent cell or would leave the grid.
+    pub fn get_path_cost(&self, start: GridPosition, path: &[GridPosition], footprint: u8, diagonals: u16) -> Option<(u32, u16)> {
+        let mut cost = 0.0;
+        let mut diagonals = diagonals;
+        let mut from = start;
+
+        for to in path {
+            if !self.is_adjacent(from, *to) || !self.contains(*to, footprint) {
+                return None;
+            }
+
+            let diagonal = self.grid_type == GridType::Square && from.x != to.x && from.y != to.y;
+            let cells = match (diagonal, self.diagonal_rule) {
+                (false, _) | (true, DiagonalRule::Simple) => 1.0,
+                (true, DiagonalRule::Alternating) => if diagonals.is_multiple_of(2) { 1.0 } else { 2.0 },
+                (true, DiagonalRule::Euclidean) => std::f32::consts::SQRT_2,
+            };
+            if diagonal {
+                diagonals += 1;
+            }
+
+            let difficult = Token::new(0, *to, footprint).get_cells().any(|c| self.is_difficult_terrain(c));
+            cost += cells * if difficult { 2.0 } else { 1.0 };
+            from = *to;
+        }
+
+        Some(((cost * self.cell_size as f32).round() as u32, diagonals))
+    }
+}
+
+/// A cell on a Grid, counted in columns and rows from the top left.
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GridPosition {
+    x: u16,
+    y: u16,
+}
+
+impl GridPosition {
+    pub fn new(x: u16, y: u16) -> Self {
+        Self { x, y }
+    }
+
+    pub fn get_x(&self) -> u16 {
+        self.x
+    }
+
+    pub fn get_y(&self) -> u16 {
+        self.y
+    }
+}
+
+impl Display for GridPosition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// An Entity's presence on a Scene's map.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Token {
+    #[serde(with = "uuid::serde::simple")]
+    entity: Uuid,
+    position: GridPosition,
+    /// Cells along each side of the space the token takes up.
+    footprint: u8,
+    /// Facing in degrees clockwise from up.
+    #[serde(default)]
+    rotation: u16,
+    /// Hidden tokens are only shown to the GM.
+    #[serde(default = "default_visible")]
+    visible: bool,
+    /// Height above the ground in feet.
+    #[serde(default)]
+    elevation: i16,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+impl Token {
+    pub fn new(entity: u128, position: GridPosition, footprint: u8) -> Self {
+        Self {
+            entity: Uuid::from_u128(entity),
+            position,
+            footprint: footprint.max(1),
+            rotation: 0,
+            visible: true,
+            elevation: 0,
+        }
+    }
+
+    pub fn get_entity(&self) -> u128 {
+        self.entity.as_u128()
+    }
+
+    pub fn get_position(&self) -> GridPosition {
+        self.position
+    }
+
+    pub(crate) fn set_position(&mut self, position: GridPosition) {
+        self.position = position;
+    }
+
+    pub fn get_footprint(&self) -> u8 {
+        self.footprint
+    }
+
+    pub fn get_rotation(&self) -> u16 {
+        self.rotation
+    }
+
+    pub fn set_rotation(&mut self, rotation: u16) {
+        self.rotation = rotation % 360;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn get_elevation(&self) -> i16 {
+        self.elevation
+    }
+
+    pub fn set_elevation(&mut self, elevation: i16) {
+        self.elevation = elevation;
+    }
+
+    /// Every cell this token takes up.
+    pub fn get_cells(&self) -> impl Iterator<Item = GridPosition> + '_ {
+        (0..self.footprint as u16).flat_map(move |dy| (0..self.footprint as u16)
+            .map(move |dx| GridPosition::new(self.position.x + dx, self.position.y + dy)))
+    }
+
+    /// Whether this token would share a cell with a token of `footprint` at `position`. Tokens at different elevations pass over each other.
+    pub fn overlaps(&self, position: GridPosition, footprint: u8, elevation: i16) -> bool {
+        let (x, y, f) = (position.x as u32, position.y as u32, footprint as u32);
+        let (sx, sy, sf) = (self.position.x as u32, self.position.y as u32, self.footprint as u32);
+
+        self.elevation == elevation && x < sx + sf && sx < x + f && y < sy + sf && sy < y + f
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TokenErrorKind {
+    OutOfBounds { position: GridPosition, footprint: u8 },
+    UnsupportedFootprint { footprint: u8 },
+    Occupied { position: GridPosition, entity: u128 },
+    NoToken { entity: u128 },
+    InvalidPath,
+    Blocked { position: GridPosition },
+    OverBudget { cost: u32, remaining: u32 },
+    OutOfTurn { entity: u128 },
+}
+
+impl Display for TokenErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenErrorKind::OutOfBounds { position, footprint } => write!(f, "A {0}x{0} token at {1} doesn't fit on the grid", footprint, position),
+            TokenErrorKind::UnsupportedFootprint { footprint } => write!(f, "A {0}x{0} token can't be placed on a hex grid", footprint),
+            TokenErrorKind::Occupied { position, entity } => write!(f, "{} is occupied by {:x}", position, entity),
+            TokenErrorKind::NoToken { entity } => write!(f, "{:x} has no token in this Scene", entity),
+            TokenErrorKind::Blocked { position } => write!(f, "A wall blocks the way to {}", position),
+            TokenErrorKind::InvalidPath => write!(f, "Each step of a move must be to an adjacent cell on the grid"),
+            TokenErrorKind::OverBudget { cost, remaining } => write!(f, "Moving costs {} ft. but only {} ft. of movement is left", cost, remaining),
+            TokenErrorKind::OutOfTurn { entity } => write!(f, "It isn't {:x}'s turn to move", entity),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TokenError {
+    kind: Option<TokenErrorKind>,
+}
+
+impl TokenError {
+    pub fn new() -> Self {
+        TokenError { kind: None }
+    }
+
+    pub fn from_kind(kind: TokenErrorKind) -> Self {
+        TokenError { kind: Some(kind) }
+    }
+
+    pub fn get_kind(&self) -> Option<&TokenErrorKind> {
+        self.kind.as_ref()
+    }
+}
+
+impl Default for TokenError {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Error for TokenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            Some(kind) => write!(f, "{}", kind),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn bounds() {
+        let grid = Grid::new(GridType::Square, 10, 8);
+        assert!(grid.contains(GridPosition::new(0, 0), 1));
+        assert!(grid.contains(GridPosition::new(8, 6), 2));
+        assert!(!grid.contains(GridPosition::new(9, 6), 2));
+        assert!(!grid.contains(GridPosition::new(0, 8), 1));
+        assert!(!grid.contains(GridPosition::new(u16::MAX, 0), 4));
+
+        // Only single-cell tokens fit on hexes
+        let hex = Grid::new(GridType::Hex, 10, 8);
+        assert!(hex.contains(GridPosition::new(9, 7), 1));
+        assert!(!hex.contains(GridPosition::new(0, 0), 2));
+    }
+
+    #[test]
+    pub fn distances() {
+        let (a, b) = (GridPosition::new(0, 0), GridPosition::new(3, 4));
+        assert_eq!(Grid::new(GridType::Square, 10, 10).get_distance(a, b), 20);
+        assert_eq!(Grid::new(GridType::Square, 10, 10).with_diagonal_rule(DiagonalRule::Alternating).get_distance(a, b), 25);
+        assert_eq!(Grid::new(GridType::Square, 10, 10).with_diagonal_rule(DiagonalRule::Euclidean).get_distance(a, b), 25);
+        assert_eq!(Grid::new(GridType::Square, 10, 10).with_cell_size(10, 70).get_distance(a, b), 40);
+
+        // Odd rows are shifted right, so (1, 1) touches (1, 0) and (2, 0) but not (0, 0)
+        let hex = Grid::new(GridType::Hex, 10, 10);
+        assert!(hex.is_adjacent(GridPosition::new(1, 1), GridPosition::new(2, 0)));
+        assert!(!hex.is_adjacent(GridPosition::new(1, 1), GridPosition::new(0, 0)));
+        assert_eq!(hex.get_distance(GridPosition::new(0, 0), GridPosition::new(0, 4)), 20);
+        assert_eq!(hex.get_distance(GridPosition::new(0, 0), GridPosition::new(3, 0)), 15);
+    }
+
+    #[test]
+    pub fn path_costs() {
+        let diagonal = [GridPosition::new(1, 1), GridPosition::new(2, 2), GridPosition::new(3, 3)];
+        let start = GridPosition::new(0, 0);
+
+        let mut grid = Grid::new(GridType::Square, 10, 10);
+        assert_eq!(grid.get_path_cost(start, &diagonal, 1, 0), Some((15, 3)));
+        grid.set_difficult_terrain(GridPosition::new(2, 2), true);
+        assert_eq!(grid.get_path_cost(start, &diagonal, 1, 0), Some((20, 3)));
+
+        // A Large token enters difficult terrain if any of its cells do
+        assert_eq!(grid.get_path_cost(start, &diagonal[..1], 2, 0), Some((10, 1)));
+
+        // 5-10-5 carries on from diagonals already taken this turn
+        let grid = Grid::new(GridType::Square, 10, 10).with_diagonal_rule(DiagonalRule::Alternating);
+        assert_eq!(grid.get_path_cost(start, &diagonal, 1, 0), Some((20, 3)));
+        assert_eq!(grid.get_path_cost(start, &diagonal, 1, 1), Some((25, 4)));
+
+        let grid = Grid::new(GridType::Square, 10, 10).with_diagonal_rule(DiagonalRule::Euclidean);
+        assert_eq!(grid.get_path_cost(start, &diagonal, 1, 0), Some((21, 3)));
+
+        // Skipping cells or leaving the grid
+        assert!(grid.get_path_cost(start, &[GridPosition::new(2, 2)], 1, 0).is_none());
+        assert!(grid.get_path_cost(GridPosition::new(9, 9), &[GridPosition::new(10, 9)], 1, 0).is_none());
+        assert!(grid.get_path_cost(GridPosition::new(8, 8), &[GridPosition::new(9, 9)], 2, 0).is_none());
+    }
+
+    #[test]
+    pub fn tokens() {
+        let mut token = Token::new(1, GridPosition::new(2, 3), 2);
+        assert_eq!(token.get_cells().collect::<Vec<_>>(),
+            [GridPosition::new(2, 3), GridPosition::new(3, 3), GridPosition::new(2, 4), GridPosition::new(3, 4)]);
+
+        assert!(token.overlaps(GridPosition::new(3, 4), 1, 0));
+        assert!(token.overlaps(GridPosition::new(1, 2), 2, 0));
+        assert!(!token.overlaps(GridPosition::new(4, 3), 1, 0));
+        assert!(!token.overlaps(GridPosition::new(1, 2), 1, 0));
+
+        // Flying over another creature
+        token.set_elevation(30);
+        assert!(!token.overlaps(GridPosition::new(3, 4), 1, 0));
+
+        token.set_rotation(450);
+        assert_eq!(token.get_rotation(), 90);
+    }
+
+    #[test]
+    pub fn serde() {
+        let grid: Grid = serde_yaml::from_str("grid_type: Hex\nwidth: 30\nheight: 20\nbackground: maps/cave.webp").unwrap();
+        assert_eq!(grid.get_grid_type(), GridType::Hex);
+        assert_eq!(grid.get_cell_size(), 5);
+        assert_eq!(grid.get_background(), Some("maps/cave.webp"));
+
+        let token: Token = serde_yaml::from_str("entity: 0000000000000000000000000000000a\nposition: { x: 1, y: 2 }\nfootprint: 1").unwrap();
+        assert_eq!(token.get_entity(), 10);
+        assert!(token.is_visible());
+        assert_eq!(token.get_elevation(), 0);
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{grid::{Grid, GridPosition, Token}, vision::get_token_center, walls::{is_sight_blocked, Wall}};
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LightLevel {
+    /// Heavily obscured; creatures without darkvision can't see.
+    Dark,
+    /// Lightly obscured, giving disadvantage on Perception checks that rely on sight.
+    Dim,
+    #[default]
+    Bright,
+}
+
+impl LightLevel {
+    /// How this light looks with darkvision: darkness as dim light, and dim light as bright.
+    pub fn brighten(self) -> Self {
+        match self {
+            LightLevel::Dark => LightLevel::Dim,
+            LightLevel::Dim | LightLevel::Bright => LightLevel::Bright,
+        }
+    }
+}
+
+/// Bright light out to `bright` feet, then dim light for another `dim` feet.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct LightSource {
+    bright: u16,
+    dim: u16,
+}
+
+impl LightSource {
+    pub const CANDLE: Self = Self::new(5, 5);
+    pub const TORCH: Self = Self::new(20, 20);
+    pub const LIGHT_CANTRIP: Self = Self::new(20, 20);
+    pub const LANTERN: Self = Self::new(30, 30);
+
+    pub const fn new(bright: u16, dim: u16) -> Self {
+        Self { bright, dim }
+    }
+
+    pub fn get_bright(&self) -> u16 {
+        self.bright
+    }
+
+    pub fn get_dim(&self) -> u16 {
+        self.dim
+    }
+
+    /// The light this source sheds `distance` feet away, ignoring walls.
+    pub fn get_level_at(&self, distance: f32) -> LightLevel {
+        if distance <= self.bright as f32 {
+            LightLevel::Bright
+        } else if distance <= self.bright as f32 + self.dim as f32 {
+            LightLevel::Dim
+        } else {
+            LightLevel::Dark
+        }
+    }
+}
+
+/// What a light source is attached to.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum LightAnchor {
+    /// Carried by an Entity, moving with its token. Gives no light while the Entity has no token.
+    Entity {
+        #[serde(with = "uuid::serde::simple")]
+        entity: Uuid,
+    },
+    /// Fixed in the middle of a cell.
+    Position { position: GridPosition },
+}
+
+/// A light source placed in a Scene.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Light {
+    source: LightSource,
+    anchor: LightAnchor,
+}
+
+impl Light {
+    pub fn new(source: LightSource, anchor: LightAnchor) -> Self {
+        Self { source, anchor }
+    }
+
+    pub fn on_entity(source: LightSource, entity: u128) -> Self {
+        Self::new(source, LightAnchor::Entity { entity: Uuid::from_u128(entity) })
+    }
+
+    pub fn at_position(source: LightSource, position: GridPosition) -> Self {
+        Self::new(source, LightAnchor::Position { position })
+    }
+
+    pub fn get_source(&self) -> LightSource {
+        self.source
+    }
+
+    pub fn get_anchor(&self) -> LightAnchor {
+        self.anchor
+    }
+
+    /// Where the light shines from, or None if it is carried by an Entity without a token.
+    fn get_center(&self, grid: &Grid, tokens: &[Token]) -> Option<(f32, f32)> {
+        match self.anchor {
+            LightAnchor::Entity { entity } => tokens.iter()
+                .find(|t| t.get_entity() == entity.as_u128())
+                .map(|t| get_token_center(grid, t)),
+            LightAnchor::Position { position } => Some(grid.get_cell_center(position)),
+        }
+    }
+}
+
+/// The light level in one cell: the brightest of the ambient light and every light source that can reach it past the walls.
+pub fn get_light_level(grid: &Grid, walls: &[Wall], ambient: LightLevel, lights: &[Light], tokens: &[Token], cell: GridPosition) -> LightLevel {
+    let centers: Vec<(LightSource, (f32, f32))> = lights.iter().filter_map(|l| Some((l.source, l.get_center(grid, tokens)?))).collect();
+    get_cell_light(grid, walls, ambient, &centers, cell)
+}
+
+/// The light level in every cell of the grid.
+pub fn get_light_levels(grid: &Grid, walls: &[Wall], ambient: LightLevel, lights: &[Light], tokens: &[Token]) -> BTreeMap<GridPosition, LightLevel> {
+    let centers: Vec<(LightSource, (f32, f32))> = lights.iter().filter_map(|l| Some((l.source, l.get_center(grid, tokens)?))).collect();
+
+    (0..grid.get_height())
+        .flat_map(|y| (0..grid.get_width()).map(move |x| GridPosition::new(x, y)))
+        .map(|cell| (cell, get_cell_light(grid, walls, ambient, &centers, cell)))
+        .collect()
+}
+
+fn get_cell_light(grid: &Grid, walls: &[Wall], ambient: LightLevel, centers: &[(LightSource, (f32, f32))], cell: GridPosition) -> LightLevel {
+    let (cx, cy) = grid.get_cell_center(cell);
+
+    centers.iter()
+        .filter(|(_, center)| !is_sight_blocked(walls, *center, (cx, cy)))
+        .map(|(source, (x, y))| source.get_level_at(grid.measure(cx - x, cy - y) * grid.get_cell_size() as f32))
+        .fold(ambient, LightLevel::max)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::campaigns::grid::GridType;
+
+    use super::*;
+
+    #[test]
+    pub fn light_sources() {
+        let torch = LightSource::TORCH;
+        assert_eq!(torch.get_level_at(20.0), LightLevel::Bright);
+        assert_eq!(torch.get_level_at(25.0), LightLevel::Dim);
+        assert_eq!(torch.get_level_at(45.0), LightLevel::Dark);
+
+        assert_eq!(LightLevel::Dark.brighten(), LightLevel::Dim);
+        assert_eq!(LightLevel::Dim.brighten(), LightLevel::Bright);
+    }
+
+    #[test]
+    pub fn light_levels() {
+        let grid = Grid::new(GridType::Square, 20, 20);
+        let tokens = [Token::new(1, GridPosition::new(2, 2), 1)];
+        let lights = [Light::on_entity(LightSource::TORCH, 1), Light::on_entity(LightSource::LANTERN, 55)];
+
+        // A torch lights 4 cells around it brightly, and 4 more dimly
+        let levels = get_light_levels(&grid, &[], LightLevel::Dark, &lights, &tokens);
+        assert_eq!(levels[&GridPosition::new(6, 2)], LightLevel::Bright);
+        assert_eq!(levels[&GridPosition::new(7, 6)], LightLevel::Dim);
+        assert_eq!(levels[&GridPosition::new(11, 2)], LightLevel::Dark);
+        assert_eq!(levels.len(), 400);
+
+        // Ambient light is never made darker, and walls stop the light
+        assert_eq!(get_light_level(&grid, &[], LightLevel::Dim, &lights, &tokens, GridPosition::new(15, 15)), LightLevel::Dim);
+        let walls = [Wall::new(GridPosition::new(4, 0), GridPosition::new(4, 20))];
+        assert_eq!(get_light_level(&grid, &walls, LightLevel::Dark, &lights, &tokens, GridPosition::new(5, 2)), LightLevel::Dark);
+
+        // Fixed lights shine from the middle of their cell
+        let brazier = [Light::at_position(LightSource::new(5, 0), GridPosition::new(10, 10))];
+        let levels = get_light_levels(&grid, &[], LightLevel::Dark, &brazier, &[]);
+        assert_eq!(levels.values().filter(|l| **l == LightLevel::Bright).count(), 9);
+    }
+
+    #[test]
+    pub fn serde() {
+        let lights = vec![Light::on_entity(LightSource::TORCH, 1), Light::at_position(LightSource::CANDLE, GridPosition::new(3, 4))];
+        let de: Vec<Light> = serde_yaml::from_str(&serde_yaml::to_string(&lights).unwrap()).unwrap();
+        assert_eq!(de, lights);
+    }
+}
@@ -1,14 +1,36 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::assets::asset::Asset;
 
-#[derive(Serialize, Deserialize, Debug)]
+use super::{encounter::Encounter, grid::{Grid, GridPosition, Token, TokenError, TokenErrorKind}, lighting::{get_light_level, get_light_levels, Light, LightLevel}, vision::{get_visible_cells, perceive_cell, Sight}, walls::{get_cover, is_sight_blocked, is_step_blocked, Cover, Wall}};
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Scene {
     #[serde(with = "uuid::serde::simple")]
     uuid: Uuid, 
     name: String, 
-    entities: Vec<u128>
+    /// Set by the Campaign holding this Scene, rather than saved with it. 
+    #[serde(skip)]
+    campaign: Option<u128>,
+    entities: Vec<u128>,
+    #[serde(default)]
+    encounter: Option<Encounter>,
+    #[serde(default)]
+    grid: Grid,
+    #[serde(default)]
+    tokens: Vec<Token>,
+    #[serde(default)]
+    walls: Vec<Wall>,
+    #[serde(default)]
+    ambient_light: LightLevel,
+    #[serde(default)]
+    lights: Vec<Light>,
+    /// Cells each player has seen in this Scene, by player name. 
+    #[serde(default)]
+    explored: BTreeMap<String, BTreeSet<GridPosition>>,
 }
 
 impl Scene {
@@ -16,13 +38,252 @@ impl Scene {
         Self {
             uuid: Uuid::now_v7(),
             name,
+            campaign: None,
             entities: Vec::new(),
+            encounter: None,
+            grid: Grid::default(),
+            tokens: Vec::new(),
+            walls: Vec::new(),
+            ambient_light: LightLevel::default(),
+            lights: Vec::new(),
+            explored: BTreeMap::new(),
         }
     }
 
-    pub(crate) fn get_name(&self) -> &str {
+    pub fn get_name(&self) -> &str {
         &self.name
     }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    /// A copy of this Scene under a new UUID and name, with the same map, walls, lights, Entities and tokens. 
+    /// Any combat and what players have explored are left behind. 
+    pub fn duplicate(&self, name: String) -> Self {
+        Self {
+            uuid: Uuid::now_v7(),
+            name,
+            encounter: None,
+            explored: BTreeMap::new(),
+            ..self.clone()
+        }
+    }
+
+    pub fn get_entities(&self) -> &[u128] {
+        &self.entities
+    }
+
+    /// Add an Entity to this Scene. Returns false if it is already present. 
+    pub fn add_entity(&mut self, entity: u128) -> bool {
+        if self.entities.contains(&entity) {
+            return false;
+        }
+
+        self.entities.push(entity);
+        true
+    }
+
+    /// Take an Entity out of this Scene, along with its token and its place in any combat. Returns false if it wasn't present. 
+    pub fn remove_entity(&mut self, entity: u128) -> bool {
+        if !self.entities.contains(&entity) {
+            return false;
+        }
+
+        self.entities.retain(|e| *e != entity);
+        self.remove_token(entity);
+        if let Some(encounter) = self.encounter.as_mut() {
+            encounter.remove_combatant(entity);
+        }
+        true
+    }
+
+    /// The combat running in this Scene, if any. 
+    pub fn get_encounter(&self) -> Option<&Encounter> {
+        self.encounter.as_ref()
+    }
+
+    pub fn get_encounter_mut(&mut self) -> Option<&mut Encounter> {
+        self.encounter.as_mut()
+    }
+
+    /// Replace this Scene's combat, returning the old one. 
+    pub fn set_encounter(&mut self, encounter: Option<Encounter>) -> Option<Encounter> {
+        std::mem::replace(&mut self.encounter, encounter)
+    }
+
+    pub fn get_grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Replace this Scene's map, returning the old one. Tokens that no longer fit are taken off the map. 
+    pub fn set_grid(&mut self, grid: Grid) -> Grid {
+        self.tokens.retain(|t| grid.contains(t.get_position(), t.get_footprint()));
+        std::mem::replace(&mut self.grid, grid)
+    }
+
+    pub fn get_tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    pub fn get_token(&self, entity: u128) -> Option<&Token> {
+        self.tokens.iter().find(|t| t.get_entity() == entity)
+    }
+
+    /// Rotation, visibility and elevation can be changed freely; use `move_token` to change position. 
+    pub fn get_token_mut(&mut self, entity: u128) -> Option<&mut Token> {
+        self.tokens.iter_mut().find(|t| t.get_entity() == entity)
+    }
+
+    /// Check that a token for `entity` could stand at `position` without leaving the grid or sharing space with another token. 
+    pub fn check_space(&self, entity: u128, position: GridPosition, footprint: u8, elevation: i16) -> Result<(), TokenError> {
+        if !self.grid.supports_footprint(footprint) {
+            return Err(TokenError::from_kind(TokenErrorKind::UnsupportedFootprint { footprint }));
+        }
+        if !self.grid.contains(position, footprint) {
+            return Err(TokenError::from_kind(TokenErrorKind::OutOfBounds { position, footprint }));
+        }
+
+        match self.tokens.iter().find(|t| t.get_entity() != entity && t.overlaps(position, footprint, elevation)) {
+            Some(t) => Err(TokenError::from_kind(TokenErrorKind::Occupied { position, entity: t.get_entity() })),
+            None => Ok(()),
+        }
+    }
+
+    /// Put a token on the map, replacing any the Entity already has, and add its Entity to this Scene. 
+    pub fn place_token(&mut self, token: Token) -> Result<(), TokenError> {
+        self.check_space(token.get_entity(), token.get_position(), token.get_footprint(), token.get_elevation())?;
+
+        self.add_entity(token.get_entity());
+        self.remove_token(token.get_entity());
+        self.tokens.push(token);
+        Ok(())
+    }
+
+    pub fn move_token(&mut self, entity: u128, position: GridPosition) -> Result<(), TokenError> {
+        let Some(token) = self.get_token(entity) else {
+            return Err(TokenError::from_kind(TokenErrorKind::NoToken { entity }));
+        };
+        self.check_space(entity, position, token.get_footprint(), token.get_elevation())?;
+
+        self.get_token_mut(entity).unwrap().set_position(position);
+        Ok(())
+    }
+
+    /// Take an Entity's token off the map. The Entity stays in this Scene. 
+    pub fn remove_token(&mut self, entity: u128) -> Option<Token> {
+        let index = self.tokens.iter().position(|t| t.get_entity() == entity)?;
+        Some(self.tokens.remove(index))
+    }
+
+    pub fn get_walls(&self) -> &[Wall] {
+        &self.walls
+    }
+
+    /// Walls are identified by their index, which shifts down when an earlier wall is removed. 
+    pub fn get_wall_mut(&mut self, index: usize) -> Option<&mut Wall> {
+        self.walls.get_mut(index)
+    }
+
+    /// Add a wall, returning its index. 
+    pub fn add_wall(&mut self, wall: Wall) -> usize {
+        self.walls.push(wall);
+        self.walls.len() - 1
+    }
+
+    pub fn remove_wall(&mut self, index: usize) -> Option<Wall> {
+        (index < self.walls.len()).then(|| self.walls.remove(index))
+    }
+
+    /// Whether a wall stops a token of `footprint` stepping from one cell to the next. 
+    pub fn is_step_blocked(&self, from: GridPosition, to: GridPosition, footprint: u8) -> bool {
+        is_step_blocked(&self.grid, &self.walls, from, to, footprint)
+    }
+
+    /// Whether walls block every line of sight from the centre of one cell to the other. 
+    pub fn is_sight_blocked(&self, from: GridPosition, to: GridPosition) -> bool {
+        is_sight_blocked(&self.walls, self.grid.get_cell_center(from), self.grid.get_cell_center(to))
+    }
+
+    pub fn get_ambient_light(&self) -> LightLevel {
+        self.ambient_light
+    }
+
+    pub fn set_ambient_light(&mut self, ambient_light: LightLevel) {
+        self.ambient_light = ambient_light;
+    }
+
+    pub fn get_lights(&self) -> &[Light] {
+        &self.lights
+    }
+
+    /// Add a light source, returning its index. Lights are identified by index like walls. 
+    pub fn add_light(&mut self, light: Light) -> usize {
+        self.lights.push(light);
+        self.lights.len() - 1
+    }
+
+    pub fn remove_light(&mut self, index: usize) -> Option<Light> {
+        (index < self.lights.len()).then(|| self.lights.remove(index))
+    }
+
+    pub fn get_light_level(&self, cell: GridPosition) -> LightLevel {
+        get_light_level(&self.grid, &self.walls, self.ambient_light, &self.lights, &self.tokens, cell)
+    }
+
+    pub fn get_light_levels(&self) -> BTreeMap<GridPosition, LightLevel> {
+        get_light_levels(&self.grid, &self.walls, self.ambient_light, &self.lights, &self.tokens)
+    }
+
+    /// How light a cell looks to an Entity with `sight`, or Dark if walls block its view. Returns None if it has no token. 
+    pub fn perceive_cell(&self, entity: u128, sight: &Sight, cell: GridPosition) -> Option<LightLevel> {
+        Some(perceive_cell(&self.grid, &self.walls, self.get_token(entity)?, sight, cell, self.get_light_level(cell)))
+    }
+
+    /// Cells the tokens of any of `viewers` can see with their senses, in this Scene's light. Entities without a token see nothing. 
+    pub fn get_visible_cells(&self, viewers: &[(u128, Sight)]) -> BTreeSet<GridPosition> {
+        let light = self.get_light_levels();
+        viewers.iter()
+            .filter_map(|(e, sight)| Some((self.get_token(*e)?, sight)))
+            .flat_map(|(t, sight)| get_visible_cells(&self.grid, &self.walls, &light, t, sight))
+            .collect()
+    }
+
+    /// Tokens the tokens of any of `viewers` can see: their own, and any others that stand in a visible cell and 
+    /// haven't been hidden by the GM. 
+    pub fn get_visible_tokens(&self, viewers: &[(u128, Sight)]) -> Vec<&Token> {
+        let cells = self.get_visible_cells(viewers);
+        self.tokens.iter()
+            .filter(|t| viewers.iter().any(|(e, _)| *e == t.get_entity()) || (t.is_visible() && t.get_cells().any(|c| cells.contains(&c))))
+            .collect()
+    }
+
+    /// Cells a player has seen in this Scene. Returns None if they haven't explored it at all. 
+    pub fn get_explored_cells(&self, player: &str) -> Option<&BTreeSet<GridPosition>> {
+        self.explored.get(player)
+    }
+
+    /// Add the cells a player's tokens can currently see to what they have explored. Returns how many cells were new. 
+    pub fn explore(&mut self, player: &str, viewers: &[(u128, Sight)]) -> usize {
+        let cells = self.get_visible_cells(viewers);
+        let explored = self.explored.entry(String::from(player)).or_default();
+        let before = explored.len();
+        explored.extend(cells);
+        explored.len() - before
+    }
+
+    /// Forget what a player has explored, or every player if None. 
+    pub fn reset_explored(&mut self, player: Option<&str>) {
+        match player {
+            Some(player) => { self.explored.remove(player); },
+            None => self.explored.clear(),
+        }
+    }
+
+    /// The cover walls give one Entity's token against another's. Returns None if either has no token. 
+    pub fn get_cover(&self, from: u128, to: u128) -> Option<Cover> {
+        Some(get_cover(&self.grid, &self.walls, self.get_token(from)?, self.get_token(to)?))
+    }
 }
 
 impl Asset for Scene {
@@ -31,6 +292,141 @@ impl Asset for Scene {
     }
     
     fn get_owning_campaign(&self) -> Option<u128> {
-        todo!()
+        self.campaign
+    }
+
+    fn set_owning_campaign(&mut self, campaign: Option<u128>) {
+        self.campaign = campaign;
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::campaigns::{grid::GridType, lighting::LightSource};
+
+    use super::*;
+
+    #[test]
+    pub fn tokens() {
+        let mut scene = Scene::new(String::from("Crossroads"));
+        scene.set_grid(Grid::new(GridType::Square, 10, 10));
+
+        assert!(scene.place_token(Token::new(1, GridPosition::new(0, 0), 2)).is_ok());
+        assert_eq!(scene.get_entities(), [1]);
+
+        // Out of bounds, or on top of another token 
+        let e = scene.place_token(Token::new(2, GridPosition::new(9, 9), 2)).unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::OutOfBounds { position: GridPosition::new(9, 9), footprint: 2 }));
+        let e = scene.place_token(Token::new(2, GridPosition::new(1, 1), 1)).unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::Occupied { position: GridPosition::new(1, 1), entity: 1 }));
+        assert!(scene.place_token(Token::new(2, GridPosition::new(2, 0), 1)).is_ok());
+
+        // A token can move within its own space but not into another's 
+        assert!(scene.move_token(1, GridPosition::new(0, 1)).is_ok());
+        assert!(scene.move_token(1, GridPosition::new(1, 0)).is_err());
+        assert_eq!(scene.move_token(3, GridPosition::new(5, 5)).unwrap_err().get_kind(), Some(&TokenErrorKind::NoToken { entity: 3 }));
+        assert_eq!(scene.get_token(1).unwrap().get_position(), GridPosition::new(0, 1));
+
+        // Flying tokens pass over others 
+        scene.get_token_mut(2).unwrap().set_elevation(20);
+        assert!(scene.move_token(2, GridPosition::new(1, 1)).is_ok());
+
+        // Shrinking the map drops tokens that no longer fit, and hexes only hold single-cell tokens 
+        scene.place_token(Token::new(3, GridPosition::new(8, 8), 1)).unwrap();
+        scene.set_grid(Grid::new(GridType::Hex, 5, 5));
+        assert!(scene.get_token(3).is_none());
+        assert!(scene.get_token(1).is_none());
+        assert_eq!(scene.get_entities(), [1, 2, 3]);
+        let e = scene.place_token(Token::new(1, GridPosition::new(0, 0), 2)).unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::UnsupportedFootprint { footprint: 2 }));
+
+        assert!(scene.remove_token(2).is_some());
+        assert!(scene.remove_token(2).is_none());
+    }
+
+    #[test]
+    pub fn duplicate() {
+        let mut scene = Scene::new(String::from("Tavern"));
+        scene.place_token(Token::new(1, GridPosition::new(2, 2), 1)).unwrap();
+        scene.add_wall(Wall::new(GridPosition::new(0, 0), GridPosition::new(5, 0)));
+        scene.set_encounter(Some(Encounter::default()));
+        scene.set_owning_campaign(Some(7));
+
+        let copy = scene.duplicate(String::from("Tavern (Night)"));
+        assert_ne!(copy.get_uuid(), scene.get_uuid());
+        assert_eq!(copy.get_name(), "Tavern (Night)");
+        assert_eq!(copy.get_owning_campaign(), Some(7));
+        assert_eq!(copy.get_tokens(), scene.get_tokens());
+        assert_eq!(copy.get_walls().len(), 1);
+        assert!(copy.get_encounter().is_none());
+
+        // Removing an Entity takes its token with it 
+        assert!(scene.remove_entity(1));
+        assert!(!scene.remove_entity(1));
+        assert!(scene.get_entities().is_empty() && scene.get_tokens().is_empty());
+    }
+
+    #[test]
+    pub fn fog_of_war() {
+        let mut scene = Scene::new(String::from("Crypt"));
+        scene.set_grid(Grid::new(GridType::Square, 10, 10));
+        scene.add_wall(Wall::new(GridPosition::new(5, 0), GridPosition::new(5, 10)));
+        scene.place_token(Token::new(1, GridPosition::new(2, 2), 1)).unwrap();
+        scene.place_token(Token::new(2, GridPosition::new(4, 7), 1)).unwrap();
+        scene.place_token(Token::new(3, GridPosition::new(8, 2), 1)).unwrap();
+
+        let sight = Sight::default();
+        assert_eq!(scene.get_visible_cells(&[(1, sight)]).len(), 50);
+        assert_eq!(scene.get_visible_cells(&[(1, sight), (3, sight)]).len(), 100);
+        assert!(scene.get_visible_cells(&[(55, sight)]).is_empty());
+
+        // The ghoul behind the wall is hidden, and so is the one the GM has hidden 
+        let visible: Vec<u128> = scene.get_visible_tokens(&[(1, sight)]).iter().map(|t| t.get_entity()).collect();
+        assert_eq!(visible, [1, 2]);
+        scene.get_token_mut(2).unwrap().set_visible(false);
+        let visible: Vec<u128> = scene.get_visible_tokens(&[(1, sight)]).iter().map(|t| t.get_entity()).collect();
+        assert_eq!(visible, [1]);
+
+        // Explored cells stay explored once the token moves on 
+        assert!(scene.get_explored_cells("alice").is_none());
+        assert_eq!(scene.explore("alice", &[(1, sight)]), 50);
+        assert_eq!(scene.explore("alice", &[(1, sight)]), 0);
+        scene.move_token(1, GridPosition::new(7, 7)).unwrap();
+        assert_eq!(scene.explore("alice", &[(1, sight)]), 50);
+        assert_eq!(scene.get_explored_cells("alice").unwrap().len(), 100);
+        assert!(scene.get_explored_cells("bob").is_none());
+
+        let de: Scene = serde_yaml::from_str(&serde_yaml::to_string(&scene).unwrap()).unwrap();
+        assert_eq!(de.get_explored_cells("alice"), scene.get_explored_cells("alice"));
+
+        scene.reset_explored(Some("alice"));
+        assert!(scene.get_explored_cells("alice").is_none());
+    }
+
+    #[test]
+    pub fn lighting() {
+        let mut scene = Scene::new(String::from("Mine"));
+        scene.set_grid(Grid::new(GridType::Square, 20, 20));
+        scene.place_token(Token::new(1, GridPosition::new(2, 2), 1)).unwrap();
+        scene.place_token(Token::new(2, GridPosition::new(12, 2), 1)).unwrap();
+        assert_eq!(scene.get_light_level(GridPosition::new(12, 2)), LightLevel::Bright);
+
+        scene.set_ambient_light(LightLevel::Dark);
+        assert_eq!(scene.get_visible_tokens(&[(1, Sight::default())]).len(), 1);
+        assert_eq!(scene.perceive_cell(1, &Sight::new(60, 0), GridPosition::new(12, 2)), Some(LightLevel::Dim));
+        assert!(scene.perceive_cell(55, &Sight::default(), GridPosition::new(12, 2)).is_none());
+
+        // The miner's lantern lights them up 
+        let lantern = scene.add_light(Light::on_entity(LightSource::LANTERN, 2));
+        assert_eq!(scene.get_light_level(GridPosition::new(12, 2)), LightLevel::Bright);
+        assert_eq!(scene.get_visible_tokens(&[(1, Sight::default())]).len(), 2);
+
+        // Lights move with their token 
+        scene.move_token(2, GridPosition::new(19, 19)).unwrap();
+        assert_eq!(scene.get_light_level(GridPosition::new(12, 2)), LightLevel::Dark);
+
+        assert!(scene.remove_light(lantern).is_some());
+        assert!(scene.remove_light(lantern).is_none());
+        assert_eq!(scene.get_light_level(GridPosition::new(19, 19)), LightLevel::Dark);
     }
 }
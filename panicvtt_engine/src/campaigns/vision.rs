@@ -0,0 +1,128 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{grid::{Grid, GridPosition, GridType, Token}, lighting::LightLevel, walls::{is_sight_blocked, Wall}};
+
+/// How far a viewer can see without light, in feet.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct Sight {
+    darkvision: u16,
+    /// Blindsight or truesight, which see regardless of light.
+    blindsight: u16,
+}
+
+impl Sight {
+    pub fn new(darkvision: u16, blindsight: u16) -> Self {
+        Self { darkvision, blindsight }
+    }
+
+    pub fn get_darkvision(&self) -> u16 {
+        self.darkvision
+    }
+
+    pub fn get_blindsight(&self) -> u16 {
+        self.blindsight
+    }
+
+    /// How `light` looks to this viewer from `distance` feet away.
+    pub fn perceive(&self, light: LightLevel, distance: f32) -> LightLevel {
+        if distance <= self.blindsight as f32 {
+            LightLevel::Bright
+        } else if distance <= self.darkvision as f32 {
+            light.brighten()
+        } else {
+            light
+        }
+    }
+}
+
+/// The centre of a token's whole footprint, in cells from the top left of the map.
+pub(crate) fn get_token_center(grid: &Grid, token: &Token) -> (f32, f32) {
+    let position = token.get_position();
+    match grid.get_grid_type() {
+        GridType::Square => {
+            let half = token.get_footprint() as f32 / 2.0;
+            (position.get_x() as f32 + half, position.get_y() as f32 + half)
+        },
+        GridType::Hex => grid.get_cell_center(position),
+    }
+}
+
+/// How light a cell looks to a token, or Dark if walls block its view. `light` is the cell's actual light level.
+pub fn perceive_cell(grid: &Grid, walls: &[Wall], token: &Token, sight: &Sight, cell: GridPosition, light: LightLevel) -> LightLevel {
+    let (x, y) = get_token_center(grid, token);
+    let (cx, cy) = grid.get_cell_center(cell);
+    if is_sight_blocked(walls, (x, y), (cx, cy)) {
+        return LightLevel::Dark;
+    }
+
+    sight.perceive(light, grid.measure(cx - x, cy - y) * grid.get_cell_size() as f32)
+}
+
+/// Cells a token can see: its own, and every cell whose centre it has a clear line of sight to from its centre and 
+/// isn't too dark for it to make out. `light` holds each cell's light level; missing cells are dark.
+pub fn get_visible_cells(grid: &Grid, walls: &[Wall], light: &BTreeMap<GridPosition, LightLevel>, token: &Token, sight: &Sight) -> BTreeSet<GridPosition> {
+    (0..grid.get_height())
+        .flat_map(|y| (0..grid.get_width()).map(move |x| GridPosition::new(x, y)))
+        .filter(|cell| token.get_cells().any(|c| c == *cell) 
+            || perceive_cell(grid, walls, token, sight, *cell, light.get(cell).copied().unwrap_or(LightLevel::Dark)) != LightLevel::Dark)
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::campaigns::{lighting::{get_light_levels, Light, LightSource}, walls::WallKind};
+
+    use super::*;
+
+    #[test]
+    pub fn visible_cells() {
+        let grid = Grid::new(GridType::Square, 10, 10);
+        let light = get_light_levels(&grid, &[], LightLevel::Bright, &[], &[]);
+        let get_visible_cells = |walls: &[Wall], token: &Token| get_visible_cells(&grid, walls, &light, token, &Sight::default());
+        let token = Token::new(1, GridPosition::new(2, 2), 1);
+        assert_eq!(get_visible_cells(&[], &token).len(), 100);
+
+        // A wall across the map hides everything past it
+        let walls = [Wall::new(GridPosition::new(5, 0), GridPosition::new(5, 10))];
+        let cells = get_visible_cells(&walls, &token);
+        assert_eq!(cells.len(), 50);
+        assert!(cells.contains(&GridPosition::new(4, 9)) && !cells.contains(&GridPosition::new(5, 2)));
+
+        // Windows don't hide anything, and a one-way wall drawn upward only hides from its left
+        let window = [walls[0].with_kind(WallKind::Window)];
+        assert_eq!(get_visible_cells(&window, &token).len(), 100);
+        let one_way = [Wall::new(GridPosition::new(5, 10), GridPosition::new(5, 0)).with_kind(WallKind::OneWay)];
+        assert_eq!(get_visible_cells(&one_way, &token).len(), 50);
+        assert_eq!(get_visible_cells(&one_way, &Token::new(1, GridPosition::new(7, 2), 1)).len(), 100);
+
+        // Large tokens look out from the middle of their space
+        let large = Token::new(1, GridPosition::new(3, 3), 2);
+        assert_eq!(get_token_center(&grid, &large), (4.0, 4.0));
+        assert!(get_visible_cells(&walls, &large).contains(&GridPosition::new(4, 4)));
+    }
+
+    #[test]
+    pub fn darkness() {
+        let grid = Grid::new(GridType::Square, 20, 20);
+        let token = Token::new(1, GridPosition::new(2, 2), 1);
+        let dark = get_light_levels(&grid, &[], LightLevel::Dark, &[], &[]);
+        assert_eq!(get_visible_cells(&grid, &[], &dark, &token, &Sight::default()).len(), 1);
+
+        // Darkvision sees darkness as dim light out to its range 
+        let darkvision = Sight::new(60, 0);
+        let cells = get_visible_cells(&grid, &[], &dark, &token, &darkvision);
+        assert_eq!(cells.len(), 15 * 15);
+        assert_eq!(perceive_cell(&grid, &[], &token, &darkvision, GridPosition::new(14, 2), LightLevel::Dark), LightLevel::Dim);
+        assert_eq!(perceive_cell(&grid, &[], &token, &darkvision, GridPosition::new(15, 2), LightLevel::Dark), LightLevel::Dark);
+        assert_eq!(perceive_cell(&grid, &[], &token, &darkvision, GridPosition::new(5, 5), LightLevel::Dim), LightLevel::Bright);
+        assert_eq!(perceive_cell(&grid, &[], &token, &Sight::new(0, 10), GridPosition::new(4, 4), LightLevel::Dark), LightLevel::Bright);
+
+        // A torch lets anyone see around it 
+        let torchlit = get_light_levels(&grid, &[], LightLevel::Dark, &[Light::on_entity(LightSource::TORCH, 1)], std::slice::from_ref(&token));
+        assert_eq!(get_visible_cells(&grid, &[], &torchlit, &token, &Sight::default()).len(), 11 * 11);
+
+        // Walls still block darkvision 
+        let walls = [Wall::new(GridPosition::new(5, 0), GridPosition::new(5, 20))];
+        assert_eq!(perceive_cell(&grid, &walls, &token, &darkvision, GridPosition::new(6, 2), LightLevel::Bright), LightLevel::Dark);
+    }
+}
@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+
+use crate::entities::modifier::StatIntType;
+
+use super::grid::{Grid, GridPosition, GridType, Token};
+
+/// Slack for points that lie exactly on a wall.
+const EPSILON: f32 = 0.0001;
+
+/// How far sight lines are pulled back from their ends, so a corner touching a wall doesn't count as blocked.
+const SIGHT_INSET: f32 = 0.01;
+
+type Point = (f32, f32);
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum WallKind {
+    /// Blocks sight and movement.
+    #[default]
+    Wall,
+    /// Blocks sight and movement unless open.
+    Door,
+    /// Blocks movement unless open, but never sight.
+    Window,
+    /// Blocks movement, and blocks sight only for viewers on its left, facing from `start` to `end`.
+    /// With y running down the map, a one-way wall drawn left to right hides what is below it from those above.
+    OneWay,
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum WallState {
+    Open,
+    #[default]
+    Closed,
+    /// Closed, and can't be opened without unlocking it first.
+    Locked,
+}
+
+/// A wall segment on a Scene's map. Walls run between grid intersections and reach all the way up, so elevation
+/// doesn't matter.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Wall {
+    start: GridPosition,
+    end: GridPosition,
+    #[serde(default)]
+    kind: WallKind,
+    #[serde(default)]
+    state: WallState,
+}
+
+impl Wall {
+    pub fn new(start: GridPosition, end: GridPosition) -> Self {
+        Self { start, end, kind: WallKind::default(), state: WallState::default() }
+    }
+
+    pub fn with_kind(mut self, kind: WallKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_state(mut self, state: WallState) -> Self {
+        self.state = state;
+        self
+    }
+
+    pub fn get_start(&self) -> GridPosition {
+        self.start
+    }
+
+    pub fn get_end(&self) -> GridPosition {
+        self.end
+    }
+
+    pub fn get_kind(&self) -> WallKind {
+        self.kind
+    }
+
+    pub fn get_state(&self) -> WallState {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: WallState) {
+        self.state = state;
+    }
+
+    /// Open a door or window. Returns false if it is locked or can't be opened.
+    pub fn open(&mut self) -> bool {
+        match (self.kind, self.state) {
+            (WallKind::Door | WallKind::Window, WallState::Open | WallState::Closed) => {
+                self.state = WallState::Open;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    pub fn blocks_movement(&self) -> bool {
+        match self.kind {
+            WallKind::Wall | WallKind::OneWay => true,
+            WallKind::Door | WallKind::Window => self.state != WallState::Open,
+        }
+    }
+
+    fn get_points(&self) -> (Point, Point) {
+        ((self.start.get_x() as f32, self.start.get_y() as f32), (self.end.get_x() as f32, self.end.get_y() as f32))
+    }
+
+    /// Whether this wall stops a viewer at `viewer` from seeing past it.
+    pub(crate) fn blocks_sight(&self, viewer: Point) -> bool {
+        match self.kind {
+            WallKind::Wall => true,
+            WallKind::Door => self.state != WallState::Open,
+            WallKind::Window => false,
+            WallKind::OneWay => {
+                let (start, end) = self.get_points();
+                orientation(start, end, viewer) < 0.0
+            },
+        }
+    }
+
+    /// Whether the segment from `a` to `b` touches this wall.
+    pub(crate) fn crosses(&self, a: Point, b: Point) -> bool {
+        let (start, end) = self.get_points();
+        segments_touch(a, b, start, end)
+    }
+}
+
+/// Positive if `c` lies clockwise of `a` to `b` on the map, negative if anticlockwise, and 0 if they are in line.
+fn orientation(a: Point, b: Point, c: Point) -> f32 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+fn within_bounds(a: Point, b: Point, p: Point) -> bool {
+    p.0 >= a.0.min(b.0) - EPSILON && p.0 <= a.0.max(b.0) + EPSILON && p.1 >= a.1.min(b.1) - EPSILON && p.1 <= a.1.max(b.1) + EPSILON
+}
+
+/// Whether two segments cross or touch.
+fn segments_touch(p1: Point, p2: Point, q1: Point, q2: Point) -> bool {
+    let (d1, d2) = (orientation(q1, q2, p1), orientation(q1, q2, p2));
+    let (d3, d4) = (orientation(p1, p2, q1), orientation(p1, p2, q2));
+    let opposite = |a: f32, b: f32| (a > EPSILON && b < -EPSILON) || (a < -EPSILON && b > EPSILON);
+
+    if opposite(d1, d2) && opposite(d3, d4) {
+        return true;
+    }
+
+    (d1.abs() <= EPSILON && within_bounds(q1, q2, p1))
+        || (d2.abs() <= EPSILON && within_bounds(q1, q2, p2))
+        || (d3.abs() <= EPSILON && within_bounds(p1, p2, q1))
+        || (d4.abs() <= EPSILON && within_bounds(p1, p2, q2))
+}
+
+/// Whether any wall blocks sight from `a` to `b`. The line is pulled back slightly at both ends.
+pub(crate) fn is_sight_blocked(walls: &[Wall], a: Point, b: Point) -> bool {
+    let length = (b.0 - a.0).hypot(b.1 - a.1);
+    if length <= 2.0 * SIGHT_INSET {
+        return false;
+    }
+
+    let (ux, uy) = ((b.0 - a.0) / length * SIGHT_INSET, (b.1 - a.1) / length * SIGHT_INSET);
+    let (a, b) = ((a.0 + ux, a.1 + uy), (b.0 - ux, b.1 - uy));
+    walls.iter().any(|w| w.blocks_sight(a) && w.crosses(a, b))
+}
+
+/// Whether a wall stops a token of `footprint` stepping from one cell to the next.
+pub(crate) fn is_step_blocked(grid: &Grid, walls: &[Wall], from: GridPosition, to: GridPosition, footprint: u8) -> bool {
+    let from_cells = Token::new(0, from, footprint).get_cells().collect::<Vec<_>>();
+    let to_cells = Token::new(0, to, footprint).get_cells().collect::<Vec<_>>();
+
+    from_cells.iter().zip(to_cells.iter()).any(|(a, b)| {
+        let (a, b) = (grid.get_cell_center(*a), grid.get_cell_center(*b));
+        walls.iter().any(|w| w.blocks_movement() && w.crosses(a, b))
+    })
+}
+
+/// How well walls protect a target from an attacker.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Cover {
+    Clear,
+    Half,
+    ThreeQuarters,
+    /// The target can't be seen or targeted directly.
+    Total,
+}
+
+impl Cover {
+    /// The bonus to AC and Dexterity saves.
+    pub fn get_bonus(&self) -> StatIntType {
+        match self {
+            Cover::Clear | Cover::Total => 0,
+            Cover::Half => 2,
+            Cover::ThreeQuarters => 5,
+        }
+    }
+}
+
+/// The cover walls give `to` against `from`. Only walls give cover; other creatures don't.
+///
+/// On a square grid, lines run from the attacker's best corner to each corner of the target's least hidden cell;
+/// one or two blocked lines give half cover, three give three-quarters and four give total cover. On a hex grid,
+/// lines run between cell centres and the target is either in full view or in total cover.
+pub fn get_cover(grid: &Grid, walls: &[Wall], from: &Token, to: &Token) -> Cover {
+    if grid.get_grid_type() == GridType::Hex {
+        let visible = from.get_cells().any(|a| to.get_cells()
+            .any(|b| !is_sight_blocked(walls, grid.get_cell_center(a), grid.get_cell_center(b))));
+        return if visible { Cover::Clear } else { Cover::Total };
+    }
+
+    let corners = |cell: GridPosition| {
+        let (x, y) = (cell.get_x() as f32, cell.get_y() as f32);
+        [(x, y), (x + 1.0, y), (x, y + 1.0), (x + 1.0, y + 1.0)]
+    };
+
+    let blocked = from.get_cells().flat_map(corners)
+        .flat_map(|viewer| to.get_cells().map(move |cell| corners(cell).into_iter()
+            .filter(|corner| is_sight_blocked(walls, viewer, *corner))
+            .count()))
+        .min()
+        .unwrap_or(4);
+
+    match blocked {
+        0 => Cover::Clear,
+        1 | 2 => Cover::Half,
+        3 => Cover::ThreeQuarters,
+        _ => Cover::Total,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn wall(x1: u16, y1: u16, x2: u16, y2: u16) -> Wall {
+        Wall::new(GridPosition::new(x1, y1), GridPosition::new(x2, y2))
+    }
+
+    fn token(x: u16, y: u16) -> Token {
+        Token::new(1, GridPosition::new(x, y), 1)
+    }
+
+    #[test]
+    pub fn doors_and_windows() {
+        let mut door = wall(0, 0, 1, 0).with_kind(WallKind::Door);
+        assert!(door.blocks_movement() && door.blocks_sight((0.5, 0.5)));
+        assert!(door.open());
+        assert!(!door.blocks_movement() && !door.blocks_sight((0.5, 0.5)));
+
+        let mut locked = door.with_state(WallState::Locked);
+        assert!(!locked.open());
+        locked.set_state(WallState::Closed);
+        assert!(locked.open());
+
+        let window = wall(0, 0, 1, 0).with_kind(WallKind::Window);
+        assert!(window.blocks_movement() && !window.blocks_sight((0.5, 0.5)));
+        let mut plain = wall(0, 0, 1, 0);
+        assert!(!plain.open());
+
+        // Drawn left to right, a one-way wall hides what's below from viewers above
+        let one_way = wall(0, 5, 10, 5).with_kind(WallKind::OneWay);
+        assert!(one_way.blocks_sight((3.0, 2.0)));
+        assert!(!one_way.blocks_sight((3.0, 8.0)));
+        assert!(is_sight_blocked(&[one_way], (3.0, 2.0), (3.0, 8.0)));
+        assert!(!is_sight_blocked(&[one_way], (3.0, 8.0), (3.0, 2.0)));
+    }
+
+    #[test]
+    pub fn cover() {
+        let grid = Grid::new(GridType::Square, 20, 20);
+        let attacker = token(2, 5);
+
+        assert_eq!(get_cover(&grid, &[], &attacker, &token(10, 5)), Cover::Clear);
+
+        // A long wall between them hides the target completely
+        let walls = [wall(6, 0, 6, 20)];
+        assert_eq!(get_cover(&grid, &walls, &attacker, &token(10, 5)), Cover::Total);
+
+        // Peeking past the end of a wall
+        let walls = [wall(6, 0, 6, 5)];
+        assert_eq!(get_cover(&grid, &walls, &attacker, &token(8, 4)), Cover::Half);
+        assert_eq!(get_cover(&grid, &walls, &attacker, &token(8, 7)), Cover::Clear);
+
+        // Seen at an angle through a gap in the wall, only one corner of the target's cell shows
+        let walls = [wall(6, 0, 6, 5), wall(6, 6, 6, 20)];
+        assert_eq!(get_cover(&grid, &walls, &attacker, &token(9, 3)), Cover::ThreeQuarters);
+        assert_eq!(get_cover(&grid, &walls, &attacker, &token(9, 2)), Cover::Total);
+
+        // Standing against a wall doesn't give cover from the open side
+        let walls = [wall(0, 5, 20, 5)];
+        assert_eq!(get_cover(&grid, &walls, &token(2, 5), &token(10, 5)), Cover::Clear);
+
+        // An open door doesn't block, but a closed one does
+        let door = wall(6, 0, 6, 20).with_kind(WallKind::Door);
+        assert_eq!(get_cover(&grid, &[door.with_state(WallState::Open)], &attacker, &token(10, 5)), Cover::Clear);
+        assert_eq!(get_cover(&grid, &[door], &attacker, &token(10, 5)), Cover::Total);
+    }
+
+    #[test]
+    pub fn movement() {
+        let grid = Grid::new(GridType::Square, 20, 20);
+        let walls = [wall(6, 0, 6, 5), wall(0, 8, 20, 8).with_kind(WallKind::Window)];
+
+        assert!(is_step_blocked(&grid, &walls, GridPosition::new(5, 2), GridPosition::new(6, 2), 1));
+        assert!(!is_step_blocked(&grid, &walls, GridPosition::new(5, 5), GridPosition::new(6, 5), 1));
+        assert!(is_step_blocked(&grid, &walls, GridPosition::new(3, 7), GridPosition::new(3, 8), 1));
+
+        // Part of a Large token runs into the wall
+        assert!(is_step_blocked(&grid, &walls, GridPosition::new(4, 4), GridPosition::new(5, 4), 2));
+    }
+
+    #[test]
+    pub fn serde() {
+        let door = wall(1, 2, 1, 4).with_kind(WallKind::Door).with_state(WallState::Locked);
+        let de: Wall = serde_yaml::from_str(&serde_yaml::to_string(&door).unwrap()).unwrap();
+        assert_eq!(de, door);
+
+        let de: Wall = serde_yaml::from_str("start: { x: 0, y: 0 }\nend: { x: 3, y: 0 }").unwrap();
+        assert_eq!((de.get_kind(), de.get_state()), (WallKind::Wall, WallState::Closed));
+    }
+}
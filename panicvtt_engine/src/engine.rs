@@ -1,6 +1,6 @@
-use std::path::Path;
+use std::{collections::BTreeSet, path::Path};
 
-use crate::{assets::{asset_manager::AssetManager, load_asset_result::LoadAssetResult}, campaigns::{campaign::Campaign, campaign_description::CampaignDescription}, entities::{abilities::AbilityScores, class::Class, entity::Entity, race::Race}, mechanics::dice::{Dice, Rng}};
+use crate::{assets::{asset::Asset, asset_manager::AssetManager, duplicate_policy::DuplicatePolicy, load_asset_result::LoadAssetResult}, campaigns::{area::{AreaOfEffect, SaveResult}, attack::AttackResult, campaign::Campaign, campaign_description::CampaignDescription, difficulty::{suggest_monster_sets, Difficulty, DifficultyReport, MonsterSet}, encounter::{Combatant, Encounter, EncounterPrompt, TurnEvent, TurnHook}, grid::{Grid, GridPosition, Token, TokenError, TokenErrorKind}, lighting::{Light, LightLevel}, scene::Scene, vision::Sight, walls::{Cover, Wall, WallState}}, entities::{abilities::{Ability, AbilityScores}, background::Background, biography::Biography, class::Class, entity::{Entity, EntityError}, feat::{Feat, FeatEffect, FeatPrerequisite}, improvement::AbilityScoreImprovement, modifier::{AppliedModifier, Modifier, ModifierKind, ModifierSource, Stat, StatBreakdown, StatIntType}, monster::{ChallengeRating, CreatureType, HitPointMode, Monster}, passives::PassiveSummary, proficiencies::{Proficiencies, WeaponCategory}, purse::{Coin, Purse}, race::Race, senses::Sense, size::Size, skills::{Skill, SkillDefinition}}, mechanics::dice::{Dice, Rng}, util::{dangling_reference::DanglingReference, duplicate_asset_error::DuplicateAssetError}};
 
 pub struct Engine {   
     asset_manager: AssetManager,
@@ -20,8 +20,13 @@ macro_rules! get_from_asset_manager {
 
 impl Engine {
     pub fn new(rng: Rng, asset_root: &Path) -> Self {
+        Self::with_duplicate_policy(rng, asset_root, DuplicatePolicy::default())
+    }
+
+    /// Start an engine that handles Asset files sharing a UUID with `duplicate_policy`, rather than failing to load. 
+    pub fn with_duplicate_policy(rng: Rng, asset_root: &Path, duplicate_policy: DuplicatePolicy) -> Self {
         Self {
-            asset_manager: AssetManager::new(asset_root).unwrap(),  // For now, panic if something goes wrong
+            asset_manager: AssetManager::with_duplicate_policy(asset_root, duplicate_policy).unwrap(),  // For now, panic if something goes wrong
             rng
         }
     }
@@ -31,10 +36,29 @@ impl Engine {
         self.asset_manager.get_campaign_descriptions()
     }
 
+    get_from_asset_manager!(background, Background);
     get_from_asset_manager!(campaign, Campaign);
     get_from_asset_manager!(class, Class);
     get_from_asset_manager!(race, Race);
     get_from_asset_manager!(entity, Entity);
+    get_from_asset_manager!(feat, Feat);
+    get_from_asset_manager!(monster, Monster);
+
+    pub fn get_skill(&self, skill: Skill) -> Option<&SkillDefinition> {
+        self.asset_manager.get_skill(skill)
+    }
+
+    pub fn get_skills(&self) -> Vec<&SkillDefinition> {
+        self.asset_manager.get_skills()
+    }
+
+    pub fn get_classes(&self) -> Vec<&Class> {
+        self.asset_manager.get_classes()
+    }
+
+    pub fn get_races(&self) -> Vec<&Race> {
+        self.asset_manager.get_races()
+    }
 
 
 
@@ -43,10 +67,33 @@ impl Engine {
         self.asset_manager.create_campaign(campaign_name, campaign_description).unwrap()
     }
 
-    pub fn new_entity(&mut self, builder: EntityBuilder) -> &Entity {
+    /// Create an Entity, applying the grants of the background the builder names, if any. 
+    /// 
+    /// Returns None if the builder names a class, race or background that cannot be found. 
+    pub fn new_entity(&mut self, builder: EntityBuilder) -> Option<&Entity> {
         // Construct the entity
-        let (name, class, race, abilities) = builder.build();
-        self.asset_manager.create_entity(name, class, race, abilities, &mut self.rng).unwrap()
+        let (name, class, race, abilities, background) = builder.build();
+        self.asset_manager.get_class(class)?;
+        self.asset_manager.get_race(race)?;
+        if let Some(background) = background {
+            self.asset_manager.get_background(background)?;
+        }
+        let entity_id = self.asset_manager.create_entity(name, class, race, abilities, &mut self.rng).unwrap().get_uuid();
+
+        if let Some(background) = background {
+            self.asset_manager.update_entity(entity_id, |e, assets| {
+                if let Some(background) = assets.get_background(background) {
+                    e.apply_background(background);
+                }
+            });
+        }
+
+        self.asset_manager.get_entity(entity_id)
+    }
+
+    pub fn new_background(&mut self, background_name: String) -> &Background {
+        // Create a new background through the asset manager - if this fails a Uuid invariant is violated and we cannot continue
+        self.asset_manager.create_background(background_name).unwrap()
     }
 
     pub fn new_class(&mut self, class_name: String, hit_die: Dice) -> &Class {
@@ -54,15 +101,675 @@ impl Engine {
         self.asset_manager.create_class(class_name, hit_die).unwrap()
     }
 
+    pub fn new_feat(&mut self, feat_name: String, prerequisites: Vec<FeatPrerequisite>, effects: Vec<FeatEffect>) -> &Feat {
+        // Create a new feat through the asset manager - if this fails a Uuid invariant is violated and we cannot continue
+        self.asset_manager.create_feat(feat_name, prerequisites, effects).unwrap()
+    }
+
+    pub fn new_monster(&mut self, monster_name: String, creature_type: CreatureType, challenge_rating: ChallengeRating) -> &Monster {
+        // Create a new monster through the asset manager - if this fails a Uuid invariant is violated and we cannot continue
+        self.asset_manager.create_monster(monster_name, creature_type, challenge_rating).unwrap()
+    }
+
+    /// Place an instance of a monster's stat block into a Campaign's Scene, returning the new Entity's UUID. The 
+    /// Entity belongs to that Campaign, and the stat block is looked up from it, overrides included. 
+    /// 
+    /// Returns None if the monster, Campaign or Scene cannot be found. 
+    pub fn instantiate_monster(&mut self, monster_id: u128, campaign_id: u128, scene_id: u128, hp_mode: HitPointMode) -> Option<u128> {
+        self.asset_manager.get_campaign(campaign_id)?.get_scene(scene_id)?;
+        let entity_id = self.asset_manager.create_monster_entity(monster_id, Some(campaign_id), hp_mode, &mut self.rng)?.unwrap().get_uuid();
+
+        self.asset_manager.get_campaign_mut(campaign_id)?.get_scene_mut(scene_id)?.add_entity(entity_id);
+        Some(entity_id)
+    }
+
+    pub fn new_skill(&mut self, skill_name: String, ability: Ability) -> &SkillDefinition {
+        // Create a new skill through the asset manager - if this fails a Uuid invariant is violated and we cannot continue
+        self.asset_manager.create_skill(skill_name, ability).unwrap()
+    }
+
     pub fn new_race(&mut self, race_name: String, speed: u8) -> &Race {
         // Create a new race through the asset manager - if this fails a Uuid invariant is violated and we cannot continue
         self.asset_manager.create_race(race_name, speed).unwrap()
     }
 
+    /// Level up an Entity, returning the hit die roll. Returns None if no Entity has this UUID. 
+    pub fn level_up_entity(&mut self, entity_id: u128) -> Option<Result<u16, EntityError>> {
+        let rng = &mut self.rng;
+        self.asset_manager.update_entity(entity_id, |e, assets| e.level_up(assets, rng))
+    }
+
+    /// Spend one of an Entity's pending Ability Score Improvements. Returns None if no Entity has this UUID. 
+    pub fn apply_improvement(&mut self, entity_id: u128, improvement: AbilityScoreImprovement) -> Option<Result<(), EntityError>> {
+        self.asset_manager.update_entity(entity_id, |e, assets| e.apply_improvement(improvement, assets))
+    }
+
+    /// Undo all of an Entity's Ability Score Improvements so they can be chosen again. Returns None if no Entity has this UUID. 
+    pub fn respec_improvements(&mut self, entity_id: u128) -> Option<u8> {
+        self.asset_manager.update_entity(entity_id, |e, _| e.respec_improvements())
+    }
+
+    /// Explain how an Entity's Stat is computed. Returns None if the Entity, or its race or class, cannot be found. 
+    pub fn explain_entity_stat(&self, entity_id: u128, stat: Stat) -> Option<StatBreakdown> {
+        self.asset_manager.get_entity(entity_id)?.explain_stat(stat, &self.asset_manager)
+    }
+
+    /// Attach a modifier from an item, condition or spell to an Entity. Returns false if no Entity has this UUID. 
+    pub fn add_entity_modifier(&mut self, entity_id: u128, source: ModifierSource, modifier: Modifier) -> bool {
+        self.asset_manager.update_entity(entity_id, |e, _| e.add_modifier(source, modifier)).is_some()
+    }
+
+    /// Change an Entity's size, returning the old one. Returns None if no Entity has this UUID. 
+    pub fn set_entity_size(&mut self, entity_id: u128, size: Size) -> Option<Size> {
+        self.asset_manager.update_entity(entity_id, |e, _| e.set_size(size))
+    }
+
+    /// Edit an Entity's alignment, personality and backstory. Returns false if no Entity has this UUID. 
+    pub fn edit_entity_biography<F>(&mut self, entity_id: u128, f: F) -> bool where F : FnOnce(&mut Biography) {
+        self.asset_manager.update_entity(entity_id, |e, _| f(e.get_biography_mut())).is_some()
+    }
+
+    /// Remove every modifier an Entity received from `source`. Returns None if no Entity has this UUID. 
+    pub fn remove_entity_modifiers(&mut self, entity_id: u128, source: &ModifierSource) -> Option<usize> {
+        self.asset_manager.update_entity(entity_id, |e, _| e.remove_modifiers_from(source))
+    }
+
+    /// Every proficiency an Entity has from itself, its race and its class. Returns None if any of them cannot be found. 
+    pub fn get_entity_proficiencies(&self, entity_id: u128) -> Option<Proficiencies> {
+        self.asset_manager.get_entity(entity_id)?.get_proficiencies(&self.asset_manager)
+    }
+
+    /// Add an Entity to a Campaign's party. Returns false if the Campaign or Entity is unknown, or the Entity is already a member. 
+    pub fn add_party_member(&mut self, campaign_id: u128, entity_id: u128) -> bool {
+        if self.asset_manager.get_entity(entity_id).is_none() {
+            return false;
+        }
+
+        self.asset_manager.get_campaign_mut(campaign_id).is_some_and(|c| c.add_party_member(entity_id))
+    }
+
+    /// Remove an Entity from a Campaign's party. Returns false if the Campaign is unknown or the Entity was not a member. 
+    pub fn remove_party_member(&mut self, campaign_id: u128, entity_id: u128) -> bool {
+        self.asset_manager.get_campaign_mut(campaign_id).is_some_and(|c| c.remove_party_member(entity_id))
+    }
+
+    /// Passive scores, initiative bonus and senses for every party member of a Campaign. 
+    /// 
+    /// Party members whose Entity, race or class cannot be found are skipped. Returns None if the Campaign is unknown. 
+    pub fn get_party_passives(&self, campaign_id: u128) -> Option<Vec<PassiveSummary>> {
+        let campaign = self.asset_manager.get_campaign(campaign_id)?;
+        Some(campaign.get_party().into_iter()
+            .filter_map(|id| self.asset_manager.get_entity(id))
+            .filter_map(|e| PassiveSummary::from_entity(e, &self.asset_manager))
+            .collect())
+    }
+
+    /// Split loot evenly between the party members of a Campaign, making change where coins don't divide evenly. 
+    /// 
+    /// Party members whose Entity cannot be found get nothing. Returns what couldn't be divided, or None if the Campaign is unknown. 
+    pub fn split_party_loot(&mut self, campaign_id: u128, loot: &Purse) -> Option<Purse> {
+        let members: Vec<u128> = self.asset_manager.get_campaign(campaign_id)?.get_party().into_iter()
+            .filter(|id| self.asset_manager.get_entity(*id).is_some())
+            .collect();
+
+        let (share, remainder) = loot.split(members.len() as u32);
+        for id in members {
+            self.asset_manager.update_entity(id, |e, _| e.get_purse_mut().add_purse(&share));
+        }
+
+        Some(remainder)
+    }
+
+    /// Add coins to an Entity's purse. Returns false if no Entity has this UUID. 
+    pub fn add_entity_coins(&mut self, entity_id: u128, coin: Coin, amount: u32) -> bool {
+        self.asset_manager.update_entity(entity_id, |e, _| e.get_purse_mut().add(coin, amount)).is_some()
+    }
+
+    /// Pay a price from an Entity's purse, making change as needed. Returns None if no Entity has this UUID, 
+    /// or Some(false) if it can't afford the price. 
+    pub fn spend_entity_coins(&mut self, entity_id: u128, coin: Coin, amount: u32) -> Option<bool> {
+        self.asset_manager.update_entity(entity_id, |e, _| e.get_purse_mut().spend(coin, amount))
+    }
+
+    /// Exchange coins in an Entity's purse. Returns None if no Entity has this UUID, or Some(false) if the exchange isn't possible. 
+    pub fn exchange_entity_coins(&mut self, entity_id: u128, from: Coin, to: Coin, amount: u32) -> Option<bool> {
+        self.asset_manager.update_entity(entity_id, |e, _| e.get_purse_mut().exchange(from, to, amount))
+    }
     
+    pub fn get_scene(&self, campaign_id: u128, scene_id: u128) -> Option<&Scene> {
+        self.asset_manager.get_campaign(campaign_id)?.get_scene(scene_id)
+    }
+
+    fn get_scene_mut(&mut self, campaign_id: u128, scene_id: u128) -> Option<&mut Scene> {
+        self.asset_manager.get_campaign_mut(campaign_id)?.get_scene_mut(scene_id)
+    }
+
+    /// A Campaign's Scenes in list order. Returns None if the Campaign is unknown. 
+    pub fn get_scenes(&self, campaign_id: u128) -> Option<Vec<&Scene>> {
+        Some(self.asset_manager.get_campaign(campaign_id)?.get_scenes())
+    }
+
+    /// Add an empty Scene to the end of a Campaign's list. Returns None if the Campaign is unknown. 
+    pub fn create_scene(&mut self, campaign_id: u128, scene_name: String) -> Option<&Scene> {
+        self.asset_manager.get_campaign_mut(campaign_id)?.create_scene(scene_name)
+    }
+
+    pub fn rename_scene(&mut self, campaign_id: u128, scene_id: u128, scene_name: String) -> bool {
+        self.get_scene_mut(campaign_id, scene_id).map(|s| s.set_name(scene_name)).is_some()
+    }
+
+    /// Copy a Scene's map, walls, lights and tokens into a new Scene listed after it, without its combat or explored cells. 
+    pub fn duplicate_scene(&mut self, campaign_id: u128, scene_id: u128, scene_name: String) -> Option<&Scene> {
+        self.asset_manager.get_campaign_mut(campaign_id)?.duplicate_scene(scene_id, scene_name)
+    }
+
+    /// Delete a Scene, returning it. The Campaign is left with no active Scene if it was the active one. 
+    pub fn delete_scene(&mut self, campaign_id: u128, scene_id: u128) -> Option<Scene> {
+        self.asset_manager.get_campaign_mut(campaign_id)?.remove_scene(scene_id)
+    }
+
+    /// Move a Scene to `index` in its Campaign's list. Returns false if the Campaign or Scene is unknown. 
+    pub fn move_scene(&mut self, campaign_id: u128, scene_id: u128, index: usize) -> bool {
+        self.asset_manager.get_campaign_mut(campaign_id).is_some_and(|c| c.move_scene(scene_id, index))
+    }
+
+    /// Switch the table to a Scene, or to none. Returns false if the Campaign or Scene is unknown. 
+    pub fn set_active_scene(&mut self, campaign_id: u128, scene_id: Option<u128>) -> bool {
+        self.asset_manager.get_campaign_mut(campaign_id).is_some_and(|c| c.set_active_scene(scene_id))
+    }
+
+    /// Move an Entity to another Scene, dropping its token and any place in combat in the first. 
+    /// Returns false if the Campaign or either Scene is unknown, or the Entity isn't in the first Scene. 
+    pub fn move_entity_to_scene(&mut self, campaign_id: u128, entity_id: u128, from: u128, to: u128) -> bool {
+        self.asset_manager.get_campaign_mut(campaign_id).is_some_and(|c| c.move_entity(entity_id, from, to))
+    }
+
+    /// Replace a Scene's map. Tokens that no longer fit are taken off it. Returns false if the Campaign or Scene is unknown. 
+    pub fn set_scene_grid(&mut self, campaign_id: u128, scene_id: u128, grid: Grid) -> bool {
+        self.get_scene_mut(campaign_id, scene_id).map(|s| s.set_grid(grid)).is_some()
+    }
+
+    /// Put an Entity's token on a Scene's map, sized to the Entity, and add the Entity to the Scene. 
+    /// The players controlling the Entity explore what it can see from there. 
+    /// 
+    /// Returns None if the Campaign, Scene or Entity is unknown. 
+    pub fn place_token(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128, position: GridPosition) -> Option<Result<(), TokenError>> {
+        let footprint = self.asset_manager.get_entity(entity_id)?.get_size().get_footprint();
+        let result = self.get_scene_mut(campaign_id, scene_id)?.place_token(Token::new(entity_id, position, footprint));
+        if result.is_ok() {
+            self.explore_for(campaign_id, scene_id, entity_id);
+        }
+        Some(result)
+    }
+
+    /// Move an Entity's token straight to another cell, without using any of its movement. 
+    /// The players controlling the Entity explore what it can see from there. 
+    /// Returns None if the Campaign or Scene is unknown. 
+    pub fn move_token(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128, position: GridPosition) -> Option<Result<(), TokenError>> {
+        let result = self.get_scene_mut(campaign_id, scene_id)?.move_token(entity_id, position);
+        if result.is_ok() {
+            self.explore_for(campaign_id, scene_id, entity_id);
+        }
+        Some(result)
+    }
+
+    /// Move an Entity's token cell by cell along `path`, measured with the Scene's diagonal rule and difficult terrain. 
+    /// Walls, closed doors and closed windows block the way, as do other tokens, though party members may pass 
+    /// through each other's spaces. 
+    /// Once combat in the Scene has started, a combatant may only walk on its own turn, and pays for the move from its movement. 
+    /// The players controlling the Entity explore what it can see at the end of the walk. 
+    /// 
+    /// Returns the cost in feet, or None if the Campaign, Scene or Entity is unknown. 
+    pub fn walk_token(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128, path: &[GridPosition]) -> Option<Result<u32, TokenError>> {
+        let speed = self.asset_manager.get_entity(entity_id)?.get_speed(&self.asset_manager).unwrap_or(0);
+        let party = self.asset_manager.get_campaign(campaign_id)?.get_party();
+        let scene = self.get_scene_mut(campaign_id, scene_id)?;
+        let Some(token) = scene.get_token(entity_id) else {
+            return Some(Err(TokenError::from_kind(TokenErrorKind::NoToken { entity: entity_id })));
+        };
+
+        let encounter = scene.get_encounter().filter(|e| e.get_round() > 0);
+        let combatant = encounter.and_then(|e| e.get_combatant(entity_id));
+        if combatant.is_some() && encounter.and_then(|e| e.get_current()) != combatant {
+            return Some(Err(TokenError::from_kind(TokenErrorKind::OutOfTurn { entity: entity_id })));
+        }
+        let diagonals = combatant.map_or(0, |c| c.get_diagonals());
+        let Some((cost, diagonals)) = scene.get_grid().get_path_cost(token.get_position(), path, token.get_footprint(), diagonals) else {
+            return Some(Err(TokenError::from_kind(TokenErrorKind::InvalidPath)));
+        };
+        let mut from = token.get_position();
+        for to in path {
+            if scene.is_step_blocked(from, *to, token.get_footprint()) {
+                return Some(Err(TokenError::from_kind(TokenErrorKind::Blocked { position: *to })));
+            }
+            let in_the_way = scene.get_tokens().iter().find(|t| t.get_entity() != entity_id 
+                && t.overlaps(*to, token.get_footprint(), token.get_elevation())
+                && !(party.contains(&entity_id) && party.contains(&t.get_entity())));
+            if let Some(other) = in_the_way {
+                return Some(Err(TokenError::from_kind(TokenErrorKind::Occupied { position: *to, entity: other.get_entity() })));
+            }
+            from = *to;
+        }
+
+        let remaining = combatant.map(|c| c.get_remaining_movement(speed));
+        if let Some(remaining) = remaining.filter(|r| cost > *r) {
+            return Some(Err(TokenError::from_kind(TokenErrorKind::OverBudget { cost, remaining })));
+        }
+
+        if let Some(destination) = path.last() {
+            if let Err(e) = scene.move_token(entity_id, *destination) {
+                return Some(Err(e));
+            }
+        }
+        if remaining.is_some() {
+            scene.get_encounter_mut().unwrap().spend_movement(entity_id, cost, diagonals, speed);
+        }
+        self.explore_for(campaign_id, scene_id, entity_id);
+        Some(Ok(cost))
+    }
+
+    /// Feet of movement a combatant has left this turn. Returns None if it isn't in combat in this Scene. 
+    pub fn get_remaining_movement(&self, campaign_id: u128, scene_id: u128, entity_id: u128) -> Option<u32> {
+        let speed = self.asset_manager.get_entity(entity_id)?.get_speed(&self.asset_manager).unwrap_or(0);
+        Some(self.get_encounter(campaign_id, scene_id)?.get_combatant(entity_id)?.get_remaining_movement(speed))
+    }
+
+    /// The current combatant takes the Dash action. Returns false if it isn't that Entity's turn. 
+    pub fn dash(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128) -> bool {
+        self.get_encounter_mut(campaign_id, scene_id).is_some_and(|e| e.dash(entity_id))
+    }
+
+    /// The current combatant takes the Disengage action. Returns false if it isn't that Entity's turn. 
+    pub fn disengage(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128) -> bool {
+        self.get_encounter_mut(campaign_id, scene_id).is_some_and(|e| e.disengage(entity_id))
+    }
+
+    /// The Entities whose tokens an area of effect covers in a Scene. Returns None if the Campaign or Scene is unknown. 
+    pub fn get_area_targets(&self, campaign_id: u128, scene_id: u128, area: &AreaOfEffect) -> Option<Vec<u128>> {
+        let scene = self.get_scene(campaign_id, scene_id)?;
+        Some(area.get_tokens(scene.get_grid(), scene.get_tokens()))
+    }
+
+    /// Every target makes a saving throw against `dc`, taking `damage` on a failure and half as much, rounded down, on a success. 
+    /// Saves roll with advantage or disadvantage from the target's modifiers. 
+    /// 
+    /// Returns None, changing nothing, if any target cannot be found. 
+    pub fn resolve_save_for_half(&mut self, targets: &[u128], ability: Ability, dc: StatIntType, damage: u16) -> Option<Vec<SaveResult>> {
+        let saves = targets.iter()
+            .map(|id| self.asset_manager.get_entity(*id)?.explain_stat(Stat::Save(ability), &self.asset_manager))
+            .collect::<Option<Vec<StatBreakdown>>>()?;
+
+        Some(targets.iter().zip(saves).map(|(id, save)| {
+            let roll = self.roll_d20(&save);
+            let total = roll as StatIntType + save.get_total();
+            let success = total >= dc;
+            let damage = if success { damage / 2 } else { damage };
+            let lost = self.asset_manager.update_entity(*id, |e, _| e.take_damage(damage)).unwrap();
+            SaveResult::new(*id, roll, total, success, lost)
+        }).collect())
+    }
+
+    /// Roll a d20 for a Stat, rolling twice and keeping the higher with advantage or the lower with disadvantage. 
+    fn roll_d20(&mut self, stat: &StatBreakdown) -> u8 {
+        let roll = Dice::D20.roll(&mut self.rng);
+        if stat.has_advantage() {
+            roll.max(Dice::D20.roll(&mut self.rng))
+        } else if stat.has_disadvantage() {
+            roll.min(Dice::D20.roll(&mut self.rng))
+        } else {
+            roll
+        }
+    }
+
+    /// Add a wall to a Scene's map, returning its index. Returns None if the Campaign or Scene is unknown. 
+    pub fn add_wall(&mut self, campaign_id: u128, scene_id: u128, wall: Wall) -> Option<usize> {
+        Some(self.get_scene_mut(campaign_id, scene_id)?.add_wall(wall))
+    }
+
+    pub fn remove_wall(&mut self, campaign_id: u128, scene_id: u128, index: usize) -> Option<Wall> {
+        self.get_scene_mut(campaign_id, scene_id)?.remove_wall(index)
+    }
+
+    /// Open, close, lock or unlock a wall. Returns false if there is no such wall. 
+    pub fn set_wall_state(&mut self, campaign_id: u128, scene_id: u128, index: usize, state: WallState) -> bool {
+        self.get_scene_mut(campaign_id, scene_id).and_then(|s| s.get_wall_mut(index)).map(|w| w.set_state(state)).is_some()
+    }
+
+    /// Open a door or window the way a player would. Returns false if there is no such wall, or it is locked or can't be opened. 
+    pub fn open_wall(&mut self, campaign_id: u128, scene_id: u128, index: usize) -> bool {
+        self.get_scene_mut(campaign_id, scene_id).and_then(|s| s.get_wall_mut(index)).is_some_and(|w| w.open())
+    }
+
+    /// The cover walls give one Entity's token against another's. Returns None if the Scene or either token is missing. 
+    pub fn get_cover(&self, campaign_id: u128, scene_id: u128, from: u128, to: u128) -> Option<Cover> {
+        self.get_scene(campaign_id, scene_id)?.get_cover(from, to)
+    }
+
+    /// Whether one Entity's token can see any part of another's past the walls. Light and senses aren't considered. 
+    pub fn has_line_of_sight(&self, campaign_id: u128, scene_id: u128, from: u128, to: u128) -> Option<bool> {
+        Some(self.get_cover(campaign_id, scene_id, from, to)? != Cover::Total)
+    }
+
+    /// Give a player control of an Entity in a Campaign. Returns false if the Campaign is unknown or they already control it. 
+    pub fn assign_entity(&mut self, campaign_id: u128, player: &str, entity_id: u128) -> bool {
+        self.asset_manager.get_campaign_mut(campaign_id).is_some_and(|c| c.assign_entity(player, entity_id))
+    }
+
+    /// How far an Entity sees without light: darkvision, and the better of blindsight and truesight. 
+    fn get_sight(&self, entity_id: u128) -> Sight {
+        let range = |sense| self.asset_manager.get_entity(entity_id)
+            .and_then(|e| e.get_sense_range(sense, &self.asset_manager))
+            .unwrap_or(0);
+        Sight::new(range(Sense::Darkvision), range(Sense::Blindsight).max(range(Sense::Truesight)))
+    }
+
+    /// The Entities a player controls in a Campaign, with their sight. 
+    fn get_viewers(&self, campaign_id: u128, player: &str) -> Option<Vec<(u128, Sight)>> {
+        let entities = self.asset_manager.get_campaign(campaign_id)?.get_player_entities(player);
+        Some(entities.into_iter().map(|e| (e, self.get_sight(e))).collect())
+    }
+
+    /// Set how bright a Scene is without any light sources. Returns false if the Campaign or Scene is unknown. 
+    pub fn set_ambient_light(&mut self, campaign_id: u128, scene_id: u128, level: LightLevel) -> bool {
+        self.get_scene_mut(campaign_id, scene_id).map(|s| s.set_ambient_light(level)).is_some()
+    }
+
+    /// Add a light source to a Scene, returning its index. Returns None if the Campaign or Scene is unknown. 
+    pub fn add_light(&mut self, campaign_id: u128, scene_id: u128, light: Light) -> Option<usize> {
+        Some(self.get_scene_mut(campaign_id, scene_id)?.add_light(light))
+    }
+
+    pub fn remove_light(&mut self, campaign_id: u128, scene_id: u128, index: usize) -> Option<Light> {
+        self.get_scene_mut(campaign_id, scene_id)?.remove_light(index)
+    }
+
+    pub fn get_light_level(&self, campaign_id: u128, scene_id: u128, cell: GridPosition) -> Option<LightLevel> {
+        Some(self.get_scene(campaign_id, scene_id)?.get_light_level(cell))
+    }
+
+    /// How light a cell looks to an Entity, after its darkvision, blindsight and truesight. Dark if walls block its view. 
+    /// Returns None if the Scene or the Entity's token is missing. 
+    pub fn get_perceived_light(&self, campaign_id: u128, scene_id: u128, entity_id: u128, cell: GridPosition) -> Option<LightLevel> {
+        self.get_scene(campaign_id, scene_id)?.perceive_cell(entity_id, &self.get_sight(entity_id), cell)
+    }
+
+    /// Whether an Entity has disadvantage on Perception checks that rely on sight at a cell, because it only sees dim light there. 
+    /// Returns None if the Scene or the Entity's token is missing. 
+    pub fn has_sight_disadvantage(&self, campaign_id: u128, scene_id: u128, entity_id: u128, cell: GridPosition) -> Option<bool> {
+        Some(self.get_perceived_light(campaign_id, scene_id, entity_id, cell)? == LightLevel::Dim)
+    }
+
+    /// An Entity's Perception for noticing something at a cell, with disadvantage if it only sees dim light there. 
+    /// Returns None if the Scene, the Entity or its token is missing. 
+    pub fn explain_perception_at(&self, campaign_id: u128, scene_id: u128, entity_id: u128, cell: GridPosition) -> Option<StatBreakdown> {
+        let dim = self.has_sight_disadvantage(campaign_id, scene_id, entity_id, cell)?;
+        let mut perception = self.asset_manager.get_entity(entity_id)?.explain_stat(Stat::Skill(Skill::PERCEPTION), &self.asset_manager)?;
+        if dim {
+            let source = ModifierSource::Condition { name: String::from("Dim Light") };
+            perception.apply(&AppliedModifier::new(source, Modifier::new(Stat::Skill(Skill::PERCEPTION), ModifierKind::Disadvantage)));
+        }
+        Some(perception)
+    }
+
+    /// An Entity's passive Perception for noticing something at a cell, 5 lower if it only sees dim light there. 
+    /// Returns None if the Scene, the Entity or its token is missing. 
+    pub fn get_passive_perception_at(&self, campaign_id: u128, scene_id: u128, entity_id: u128, cell: GridPosition) -> Option<StatIntType> {
+        Some(self.explain_perception_at(campaign_id, scene_id, entity_id, cell)?.get_passive())
+    }
+
+    /// Roll a Perception check to notice something at a cell, with disadvantage if the Entity only sees dim light there. 
+    /// Returns the d20 rolled and the total, or None if the Scene, the Entity or its token is missing. 
+    pub fn roll_perception_at(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128, cell: GridPosition) -> Option<(u8, StatIntType)> {
+        let perception = self.explain_perception_at(campaign_id, scene_id, entity_id, cell)?;
+        let roll = self.roll_d20(&perception);
+        Some((roll, roll as StatIntType + perception.get_total()))
+    }
+
+    /// Cells a player's tokens can currently see in a Scene, given its light and their senses. 
+    /// Returns None if the Campaign or Scene is unknown. 
+    pub fn get_visible_cells(&self, campaign_id: u128, scene_id: u128, player: &str) -> Option<BTreeSet<GridPosition>> {
+        let viewers = self.get_viewers(campaign_id, player)?;
+        Some(self.get_scene(campaign_id, scene_id)?.get_visible_cells(&viewers))
+    }
+
+    /// The tokens a player should be shown in a Scene: their own, and others their tokens can see. 
+    /// Returns None if the Campaign or Scene is unknown. 
+    pub fn get_visible_tokens(&self, campaign_id: u128, scene_id: u128, player: &str) -> Option<Vec<&Token>> {
+        let viewers = self.get_viewers(campaign_id, player)?;
+        Some(self.get_scene(campaign_id, scene_id)?.get_visible_tokens(&viewers))
+    }
+
+    /// Mark the cells a player's tokens can currently see as explored. Returns how many cells were new, or None if the 
+    /// Campaign or Scene is unknown. 
+    pub fn explore(&mut self, campaign_id: u128, scene_id: u128, player: &str) -> Option<usize> {
+        let viewers = self.get_viewers(campaign_id, player)?;
+        Some(self.get_scene_mut(campaign_id, scene_id)?.explore(player, &viewers))
+    }
+
+    /// Mark what every player controlling an Entity can now see in a Scene as explored. 
+    fn explore_for(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128) {
+        let Some(campaign) = self.asset_manager.get_campaign(campaign_id) else {
+            return;
+        };
+        let players: Vec<String> = campaign.get_players().into_iter()
+            .filter(|p| campaign.get_player_entities(p).contains(&entity_id))
+            .map(String::from)
+            .collect();
+
+        for player in players {
+            self.explore(campaign_id, scene_id, &player);
+        }
+    }
+
+    /// Cells a player has explored in a Scene. Returns None if they haven't explored it, or the Campaign or Scene is unknown. 
+    pub fn get_explored_cells(&self, campaign_id: u128, scene_id: u128, player: &str) -> Option<&BTreeSet<GridPosition>> {
+        self.get_scene(campaign_id, scene_id)?.get_explored_cells(player)
+    }
+
+    /// Roll an attack with a weapon against another Entity's token, adding cover to the target's armor class. 
+    /// No roll is made against a target in total cover. 
+    /// 
+    /// Returns None if the Scene, either Entity or either token is missing. 
+    pub fn resolve_attack(&mut self, campaign_id: u128, scene_id: u128, attacker: u128, target: u128, weapon: WeaponCategory, ability: Ability) -> Option<AttackResult> {
+        let cover = self.get_cover(campaign_id, scene_id, attacker, target)?;
+        let bonus = self.asset_manager.get_entity(attacker)?.get_attack_bonus(weapon, ability, &self.asset_manager)?;
+        let armor_class = self.asset_manager.get_entity(target)?.get_armor_class(&self.asset_manager)? + cover.get_bonus();
+
+        if cover == Cover::Total {
+            return Some(AttackResult::new(target, cover, None, 0, armor_class));
+        }
+
+        let roll = Dice::D20.roll(&mut self.rng);
+        Some(AttackResult::new(target, cover, Some(roll), roll as StatIntType + bonus, armor_class))
+    }
+
+    /// Take an Entity's token off a Scene's map. Returns false if it had none. 
+    pub fn remove_token(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128) -> bool {
+        self.get_scene_mut(campaign_id, scene_id).is_some_and(|s| s.remove_token(entity_id).is_some())
+    }
+
+    /// Change a token's rotation, visibility or elevation. Returns false if the Entity has no token in this Scene. 
+    pub fn edit_token<F>(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128, f: F) -> bool where F : FnOnce(&mut Token) {
+        self.get_scene_mut(campaign_id, scene_id).and_then(|s| s.get_token_mut(entity_id)).map(f).is_some()
+    }
+
+    /// Rate a fight between party Entities and monster stat blocks, which may repeat. Returns None if any of them cannot be found. 
+    pub fn get_encounter_difficulty(&self, party: &[u128], monsters: &[u128]) -> Option<DifficultyReport> {
+        let levels = party.iter().map(|id| Some(self.asset_manager.get_entity(*id)?.get_level())).collect::<Option<Vec<u8>>>()?;
+        let xp = monsters.iter().map(|id| Some(self.asset_manager.get_monster(*id)?.get_xp())).collect::<Option<Vec<u32>>>()?;
+
+        Some(DifficultyReport::new(&levels, &xp))
+    }
+
+    /// Suggest groups of up to `max_monsters` loaded monsters that rate as `difficulty` for party Entities, hardest first. 
+    /// Returns None if a party member cannot be found. 
+    pub fn suggest_encounters(&self, party: &[u128], difficulty: Difficulty, max_monsters: u8) -> Option<Vec<MonsterSet>> {
+        let levels = party.iter().map(|id| Some(self.asset_manager.get_entity(*id)?.get_level())).collect::<Option<Vec<u8>>>()?;
+        let monsters: Vec<(u128, u32)> = self.asset_manager.get_monsters().iter().map(|m| (m.get_uuid(), m.get_xp())).collect();
+
+        Some(suggest_monster_sets(&levels, difficulty, &monsters, max_monsters))
+    }
+
+    pub fn get_encounter(&self, campaign_id: u128, scene_id: u128) -> Option<&Encounter> {
+        self.asset_manager.get_campaign(campaign_id)?.get_scene(scene_id)?.get_encounter()
+    }
+
+    fn get_encounter_mut(&mut self, campaign_id: u128, scene_id: u128) -> Option<&mut Encounter> {
+        self.get_scene_mut(campaign_id, scene_id)?.get_encounter_mut()
+    }
+
+    /// Build a Combatant for an Entity, rolling d20 + initiative bonus unless `initiative` is given. 
+    /// Monsters take their legendary action pools and lair actions from their stat blocks. 
+    fn create_combatant(&mut self, entity_id: u128, initiative: Option<i16>) -> Option<Combatant> {
+        let entity = self.asset_manager.get_entity(entity_id)?;
+        let bonus = entity.get_initiative_bonus(&self.asset_manager)
+            .unwrap_or(entity.get_ability_modifier(Ability::Dexterity) as StatIntType);
+        let initiative = initiative.unwrap_or_else(|| Dice::D20.roll(&mut self.rng) as i16 + bonus);
+
+        let mut combatant = Combatant::new(entity_id, initiative)
+            .with_tiebreakers(bonus, entity.get_ability_score(Ability::Dexterity));
+        if let Some(monster) = entity.get_monster(&self.asset_manager) {
+            combatant = combatant.with_legendary_actions(monster.get_legendary_action_count())
+                .with_lair_actions(!monster.get_lair_actions().is_empty());
+        }
+        Some(combatant)
+    }
+
+    /// Apply the regeneration and condition expiry raised by an Encounter's turn hooks. 
+    fn apply_turn_events(&mut self, campaign_id: u128, scene_id: u128) {
+        let Some(encounter) = self.get_encounter_mut(campaign_id, scene_id) else { return; };
+        for event in encounter.take_events() {
+            match event {
+                TurnEvent::Regenerate { entity, amount } => {
+                    // Regeneration doesn't bring back the dead 
+                    self.asset_manager.update_entity(entity.as_u128(), |e, _| if e.get_hp() > 0 { e.heal(amount); });
+                },
+                TurnEvent::ConditionExpired { entity, source } => {
+                    self.asset_manager.update_entity(entity.as_u128(), |e, _| e.remove_modifiers_from(&source));
+                },
+            }
+        }
+    }
+
+    /// Start combat in a Campaign's Scene, replacing any combat already running there. Each Entity rolls initiative 
+    /// unless one is given, and Entities that cannot be found are skipped. 
+    /// 
+    /// Returns the lair actions available before the first turn, or None if the Campaign or Scene is unknown. 
+    pub fn start_encounter(&mut self, campaign_id: u128, scene_id: u128, combatants: &[(u128, Option<i16>)]) -> Option<Vec<EncounterPrompt>> {
+        self.asset_manager.get_campaign(campaign_id)?.get_scene(scene_id)?;
+
+        let mut encounter = Encounter::new();
+        for (entity_id, initiative) in combatants {
+            if let Some(combatant) = self.create_combatant(*entity_id, *initiative) {
+                encounter.add_combatant(combatant);
+            }
+        }
+
+        let prompts = encounter.start().to_vec();
+        self.asset_manager.get_campaign_mut(campaign_id)?.get_scene_mut(scene_id)?.set_encounter(Some(encounter));
+        self.apply_turn_events(campaign_id, scene_id);
+        Some(prompts)
+    }
+
+    /// End combat in a Campaign's Scene. Returns false if there was none. 
+    pub fn end_encounter(&mut self, campaign_id: u128, scene_id: u128) -> bool {
+        self.asset_manager.get_campaign_mut(campaign_id)
+            .and_then(|c| c.get_scene_mut(scene_id))
+            .and_then(|s| s.set_encounter(None))
+            .is_some()
+    }
+
+    /// Advance combat to the next turn, running turn hooks and returning the legendary and lair actions the GM 
+    /// should be prompted with. Returns None if there is no combat in this Scene. 
+    pub fn next_turn(&mut self, campaign_id: u128, scene_id: u128) -> Option<Vec<EncounterPrompt>> {
+        let prompts = self.get_encounter_mut(campaign_id, scene_id)?.next_turn().to_vec();
+        self.apply_turn_events(campaign_id, scene_id);
+        Some(prompts)
+    }
+
+    /// Bring an Entity into combat, rolling initiative unless one is given. 
+    /// 
+    /// Returns its initiative, or None if there is no combat in this Scene, the Entity is unknown or it is already fighting. 
+    pub fn add_combatant(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128, initiative: Option<i16>) -> Option<i16> {
+        self.get_encounter(campaign_id, scene_id)?;
+        let combatant = self.create_combatant(entity_id, initiative)?;
+        let initiative = combatant.get_initiative();
+
+        if !self.get_encounter_mut(campaign_id, scene_id)?.add_combatant(combatant) {
+            return None;
+        }
+        self.apply_turn_events(campaign_id, scene_id);
+        Some(initiative)
+    }
+
+    /// Take an Entity out of combat. Returns false if it wasn't fighting in this Scene. 
+    pub fn remove_combatant(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128) -> bool {
+        let removed = self.get_encounter_mut(campaign_id, scene_id).is_some_and(|e| e.remove_combatant(entity_id).is_some());
+        self.apply_turn_events(campaign_id, scene_id);
+        removed
+    }
+
+    /// Postpone the current combatant's turn to a lower initiative count. Returns false if that isn't possible. 
+    pub fn delay_combatant(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128, initiative: i16) -> bool {
+        let delayed = self.get_encounter_mut(campaign_id, scene_id).is_some_and(|e| e.delay(entity_id, initiative));
+        self.apply_turn_events(campaign_id, scene_id);
+        delayed
+    }
+
+    /// Attach a start or end of turn hook to a combatant. Returns false if it isn't fighting in this Scene. 
+    pub fn add_turn_hook(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128, hook: TurnHook) -> bool {
+        self.get_encounter_mut(campaign_id, scene_id).is_some_and(|e| e.add_hook(entity_id, hook))
+    }
+
+    /// Use one of a monster's legendary actions, paying its cost from the monster's pool. 
+    /// 
+    /// Returns None if there is no combat in this Scene or the Entity has no such legendary action, 
+    /// or Some(false) if it can't be used right now. 
+    pub fn spend_legendary_action(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128, action: &str) -> Option<bool> {
+        let cost = self.asset_manager.get_entity(entity_id)?.get_monster(&self.asset_manager)?.get_legendary_action(action)?.get_cost();
+        Some(self.get_encounter_mut(campaign_id, scene_id)?.spend_legendary_action(entity_id, cost))
+    }
+
+    /// Dismiss a lair action prompt once the GM has resolved it. Returns false if there was none. 
+    pub fn resolve_lair_action(&mut self, campaign_id: u128, scene_id: u128, entity_id: u128) -> bool {
+        self.get_encounter_mut(campaign_id, scene_id).is_some_and(|e| e.resolve_lair_action(entity_id))
+    }
+
+    /// Load a Campaign and its local Assets, and make it the active Campaign. 
     pub fn load_campaign(&mut self, campaign_id: u128) -> LoadAssetResult<'_, Campaign> {
         self.asset_manager.load_campaign(campaign_id)
     }
+
+    /// The Campaign whose local Assets are visible alongside the global ones. 
+    pub fn get_active_campaign(&self) -> Option<u128> {
+        self.asset_manager.get_active_campaign()
+    }
+
+    /// Switch to another loaded Campaign, or to global Assets only. Returns false if the Campaign isn't loaded. 
+    pub fn set_active_campaign(&mut self, campaign_id: Option<u128>) -> bool {
+        self.asset_manager.set_active_campaign(campaign_id)
+    }
+
+    /// Drop a loaded Campaign along with its local Assets, discarding unsaved changes to them. 
+    /// Returns false if the Campaign isn't loaded. 
+    pub fn unload_campaign(&mut self, campaign_id: u128) -> bool {
+        self.asset_manager.unload_campaign(campaign_id)
+    }
+
+    /// Write every Asset created or changed since the last save back to the asset root, returning how many were written. 
+    pub fn save(&mut self) -> Result<usize, std::io::Error> {
+        self.asset_manager.save()
+    }
+
+    /// References between loaded Assets that don't lead anywhere, such as an Entity whose class isn't loaded. 
+    pub fn validate_assets(&self) -> Vec<DanglingReference> {
+        self.asset_manager.validate()
+    }
+
+    /// Asset files that shared a UUID with another file and were skipped under the duplicate policy. 
+    pub fn get_duplicates(&self) -> &[DuplicateAssetError] {
+        self.asset_manager.get_duplicates()
+    }
     
     // pub fn delete_entity(&mut self, uuid: EntityID) -> Option<Entity> {
     //     self.entities.remove(&uuid) 
@@ -85,7 +792,8 @@ pub struct EntityBuilder {
     name: String, 
     class: Option<u128>,
     race: Option<u128>,
-    abilities: Option<AbilityScores>
+    abilities: Option<AbilityScores>,
+    background: Option<u128>,
 }
 
 impl EntityBuilder {
@@ -93,7 +801,8 @@ impl EntityBuilder {
         Self {
             name, 
             class: None, race: None, 
-            abilities: None
+            abilities: None,
+            background: None,
         }
     }
 
@@ -112,12 +821,18 @@ impl EntityBuilder {
         self
     }
 
-    fn build(self) -> (String, u128, u128, AbilityScores) {
+    pub fn with_background(mut self, background: u128) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    fn build(self) -> (String, u128, u128, AbilityScores, Option<u128>) {
         (
             self.name,
             self.class.unwrap_or(AssetManager::DEFAULT_CLASS_UUID),
             self.race.unwrap_or(AssetManager::DEFAULT_RACE_UUID),
             self.abilities.unwrap_or(AbilityScores::from_defaults()), 
+            self.background,
         )
     } 
 }
@@ -126,7 +841,7 @@ impl EntityBuilder {
 pub mod tests {
     use std::path::Path;
 
-    use crate::{assets::{asset::Asset, asset_manager::AssetManager}, engine::EntityBuilder, entities::abilities::AbilityScores, mechanics::dice::{Dice, Rng}};
+    use crate::{assets::{asset::Asset, asset_manager::AssetManager, load_asset_result::LoadAssetResult}, campaigns::{area::{AreaOfEffect, AreaShape}, difficulty::Difficulty, encounter::{EncounterPrompt, TurnHook, TurnTiming}, grid::{DiagonalRule, Grid, GridPosition, GridType, TokenErrorKind}, lighting::{Light, LightLevel, LightSource}, walls::{Cover, Wall, WallKind, WallState}}, engine::EntityBuilder, entities::{abilities::{Ability, AbilityScores}, biography::Alignment, improvement::AbilityScoreImprovement, modifier::{Modifier, ModifierKind, ModifierSource, Stat, StatIntType}, monster::{ChallengeRating, CreatureType, HitPointMode}, proficiencies::WeaponCategory, purse::{Coin, Purse}, senses::Sense, size::Size, skills::{Skill, SkillAttributes}}, mechanics::dice::{Dice, Rng}};
 
     use super::Engine;
 
@@ -148,7 +863,7 @@ pub mod tests {
 
         let entity_id: u128;
         {
-            let entity = engine.new_entity(EntityBuilder::new(String::from("Test Entity")));
+            let entity = engine.new_entity(EntityBuilder::new(String::from("Test Entity"))).unwrap();
             assert_eq!(entity.get_name(), String::from("Test Entity"));
             entity_id = entity.get_uuid();
         }
@@ -175,6 +890,16 @@ pub mod tests {
 
         let r = engine.asset_manager.get_race(race_id).unwrap();
         assert_eq!(r.get_uuid(), race_id);
+
+        let feat_id: u128;
+        {
+            let feat = engine.new_feat(String::from("Feat 2"), Vec::new(), Vec::new());
+            assert_eq!(feat.get_name(), String::from("Feat 2"));
+            feat_id = feat.get_uuid();
+        }
+
+        let f = engine.asset_manager.get_feat(feat_id).unwrap();
+        assert_eq!(f.get_uuid(), feat_id);
     }
 
     #[test]
@@ -197,7 +922,7 @@ pub mod tests {
             .with_class(c)
             .with_race(r)
             .with_abilities(a)
-        );
+        ).unwrap();
         
             assert_eq!(e.get_name(), "Builder Entity");
             entity_id = e.get_uuid();
@@ -208,4 +933,681 @@ pub mod tests {
         assert_eq!(e.get_race(&engine.asset_manager).unwrap().get_uuid(), race_id);
         assert_eq!(*e.get_ability_scores(), AbilityScores::new(1, 6, 11, 16, 21, 26));
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn level_up_and_improve() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let entity_id = engine.new_entity(EntityBuilder::new(String::from("Leveling Entity"))
+            .with_abilities(AbilityScores::new(13, 10, 10, 10, 10, 10))).unwrap().get_uuid();
+        assert!(engine.level_up_entity(55).is_none());
+
+        for _ in 1..8 {
+            engine.level_up_entity(entity_id).unwrap().unwrap();
+        }
+        assert_eq!(engine.get_entity(entity_id).unwrap().get_level(), 8);
+        assert_eq!(engine.get_entity(entity_id).unwrap().get_pending_improvements(), 2);
+
+        // Global Feat 1 requires 13 STR and grants +1 STR 
+        engine.apply_improvement(entity_id, AbilityScoreImprovement::Feat { feat: 0xfea7 }).unwrap().unwrap();
+        engine.apply_improvement(entity_id, AbilityScoreImprovement::PlusTwo { ability: Ability::Strength }).unwrap().unwrap();
+        assert_eq!(engine.get_entity(entity_id).unwrap().get_ability_score(Ability::Strength), 16);
+        assert!(engine.apply_improvement(entity_id, AbilityScoreImprovement::PlusTwo { ability: Ability::Strength }).unwrap().is_err());
+
+        assert_eq!(engine.respec_improvements(entity_id), Some(2));
+        assert_eq!(engine.get_entity(entity_id).unwrap().get_ability_score(Ability::Strength), 13);
+    }
+
+    #[test]
+    pub fn entity_modifiers() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let entity_id = engine.new_entity(EntityBuilder::new(String::from("Modified Entity"))).unwrap().get_uuid();
+        let source = ModifierSource::Condition { name: String::from("Slowed") };
+
+        assert!(!engine.add_entity_modifier(55, source.clone(), Modifier::new(Stat::ArmorClass, ModifierKind::Flat(-2))));
+        assert!(engine.add_entity_modifier(entity_id, source.clone(), Modifier::new(Stat::ArmorClass, ModifierKind::Flat(-2))));
+
+        let ac = engine.explain_entity_stat(entity_id, Stat::ArmorClass).unwrap();
+        assert_eq!(ac.get_base(), 10);
+        assert_eq!(ac.get_total(), 8);
+        assert!(engine.explain_entity_stat(55, Stat::ArmorClass).is_none());
+
+        assert_eq!(engine.remove_entity_modifiers(entity_id, &source), Some(1));
+        assert_eq!(engine.explain_entity_stat(entity_id, Stat::ArmorClass).unwrap().get_total(), 10);
+    }
+
+    #[test]
+    pub fn entity_proficiencies() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        // Global Class 1 and Global Race 1 both grant proficiencies 
+        let entity_id = engine.new_entity(EntityBuilder::new(String::from("Proficient Entity"))
+            .with_class(0x00000000111122223333444444444444)
+            .with_race(0xaaaaaaaabbbbccccddddeeeeeeeeeeee)).unwrap().get_uuid();
+
+        let proficiencies = engine.get_entity_proficiencies(entity_id).unwrap();
+        assert!(proficiencies.knows_language("Elvish"));
+        assert!(proficiencies.is_proficient_with_weapon(WeaponCategory::Simple));
+        assert!(engine.get_entity_proficiencies(55).is_none());
+    }
+
+    #[test]
+    pub fn party_passives() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Party"), String::new()).get_uuid();
+        let wise = engine.new_entity(EntityBuilder::new(String::from("Wise"))
+            .with_abilities(AbilityScores::new(10, 10, 10, 10, 16, 10))).unwrap().get_uuid();
+        let quick = engine.new_entity(EntityBuilder::new(String::from("Quick"))
+            .with_abilities(AbilityScores::new(10, 18, 10, 10, 10, 10))).unwrap().get_uuid();
+
+        assert!(engine.get_party_passives(55).is_none());
+        assert!(!engine.add_party_member(campaign_id, 55));
+        assert!(!engine.add_party_member(55, wise));
+        assert!(engine.add_party_member(campaign_id, wise));
+        assert!(engine.add_party_member(campaign_id, quick));
+        assert!(!engine.add_party_member(campaign_id, quick));
+
+        let passives = engine.get_party_passives(campaign_id).unwrap();
+        assert_eq!(passives.len(), 2);
+        assert_eq!(passives[0].get_name(), "Wise");
+        assert_eq!(passives[0].get_passive_perception(), 13);
+        assert_eq!(passives[1].get_name(), "Quick");
+        assert_eq!(passives[1].get_initiative_bonus(), 4);
+
+        assert!(engine.remove_party_member(campaign_id, wise));
+        assert_eq!(engine.get_party_passives(campaign_id).unwrap().len(), 1);
+    }
+
+    #[test]
+    pub fn homebrew_skills() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+        assert_eq!(engine.get_skills().len(), 18);
+
+        let cooking = engine.new_skill(String::from("Cooking"), Ability::Wisdom).get_skill();
+        assert_eq!(engine.get_skills().len(), 19);
+        assert_eq!(engine.get_skill(cooking).unwrap().get_name(), "Cooking");
+
+        let chef = engine.new_entity(EntityBuilder::new(String::from("Chef"))
+            .with_abilities(AbilityScores::new(10, 10, 10, 10, 14, 10))).unwrap().get_uuid();
+        assert_eq!(engine.explain_entity_stat(chef, Stat::Skill(cooking)).unwrap().get_total(), 2);
+        assert!(engine.explain_entity_stat(chef, Stat::Skill(Skill::from_uuid(0xdead))).is_none());
+    }
+
+    #[test]
+    pub fn entity_with_background() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        // Global Background 1 grants Insight, Religion, two languages, equipment and gold 
+        let acolyte = engine.new_entity(EntityBuilder::new(String::from("Acolyte"))
+            .with_background(0x00000000000000000000000000000bac)).unwrap();
+        assert_eq!(acolyte.get_background_uuid(), Some(0xbac));
+        assert_eq!(acolyte.get_skill_attribute(Skill::RELIGION), SkillAttributes::Proficient);
+        assert!(acolyte.get_own_proficiencies().knows_language("Celestial"));
+        assert_eq!(acolyte.get_equipment().len(), 3);
+        assert_eq!(acolyte.get_purse().get(Coin::Gold), 15);
+
+        let background = engine.get_background(0xbac).unwrap();
+        assert_eq!(background.get_feature().get_name(), "Shelter of the Faithful");
+
+        // Unknown backgrounds, classes and races create nothing 
+        assert!(engine.new_entity(EntityBuilder::new(String::from("Nobody")).with_background(55)).is_none());
+        assert!(engine.new_entity(EntityBuilder::new(String::from("Nobody")).with_class(55)).is_none());
+        assert!(engine.new_entity(EntityBuilder::new(String::from("Nobody")).with_race(55)).is_none());
+
+        let id = engine.new_background(String::from("Hermit")).get_uuid();
+        assert_eq!(engine.get_background(id).unwrap().get_name(), "Hermit");
+    }
+
+    #[test]
+    pub fn entity_biography() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+        let id = engine.new_entity(EntityBuilder::new(String::from("Bard"))).unwrap().get_uuid();
+
+        assert_eq!(engine.set_entity_size(id, Size::Small), Some(Size::Medium));
+        assert_eq!(engine.set_entity_size(55, Size::Small), None);
+
+        assert!(engine.edit_entity_biography(id, |b| {
+            b.set_alignment(Some(Alignment::ChaoticGood));
+            b.set_flaws(vec![String::from("Can't resist a pretty face")]);
+        }));
+        assert!(!engine.edit_entity_biography(55, |b| b.set_age(Some(20))));
+
+        let entity = engine.get_entity(id).unwrap();
+        assert_eq!(entity.get_size(), Size::Small);
+        assert_eq!(entity.get_biography().get_alignment(), Some(Alignment::ChaoticGood));
+        assert_eq!(entity.get_biography().get_flaws().len(), 1);
+    }
+
+    #[test]
+    pub fn party_loot() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Loot"), String::new()).get_uuid();
+        let members: Vec<u128> = ["Fighter", "Rogue", "Wizard"].iter()
+            .map(|n| engine.new_entity(EntityBuilder::new(String::from(*n))).unwrap().get_uuid())
+            .collect();
+        for id in &members {
+            engine.add_party_member(campaign_id, *id);
+        }
+
+        assert!(engine.split_party_loot(55, &Purse::new()).is_none());
+        let remainder = engine.split_party_loot(campaign_id, &Purse::from_coins(0, 0, 0, 10, 1)).unwrap();
+        assert_eq!(remainder, Purse::from_coins(2, 0, 0, 0, 0));
+        for id in &members {
+            assert_eq!(*engine.get_entity(*id).unwrap().get_purse(), Purse::from_coins(6, 6, 0, 6, 0));
+        }
+
+        // The Rogue buys something and makes change 
+        assert_eq!(engine.spend_entity_coins(members[1], Coin::Silver, 7), Some(true));
+        assert_eq!(*engine.get_entity(members[1]).unwrap().get_purse(), Purse::from_coins(6, 9, 0, 5, 0));
+        assert_eq!(engine.spend_entity_coins(members[1], Coin::Platinum, 1), Some(false));
+        assert_eq!(engine.exchange_entity_coins(members[1], Coin::Gold, Coin::Silver, 1), Some(true));
+        assert_eq!(engine.get_entity(members[1]).unwrap().get_purse().get(Coin::Silver), 19);
+        assert!(engine.add_entity_coins(members[2], Coin::Electrum, 2));
+        assert!(!engine.add_entity_coins(55, Coin::Electrum, 2));
+    }
+
+    #[test]
+    pub fn monsters() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Dragons"), String::new()).get_uuid();
+        assert!(engine.set_active_campaign(Some(campaign_id)));
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Lair")).unwrap().get_uuid();
+
+        // Global Monster 1 is a CR 10 dragon with 17d10+85 hit dice 
+        let average = engine.instantiate_monster(0xd4a6, campaign_id, scene_id, HitPointMode::Average).unwrap();
+        let rolled = engine.instantiate_monster(0xd4a6, campaign_id, scene_id, HitPointMode::Rolled).unwrap();
+        assert_eq!(engine.get_campaign(campaign_id).unwrap().get_scene(scene_id).unwrap().get_entities(), [average, rolled]);
+
+        let dragon = engine.get_entity(average).unwrap();
+        assert_eq!(dragon.get_name(), "Global Monster 1");
+        assert_eq!(dragon.get_hp_max(), 178);
+        assert_eq!(dragon.get_proficiency_bonus(), 4);
+        assert_eq!(dragon.get_size(), Size::Large);
+        assert_eq!(dragon.get_monster(&engine.asset_manager).unwrap().get_legendary_action_count(), 3);
+        assert!((17 + 85..=170 + 85).contains(&engine.get_entity(rolled).unwrap().get_hp_max()));
+
+        // Armor class and speed come from the stat block; saves and skills use the CR's proficiency bonus 
+        assert_eq!(engine.explain_entity_stat(average, Stat::ArmorClass).unwrap().get_total(), 18);
+        assert_eq!(engine.explain_entity_stat(average, Stat::Speed).unwrap().get_total(), 40);
+        assert_eq!(engine.explain_entity_stat(average, Stat::Save(Ability::Constitution)).unwrap().get_total(), 9);
+        assert_eq!(engine.explain_entity_stat(average, Stat::Skill(Skill::PERCEPTION)).unwrap().get_total(), 8);
+
+        assert!(engine.instantiate_monster(55, campaign_id, scene_id, HitPointMode::Average).is_none());
+        assert!(engine.instantiate_monster(0xd4a6, campaign_id, 55, HitPointMode::Average).is_none());
+
+        let id = engine.new_monster(String::from("Kobold"), CreatureType::Humanoid, ChallengeRating::EIGHTH).get_uuid();
+        assert_eq!(engine.get_monster(id).unwrap().get_xp(), 25);
+    }
+
+    #[test]
+    pub fn legendary_and_lair_actions() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Boss Fight"), String::new()).get_uuid();
+        assert!(engine.set_active_campaign(Some(campaign_id)));
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Lair")).unwrap().get_uuid();
+        let dragon = engine.instantiate_monster(0xd4a6, campaign_id, scene_id, HitPointMode::Average).unwrap();
+        let fighter = engine.new_entity(EntityBuilder::new(String::from("Fighter"))).unwrap().get_uuid();
+
+        assert!(engine.start_encounter(campaign_id, 55, &[]).is_none());
+        assert!(engine.next_turn(campaign_id, scene_id).is_none());
+
+        // The fighter goes first, then the lair on count 20, then the dragon 
+        assert_eq!(engine.start_encounter(campaign_id, scene_id, &[(dragon, Some(12)), (fighter, Some(21)), (55, Some(30))]).unwrap(), []);
+        assert_eq!(engine.get_encounter(campaign_id, scene_id).unwrap().get_combatants().len(), 2);
+        let prompts = engine.next_turn(campaign_id, scene_id).unwrap();
+        assert_eq!(prompts.len(), 2);
+        assert!(matches!(prompts[0], EncounterPrompt::LegendaryActions { remaining: 3, .. }));
+        assert!(matches!(prompts[1], EncounterPrompt::LairAction { .. }));
+
+        // Wing Attack costs 2 of the dragon's 3 legendary actions 
+        assert_eq!(engine.spend_legendary_action(campaign_id, scene_id, dragon, "Wing Attack"), Some(true));
+        assert_eq!(engine.spend_legendary_action(campaign_id, scene_id, dragon, "Wing Attack"), Some(false));
+        assert_eq!(engine.spend_legendary_action(campaign_id, scene_id, dragon, "Detect"), Some(true));
+        assert_eq!(engine.spend_legendary_action(campaign_id, scene_id, dragon, "Fly"), None);
+        assert_eq!(engine.spend_legendary_action(campaign_id, scene_id, fighter, "Detect"), None);
+        assert!(engine.resolve_lair_action(campaign_id, scene_id, dragon));
+        assert!(engine.get_encounter(campaign_id, scene_id).unwrap().get_prompts().is_empty());
+
+        assert!(engine.end_encounter(campaign_id, scene_id));
+        assert!(!engine.end_encounter(campaign_id, scene_id));
+    }
+
+    #[test]
+    pub fn encounter() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Brawl"), String::new()).get_uuid();
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Tavern")).unwrap().get_uuid();
+        let quick = engine.new_entity(EntityBuilder::new(String::from("Quick"))
+            .with_abilities(AbilityScores::new(10, 18, 10, 10, 10, 10))).unwrap().get_uuid();
+        let slow = engine.new_entity(EntityBuilder::new(String::from("Slow"))
+            .with_abilities(AbilityScores::new(10, 6, 10, 10, 10, 10))).unwrap().get_uuid();
+        let late = engine.new_entity(EntityBuilder::new(String::from("Late"))).unwrap().get_uuid();
+
+        // Tied initiative goes to the higher Dexterity 
+        engine.start_encounter(campaign_id, scene_id, &[(slow, Some(10)), (quick, Some(10))]).unwrap();
+        let encounter = engine.get_encounter(campaign_id, scene_id).unwrap();
+        assert_eq!(encounter.get_combatants()[0].get_entity(), quick);
+        assert_eq!(encounter.get_current().unwrap().get_entity(), quick);
+
+        // Rolled initiative is a d20 plus the initiative bonus 
+        let rolled = engine.add_combatant(campaign_id, scene_id, late, None).unwrap();
+        assert!((1..=20).contains(&rolled));
+        assert_eq!(engine.add_combatant(campaign_id, scene_id, late, Some(1)), None);
+        assert_eq!(engine.add_combatant(campaign_id, scene_id, 55, None), None);
+        assert!(engine.delay_combatant(campaign_id, scene_id, quick, 0));
+        assert_eq!(engine.get_encounter(campaign_id, scene_id).unwrap().get_current().unwrap().get_entity(), slow);
+        assert!(engine.remove_combatant(campaign_id, scene_id, late));
+        assert!(!engine.remove_combatant(campaign_id, scene_id, late));
+
+        // Slow is regenerating, and blessed until the end of their turn 
+        let blessed = ModifierSource::Spell { name: String::from("Bless") };
+        engine.add_entity_modifier(slow, blessed.clone(), Modifier::new(Stat::Initiative, ModifierKind::Flat(1)));
+        assert!(engine.add_turn_hook(campaign_id, scene_id, slow, TurnHook::Regeneration { amount: 100 }));
+        assert!(engine.add_turn_hook(campaign_id, scene_id, slow, TurnHook::ConditionExpiry { source: blessed, timing: TurnTiming::EndOfTurn, turns: 1 }));
+        assert!(!engine.add_turn_hook(campaign_id, scene_id, late, TurnHook::Regeneration { amount: 1 }));
+
+        engine.next_turn(campaign_id, scene_id);
+        let slow_entity = engine.get_entity(slow).unwrap();
+        assert!(slow_entity.get_modifiers().is_empty());
+        assert_eq!(slow_entity.get_hp(), slow_entity.get_hp_max());
+
+        // The Encounter survives a round trip through the Campaign file 
+        let campaign = engine.get_campaign(campaign_id).unwrap();
+        let de: crate::campaigns::campaign::Campaign = serde_yaml::from_str(&serde_yaml::to_string(campaign).unwrap()).unwrap();
+        let encounter = de.get_scene(scene_id).unwrap().get_encounter().unwrap();
+        assert_eq!(encounter.get_combatants(), engine.get_encounter(campaign_id, scene_id).unwrap().get_combatants());
+        assert_eq!(encounter.get_current().unwrap().get_entity(), quick);
+    }
+
+    #[test]
+    pub fn encounter_survives_restart() {
+        let root = tempdir::TempDir::new("panicvtt_encounter").unwrap();
+        std::fs::create_dir(root.path().join("campaigns")).unwrap();
+        std::fs::write(root.path().join("campaigns/manifest.panic"), "[]").unwrap();
+
+        let mut engine = Engine::new(Rng::new(0, 1), root.path());
+        let class = engine.new_class(String::from("Fighter"), Dice::D10).get_uuid();
+        let race = engine.new_race(String::from("Human"), 30).get_uuid();
+        let first = engine.new_entity(EntityBuilder::new(String::from("First")).with_class(class).with_race(race)).unwrap().get_uuid();
+        let second = engine.new_entity(EntityBuilder::new(String::from("Second")).with_class(class).with_race(race)).unwrap().get_uuid();
+        let campaign_id = engine.new_campaign(String::from("Brawl"), String::new()).get_uuid();
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Tavern")).unwrap().get_uuid();
+
+        engine.start_encounter(campaign_id, scene_id, &[(first, Some(15)), (second, Some(10))]).unwrap();
+        assert!(engine.add_turn_hook(campaign_id, scene_id, second, TurnHook::Regeneration { amount: 1 }));
+        engine.next_turn(campaign_id, scene_id);
+        engine.save().unwrap();
+
+        // A fresh engine over the same files picks the Encounter up where it left off 
+        let mut engine = Engine::new(Rng::new(0, 1), root.path());
+        assert!(matches!(engine.load_campaign(campaign_id), LoadAssetResult::Ok { .. }));
+        let encounter = engine.get_encounter(campaign_id, scene_id).unwrap();
+        assert_eq!(encounter.get_round(), 1);
+        assert_eq!(encounter.get_current().unwrap().get_entity(), second);
+        assert_eq!(encounter.get_combatant(second).unwrap().get_hooks().len(), 1);
+
+        engine.next_turn(campaign_id, scene_id);
+        assert_eq!(engine.get_encounter(campaign_id, scene_id).unwrap().get_round(), 2);
+    }
+
+    #[test]
+    pub fn encounter_difficulty() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+        let party: Vec<u128> = (0..4).map(|_| engine.new_entity(EntityBuilder::new(String::from("Hero"))).unwrap().get_uuid()).collect();
+        let kobold = engine.new_monster(String::from("Kobold"), CreatureType::Humanoid, ChallengeRating::EIGHTH).get_uuid();
+
+        // Four 1st level heroes against a CR 10 dragon, and against a pack of kobolds 
+        let report = engine.get_encounter_difficulty(&party, &[0xd4a6]).unwrap();
+        assert_eq!(report.get_adjusted_xp(), 5900);
+        assert_eq!(report.get_difficulty(), Difficulty::Deadly);
+        let report = engine.get_encounter_difficulty(&party, &[kobold; 4]).unwrap();
+        assert_eq!(report.get_adjusted_xp(), 200);
+        assert_eq!(report.get_difficulty(), Difficulty::Medium);
+        assert!(engine.get_encounter_difficulty(&party, &[55]).is_none());
+        assert!(engine.get_encounter_difficulty(&[55], &[kobold]).is_none());
+
+        // Only kobolds are gentle enough for an easy fight 
+        let sets = engine.suggest_encounters(&party, Difficulty::Easy, 6).unwrap();
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].get_monsters(), [(kobold, 3)]);
+        assert!(engine.suggest_encounters(&[55], Difficulty::Easy, 6).is_none());
+    }
+
+    #[test]
+    pub fn tokens() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Maps"), String::new()).get_uuid();
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Cave")).unwrap().get_uuid();
+        assert!(engine.set_scene_grid(campaign_id, scene_id, Grid::new(GridType::Square, 12, 12).with_background(String::from("maps/cave.webp"))));
+        assert!(!engine.set_scene_grid(campaign_id, 55, Grid::default()));
+
+        // The dragon is Large, so it takes up two cells on each side 
+        let dragon = engine.new_monster(String::from("Wyrmling"), CreatureType::Dragon, ChallengeRating::from_whole(2).unwrap()).get_uuid();
+        let dragon = engine.asset_manager.create_monster_entity(dragon, None, HitPointMode::Average, &mut engine.rng).unwrap().unwrap().get_uuid();
+        engine.set_entity_size(dragon, Size::Large);
+        let hero = engine.new_entity(EntityBuilder::new(String::from("Hero"))).unwrap().get_uuid();
+
+        assert!(engine.place_token(campaign_id, scene_id, dragon, GridPosition::new(4, 4)).unwrap().is_ok());
+        assert_eq!(engine.get_scene(campaign_id, scene_id).unwrap().get_token(dragon).unwrap().get_footprint(), 2);
+        let e = engine.place_token(campaign_id, scene_id, hero, GridPosition::new(5, 5)).unwrap().unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::Occupied { position: GridPosition::new(5, 5), entity: dragon }));
+        assert!(engine.place_token(campaign_id, scene_id, hero, GridPosition::new(6, 5)).unwrap().is_ok());
+        assert!(engine.place_token(campaign_id, scene_id, 55, GridPosition::new(0, 0)).is_none());
+
+        assert!(engine.move_token(campaign_id, scene_id, hero, GridPosition::new(11, 11)).unwrap().is_ok());
+        assert!(engine.move_token(campaign_id, scene_id, hero, GridPosition::new(12, 11)).unwrap().is_err());
+        assert!(engine.edit_token(campaign_id, scene_id, hero, |t| t.set_visible(false)));
+        assert!(!engine.get_scene(campaign_id, scene_id).unwrap().get_token(hero).unwrap().is_visible());
+
+        assert!(engine.remove_token(campaign_id, scene_id, hero));
+        assert!(!engine.remove_token(campaign_id, scene_id, hero));
+        assert!(!engine.edit_token(campaign_id, scene_id, hero, |t| t.set_rotation(90)));
+        assert_eq!(engine.get_scene(campaign_id, scene_id).unwrap().get_entities(), [dragon, hero]);
+    }
+
+    #[test]
+    pub fn movement() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Chase"), String::new()).get_uuid();
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Bog")).unwrap().get_uuid();
+        let mut grid = Grid::new(GridType::Square, 20, 20).with_diagonal_rule(DiagonalRule::Alternating);
+        grid.set_difficult_terrain(GridPosition::new(0, 2), true);
+        engine.set_scene_grid(campaign_id, scene_id, grid);
+
+        let hero = engine.new_entity(EntityBuilder::new(String::from("Hero"))).unwrap().get_uuid();
+        engine.add_entity_modifier(hero, ModifierSource::Condition { name: String::from("Longstrider") }, Modifier::new(Stat::Speed, ModifierKind::Flat(30)));
+        let speed = engine.get_entity(hero).unwrap().get_speed(&engine.asset_manager).unwrap() as u32;
+        engine.place_token(campaign_id, scene_id, hero, GridPosition::new(0, 0)).unwrap().unwrap();
+        let path = |cells: &[(u16, u16)]| cells.iter().map(|(x, y)| GridPosition::new(*x, *y)).collect::<Vec<_>>();
+
+        // Out of combat, movement is measured but not limited
+        assert_eq!(engine.walk_token(campaign_id, scene_id, hero, &path(&[(0, 1), (0, 2)])).unwrap().unwrap(), 15);
+        assert_eq!(engine.get_remaining_movement(campaign_id, scene_id, hero), None);
+        let e = engine.walk_token(campaign_id, scene_id, hero, &path(&[(0, 4)])).unwrap().unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::InvalidPath));
+
+        // The second diagonal of the turn costs double 
+        engine.start_encounter(campaign_id, scene_id, &[(hero, Some(10))]).unwrap();
+        assert_eq!(engine.walk_token(campaign_id, scene_id, hero, &path(&[(1, 3), (2, 4)])).unwrap().unwrap(), 15);
+        assert_eq!(engine.get_remaining_movement(campaign_id, scene_id, hero), Some(speed - 15));
+
+        // Too far to walk until the hero dashes 
+        let far: Vec<(u16, u16)> = (3..3 + speed as u16 / 5).map(|x| (x, 4)).collect();
+        let e = engine.walk_token(campaign_id, scene_id, hero, &path(&far)).unwrap().unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::OverBudget { cost: speed, remaining: speed - 15 }));
+        assert_eq!(engine.get_scene(campaign_id, scene_id).unwrap().get_token(hero).unwrap().get_position(), GridPosition::new(2, 4));
+        assert!(engine.dash(campaign_id, scene_id, hero));
+        assert!(engine.disengage(campaign_id, scene_id, hero));
+        assert_eq!(engine.walk_token(campaign_id, scene_id, hero, &path(&far)).unwrap().unwrap(), speed);
+        assert_eq!(engine.get_remaining_movement(campaign_id, scene_id, hero), Some(speed - 15));
+
+        assert!(engine.next_turn(campaign_id, scene_id).is_some());
+        assert_eq!(engine.get_remaining_movement(campaign_id, scene_id, hero), Some(speed));
+        assert!(engine.walk_token(campaign_id, scene_id, 55, &[]).is_none());
+    }
+
+    #[test]
+    pub fn walking_past_tokens() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Ambush"), String::new()).get_uuid();
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Alley")).unwrap().get_uuid();
+        engine.set_scene_grid(campaign_id, scene_id, Grid::new(GridType::Square, 10, 10));
+        let race = engine.new_race(String::from("Walker"), 30).get_uuid();
+        let mut place = |name: &str, x: u16, y: u16| {
+            let entity = engine.new_entity(EntityBuilder::new(String::from(name)).with_race(race)).unwrap().get_uuid();
+            engine.place_token(campaign_id, scene_id, entity, GridPosition::new(x, y)).unwrap().unwrap();
+            entity
+        };
+        let (hero, ally, foe) = (place("Hero", 0, 0), place("Ally", 1, 0), place("Foe", 0, 1));
+        engine.add_party_member(campaign_id, hero);
+        engine.add_party_member(campaign_id, ally);
+
+        // Party members pass through each other, but can't stop in the same space or pass anybody else 
+        let e = engine.walk_token(campaign_id, scene_id, hero, &[GridPosition::new(0, 1), GridPosition::new(0, 2)]).unwrap().unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::Occupied { position: GridPosition::new(0, 1), entity: foe }));
+        assert_eq!(engine.walk_token(campaign_id, scene_id, hero, &[GridPosition::new(1, 0), GridPosition::new(2, 0)]).unwrap().unwrap(), 10);
+        assert!(engine.walk_token(campaign_id, scene_id, hero, &[GridPosition::new(1, 0)]).unwrap().is_err());
+        assert!(engine.walk_token(campaign_id, scene_id, foe, &[GridPosition::new(1, 1), GridPosition::new(2, 1)]).unwrap().is_ok());
+        let e = engine.walk_token(campaign_id, scene_id, foe, &[GridPosition::new(2, 0), GridPosition::new(3, 0)]).unwrap().unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::Occupied { position: GridPosition::new(2, 0), entity: hero }));
+
+        // In combat, only the current combatant walks 
+        engine.start_encounter(campaign_id, scene_id, &[(hero, Some(10)), (foe, Some(15))]).unwrap();
+        let e = engine.walk_token(campaign_id, scene_id, hero, &[GridPosition::new(3, 0)]).unwrap().unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::OutOfTurn { entity: hero }));
+        assert!(engine.walk_token(campaign_id, scene_id, ally, &[GridPosition::new(1, 1)]).unwrap().is_ok());
+        engine.next_turn(campaign_id, scene_id);
+        assert_eq!(engine.walk_token(campaign_id, scene_id, hero, &[GridPosition::new(3, 0)]).unwrap().unwrap(), 5);
+    }
+
+    #[test]
+    pub fn area_saves() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Blast"), String::new()).get_uuid();
+        assert!(engine.set_active_campaign(Some(campaign_id)));
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Hall")).unwrap().get_uuid();
+        let dragon = engine.instantiate_monster(0xd4a6, campaign_id, scene_id, HitPointMode::Average).unwrap();
+        let hero = engine.new_entity(EntityBuilder::new(String::from("Hero"))).unwrap().get_uuid();
+        let bystander = engine.new_entity(EntityBuilder::new(String::from("Bystander"))).unwrap().get_uuid();
+        engine.place_token(campaign_id, scene_id, dragon, GridPosition::new(4, 4)).unwrap().unwrap();
+        engine.place_token(campaign_id, scene_id, hero, GridPosition::new(7, 7)).unwrap().unwrap();
+        engine.place_token(campaign_id, scene_id, bystander, GridPosition::new(15, 15)).unwrap().unwrap();
+
+        let fireball = AreaOfEffect::new(AreaShape::Sphere { radius: 20 }, GridPosition::new(6, 6));
+        let targets = engine.get_area_targets(campaign_id, scene_id, &fireball).unwrap();
+        assert_eq!(targets, [dragon, hero]);
+        assert!(engine.get_area_targets(campaign_id, 55, &fireball).is_none());
+
+        // Everyone fails, then everyone succeeds and takes half 
+        let hp = engine.get_entity(dragon).unwrap().get_hp();
+        let results = engine.resolve_save_for_half(&targets, Ability::Dexterity, 100, 28).unwrap();
+        assert!(results.iter().all(|r| !r.is_success() && (1..=20).contains(&r.get_roll())));
+        assert_eq!(results[0].get_damage(), 28);
+        let results = engine.resolve_save_for_half(&targets, Ability::Dexterity, -100, 29).unwrap();
+        assert!(results.iter().all(|r| r.is_success()));
+        assert_eq!(results[0].get_damage(), 14);
+        assert_eq!(engine.get_entity(dragon).unwrap().get_hp(), hp - 42);
+
+        // The dragon is proficient in Dexterity saves 
+        let dex = engine.get_entity(dragon).unwrap().get_stat(Stat::Save(Ability::Dexterity), &engine.asset_manager).unwrap();
+        assert_eq!(results[0].get_total(), results[0].get_roll() as StatIntType + dex);
+
+        // Damage stops at 0 hit points, and nothing happens if a target is unknown 
+        let results = engine.resolve_save_for_half(&[hero], Ability::Dexterity, 100, u16::MAX).unwrap();
+        assert_eq!(engine.get_entity(hero).unwrap().get_hp(), 0);
+        assert!(results[0].get_damage() < u16::MAX);
+        assert!(engine.resolve_save_for_half(&[dragon, 55], Ability::Dexterity, 100, 10).is_none());
+        assert_eq!(engine.get_entity(dragon).unwrap().get_hp(), hp - 42);
+    }
+
+    #[test]
+    pub fn walls() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Siege"), String::new()).get_uuid();
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Keep")).unwrap().get_uuid();
+        let archer = engine.new_entity(EntityBuilder::new(String::from("Archer"))).unwrap().get_uuid();
+        let guard = engine.new_entity(EntityBuilder::new(String::from("Guard"))).unwrap().get_uuid();
+        engine.place_token(campaign_id, scene_id, archer, GridPosition::new(2, 5)).unwrap().unwrap();
+        engine.place_token(campaign_id, scene_id, guard, GridPosition::new(10, 5)).unwrap().unwrap();
+
+        // A wall with a door in it, from (6, 0) down to (6, 20)
+        engine.add_wall(campaign_id, scene_id, Wall::new(GridPosition::new(6, 0), GridPosition::new(6, 5))).unwrap();
+        let door = engine.add_wall(campaign_id, scene_id, Wall::new(GridPosition::new(6, 5), GridPosition::new(6, 6)).with_kind(WallKind::Door)).unwrap();
+        engine.add_wall(campaign_id, scene_id, Wall::new(GridPosition::new(6, 6), GridPosition::new(6, 20))).unwrap();
+        assert_eq!(engine.get_cover(campaign_id, scene_id, archer, guard), Some(Cover::Total));
+        assert_eq!(engine.has_line_of_sight(campaign_id, scene_id, archer, guard), Some(false));
+        assert_eq!(engine.resolve_attack(campaign_id, scene_id, archer, guard, WeaponCategory::Simple, Ability::Dexterity).unwrap().get_roll(), None);
+
+        let e = engine.walk_token(campaign_id, scene_id, archer, &(3..8).map(|x| GridPosition::new(x, 5)).collect::<Vec<_>>()).unwrap().unwrap_err();
+        assert_eq!(e.get_kind(), Some(&TokenErrorKind::Blocked { position: GridPosition::new(6, 5) }));
+
+        // Locked doors have to be unlocked before they can be opened 
+        assert!(engine.set_wall_state(campaign_id, scene_id, door, WallState::Locked));
+        assert!(!engine.open_wall(campaign_id, scene_id, door));
+        assert!(engine.set_wall_state(campaign_id, scene_id, door, WallState::Closed));
+        assert!(engine.open_wall(campaign_id, scene_id, door));
+        assert!(!engine.open_wall(campaign_id, scene_id, 0));
+        assert!(!engine.set_wall_state(campaign_id, scene_id, 55, WallState::Open));
+
+        // Through the open door the guard is only partly hidden 
+        let cover = engine.get_cover(campaign_id, scene_id, archer, guard).unwrap();
+        assert!(cover > Cover::Clear && cover < Cover::Total);
+        let attack = engine.resolve_attack(campaign_id, scene_id, archer, guard, WeaponCategory::Simple, Ability::Dexterity).unwrap();
+        let armor_class = engine.get_entity(guard).unwrap().get_armor_class(&engine.asset_manager).unwrap();
+        assert_eq!(attack.get_armor_class(), armor_class + cover.get_bonus());
+        assert!(attack.get_roll().is_some());
+        assert_eq!(engine.walk_token(campaign_id, scene_id, archer, &(3..8).map(|x| GridPosition::new(x, 5)).collect::<Vec<_>>()).unwrap().unwrap(), 25);
+
+        assert!(engine.remove_wall(campaign_id, scene_id, door).is_some());
+        assert!(engine.remove_wall(campaign_id, scene_id, 55).is_none());
+        assert!(engine.get_cover(campaign_id, scene_id, archer, 55).is_none());
+    }
+
+    #[test]
+    pub fn fog_of_war() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Dungeon Crawl"), String::new()).get_uuid();
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Cells")).unwrap().get_uuid();
+        engine.set_scene_grid(campaign_id, scene_id, Grid::new(GridType::Square, 10, 10));
+        let door = engine.add_wall(campaign_id, scene_id, Wall::new(GridPosition::new(5, 0), GridPosition::new(5, 10)).with_kind(WallKind::Door)).unwrap();
+
+        let rogue = engine.new_entity(EntityBuilder::new(String::from("Rogue"))).unwrap().get_uuid();
+        let zombie = engine.new_entity(EntityBuilder::new(String::from("Zombie"))).unwrap().get_uuid();
+        assert!(engine.assign_entity(campaign_id, "alice", rogue));
+        assert!(!engine.assign_entity(campaign_id, "alice", rogue));
+        assert!(!engine.assign_entity(55, "alice", rogue));
+
+        // Placing a token explores what it can see for its players 
+        engine.place_token(campaign_id, scene_id, zombie, GridPosition::new(8, 8)).unwrap().unwrap();
+        assert!(engine.get_explored_cells(campaign_id, scene_id, "alice").is_none());
+        engine.place_token(campaign_id, scene_id, rogue, GridPosition::new(1, 1)).unwrap().unwrap();
+        assert_eq!(engine.get_explored_cells(campaign_id, scene_id, "alice").unwrap().len(), 50);
+
+        let visible = |engine: &Engine| engine.get_visible_tokens(campaign_id, scene_id, "alice").unwrap().iter().map(|t| t.get_entity()).collect::<Vec<_>>();
+        assert_eq!(visible(&engine), [rogue]);
+        assert_eq!(engine.get_visible_cells(campaign_id, scene_id, "alice").unwrap().len(), 50);
+        assert_eq!(engine.explore(campaign_id, scene_id, "alice"), Some(0));
+
+        // Opening the door reveals the zombie and the rest of the room 
+        assert!(engine.open_wall(campaign_id, scene_id, door));
+        assert_eq!(visible(&engine), [zombie, rogue]);
+        assert_eq!(engine.explore(campaign_id, scene_id, "alice"), Some(50));
+
+        // Players who control nothing see nothing 
+        assert!(engine.get_visible_tokens(campaign_id, scene_id, "bob").unwrap().is_empty());
+        assert!(engine.get_explored_cells(campaign_id, scene_id, "bob").is_none());
+        assert_eq!(engine.get_explored_cells(campaign_id, scene_id, "alice").unwrap().len(), 100);
+        assert!(engine.explore(campaign_id, 55, "alice").is_none());
+    }
+
+    #[test]
+    pub fn lighting() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Underdark"), String::new()).get_uuid();
+        let scene_id = engine.asset_manager.get_campaign_mut(campaign_id).unwrap().create_scene(String::from("Cavern")).unwrap().get_uuid();
+        assert!(engine.set_ambient_light(campaign_id, scene_id, LightLevel::Dark));
+        assert!(!engine.set_ambient_light(campaign_id, 55, LightLevel::Dark));
+
+        let human = engine.new_entity(EntityBuilder::new(String::from("Human"))).unwrap().get_uuid();
+        let dwarf = engine.new_entity(EntityBuilder::new(String::from("Dwarf"))).unwrap().get_uuid();
+        let goblin = engine.new_entity(EntityBuilder::new(String::from("Goblin"))).unwrap().get_uuid();
+        let source = ModifierSource::Condition { name: String::from("Dwarven Eyes") };
+        engine.add_entity_modifier(dwarf, source, Modifier::new(Stat::Sense(Sense::Darkvision), ModifierKind::SetTo(60)));
+        engine.place_token(campaign_id, scene_id, human, GridPosition::new(2, 2)).unwrap().unwrap();
+        engine.place_token(campaign_id, scene_id, dwarf, GridPosition::new(2, 3)).unwrap().unwrap();
+        engine.place_token(campaign_id, scene_id, goblin, GridPosition::new(6, 2)).unwrap().unwrap();
+        engine.assign_entity(campaign_id, "alice", human);
+        engine.assign_entity(campaign_id, "bob", dwarf);
+
+        // In the dark the human sees only themself, while the dwarf sees everyone dimly 
+        let goblin_cell = GridPosition::new(6, 2);
+        assert_eq!(engine.get_visible_tokens(campaign_id, scene_id, "alice").unwrap().len(), 1);
+        assert_eq!(engine.get_visible_tokens(campaign_id, scene_id, "bob").unwrap().len(), 3);
+        assert_eq!(engine.get_perceived_light(campaign_id, scene_id, human, goblin_cell), Some(LightLevel::Dark));
+        assert_eq!(engine.has_sight_disadvantage(campaign_id, scene_id, dwarf, goblin_cell), Some(true));
+
+        // Dim light makes the dwarf roll Perception with disadvantage there, and lowers their passive Perception by 5 
+        let passive = engine.get_entity(dwarf).unwrap().get_passive_perception(&engine.asset_manager).unwrap();
+        let perception = engine.explain_perception_at(campaign_id, scene_id, dwarf, goblin_cell).unwrap();
+        assert!(perception.has_disadvantage());
+        assert_eq!(engine.get_passive_perception_at(campaign_id, scene_id, dwarf, goblin_cell), Some(passive - 5));
+        let (roll, total) = engine.roll_perception_at(campaign_id, scene_id, dwarf, goblin_cell).unwrap();
+        assert_eq!(total, roll as StatIntType + perception.get_total());
+
+        // The human lights a torch 
+        let torch = engine.add_light(campaign_id, scene_id, Light::on_entity(LightSource::TORCH, human)).unwrap();
+        assert_eq!(engine.get_light_level(campaign_id, scene_id, goblin_cell), Some(LightLevel::Bright));
+        assert_eq!(engine.get_visible_tokens(campaign_id, scene_id, "alice").unwrap().len(), 3);
+        assert_eq!(engine.has_sight_disadvantage(campaign_id, scene_id, dwarf, goblin_cell), Some(false));
+        assert_eq!(engine.get_passive_perception_at(campaign_id, scene_id, dwarf, goblin_cell), Some(passive));
+
+        assert!(engine.remove_light(campaign_id, scene_id, torch).is_some());
+        assert!(engine.has_sight_disadvantage(campaign_id, scene_id, 55, goblin_cell).is_none());
+        assert!(engine.roll_perception_at(campaign_id, scene_id, 55, goblin_cell).is_none());
+        assert!(engine.add_light(campaign_id, 55, Light::at_position(LightSource::CANDLE, goblin_cell)).is_none());
+    }
+
+    #[test]
+    pub fn scenes() {
+        let rng = Rng::new(0, 1);
+        let mut engine = Engine::new(rng, Path::new("test/assets"));
+
+        let campaign_id = engine.new_campaign(String::from("Sandbox"), String::new()).get_uuid();
+        let inn = engine.create_scene(campaign_id, String::from("Inn")).unwrap().get_uuid();
+        let forest = engine.create_scene(campaign_id, String::from("Forest")).unwrap().get_uuid();
+        assert!(engine.create_scene(55, String::from("Nowhere")).is_none());
+        assert_eq!(engine.get_scene(campaign_id, inn).unwrap().get_owning_campaign(), Some(campaign_id));
+
+        assert!(engine.rename_scene(campaign_id, forest, String::from("Dark Forest")));
+        assert!(!engine.rename_scene(campaign_id, 55, String::from("Nowhere")));
+        let copy = engine.duplicate_scene(campaign_id, inn, String::from("Inn (Burning)")).unwrap().get_uuid();
+        assert!(engine.move_scene(campaign_id, forest, 0));
+        let names: Vec<&str> = engine.get_scenes(campaign_id).unwrap().iter().map(|s| s.get_name()).collect();
+        assert_eq!(names, ["Dark Forest", "Inn", "Inn (Burning)"]);
+
+        // Entities travel from Scene to Scene without their tokens 
+        let hero = engine.new_entity(EntityBuilder::new(String::from("Hero"))).unwrap().get_uuid();
+        engine.place_token(campaign_id, inn, hero, GridPosition::new(1, 1)).unwrap().unwrap();
+        assert!(engine.move_entity_to_scene(campaign_id, hero, inn, forest));
+        assert!(engine.get_scene(campaign_id, inn).unwrap().get_token(hero).is_none());
+        assert_eq!(engine.get_scene(campaign_id, forest).unwrap().get_entities(), [hero]);
+
+        assert!(engine.set_active_scene(campaign_id, Some(copy)));
+        assert!(!engine.set_active_scene(campaign_id, Some(55)));
+        assert!(engine.delete_scene(campaign_id, copy).is_some());
+        assert_eq!(engine.get_campaign(campaign_id).unwrap().get_active_scene(), None);
+        assert_eq!(engine.get_scenes(campaign_id).unwrap().len(), 2);
+    }
+}
@@ -1,5 +1,18 @@
 pub mod abilities;
+pub mod background;
+pub mod biography;
 pub mod class;
+pub mod encumbrance;
 pub mod entity;
+pub mod feat;
+pub mod improvement;
+pub mod item;
+pub mod modifier;
+pub mod monster;
+pub mod passives;
+pub mod proficiencies;
+pub mod purse;
 pub mod race;
+pub mod senses;
+pub mod size;
 pub mod skills;
\ No newline at end of file
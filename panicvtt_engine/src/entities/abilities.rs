@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use enum_map::Enum;
 use serde::{Deserialize, Serialize};
-use strum::{EnumCount, EnumIter};
+use strum::{EnumCount, EnumIter, IntoStaticStr};
 
 use super::skills::SkillModifierIntType;
 
@@ -54,6 +54,33 @@ impl AbilityScores {
         // Max ability score possible is 30, so this won't panic
         ((score as f64 - 10f64) / 2f64).floor() as SkillModifierIntType
     }
+
+    /// Raise an ability score by up to `amount` without exceeding `cap`. 
+    /// 
+    /// Returns the amount actually applied, which is less than `amount` if the cap was hit. 
+    pub fn increase_ability_score(&mut self, ability: Ability, amount: AbilityScoreIntType, cap: AbilityScoreIntType) -> AbilityScoreIntType {
+        let score = self.get_ability_score_mut(ability);
+        let applied = cap.saturating_sub(*score).min(amount);
+        *score += applied;
+        applied
+    }
+
+    /// Lower an ability score by `amount`, stopping at 0. 
+    pub fn decrease_ability_score(&mut self, ability: Ability, amount: AbilityScoreIntType) {
+        let score = self.get_ability_score_mut(ability);
+        *score = score.saturating_sub(amount);
+    }
+
+    fn get_ability_score_mut(&mut self, ability: Ability) -> &mut AbilityScoreIntType {
+        match ability {
+            Ability::Strength => &mut self.strength,
+            Ability::Dexterity => &mut self.dexterity,
+            Ability::Constitution => &mut self.constitution,
+            Ability::Intelligence => &mut self.intelligence,
+            Ability::Wisdom => &mut self.wisdom,
+            Ability::Charisma => &mut self.charisma,
+        }
+    }
 }
 
 impl Display for AbilityScores {
@@ -69,7 +96,8 @@ impl Display for AbilityScores {
     }
 }
 
-#[derive(Clone, Copy, Enum, EnumIter, EnumCount, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Enum, EnumIter, EnumCount, IntoStaticStr, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[strum(serialize_all = "snake_case")]
 pub enum Ability {
     Strength, 
     Dexterity,
@@ -100,8 +128,9 @@ impl Display for Ability {
 
 pub type SaveIntType = i8; 
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SaveAttributes {
+    #[default]
     Normal          = 0,
     Proficient      = 1, 
 }
@@ -145,6 +174,25 @@ mod tests {
         assert_eq!(scores.get_ability_score(Ability::Charisma), scores.charisma);
     }
 
+    #[test]
+    fn ability_scores_increase_decrease() {
+        let mut scores = AbilityScores::new(10, 19, 20, 4, 5, 6);
+
+        assert_eq!(scores.increase_ability_score(Ability::Strength, 2, 20), 2);
+        assert_eq!(scores.strength, 12);
+
+        // Capped increases only apply what fits 
+        assert_eq!(scores.increase_ability_score(Ability::Dexterity, 2, 20), 1);
+        assert_eq!(scores.dexterity, 20);
+        assert_eq!(scores.increase_ability_score(Ability::Constitution, 1, 20), 0);
+        assert_eq!(scores.constitution, 20);
+
+        scores.decrease_ability_score(Ability::Dexterity, 1);
+        assert_eq!(scores.dexterity, 19);
+        scores.decrease_ability_score(Ability::Intelligence, 10);
+        assert_eq!(scores.intelligence, 0);
+    }
+
     #[test]
     fn ability_scores_impls() {
         let scores = AbilityScores::new(1, 2, 3, 21, 22, 23);
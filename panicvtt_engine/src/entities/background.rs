@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::assets::asset::Asset;
+
+use super::{item::Item, proficiencies::Proficiencies, skills::Skill};
+
+/// A background's narrative feature, e.g. a Sage's Researcher.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct BackgroundFeature {
+    name: String,
+    #[serde(default)]
+    description: String,
+}
+
+impl BackgroundFeature {
+    pub fn new(name: String, description: String) -> Self {
+        Self { name, description }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+}
+
+/// A background grants skill, tool and language proficiencies, starting equipment and gold when an Entity is created with it.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Background {
+    #[serde(with = "uuid::serde::simple")]
+    uuid: Uuid,
+    #[serde(skip)]
+    campaign: Option<u128>,
+    #[serde(default)]
+    overrides: Option<u128>,
+    name: String,
+    #[serde(default)]
+    skills: Vec<Skill>,
+    #[serde(default)]
+    proficiencies: Proficiencies,
+    #[serde(default)]
+    equipment: Vec<Item>,
+    #[serde(default)]
+    gold: u32,
+    #[serde(default)]
+    feature: BackgroundFeature,
+}
+
+impl Background {
+    pub fn new(name: String) -> Self {
+        Self {
+            uuid: Uuid::now_v7(),
+            campaign: None,
+            overrides: None,
+            name,
+            skills: Vec::new(),
+            proficiencies: Proficiencies::new(),
+            equipment: Vec::new(),
+            gold: 0,
+            feature: BackgroundFeature::default(),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn with_skills(mut self, skills: Vec<Skill>) -> Self {
+        self.skills = skills;
+        self
+    }
+
+    pub fn get_skills(&self) -> &[Skill] {
+        &self.skills
+    }
+
+    pub fn with_proficiencies(mut self, proficiencies: Proficiencies) -> Self {
+        self.proficiencies = proficiencies;
+        self
+    }
+
+    pub fn get_proficiencies(&self) -> &Proficiencies {
+        &self.proficiencies
+    }
+
+    /// Starting equipment, and gold in gp. 
+    pub fn with_equipment(mut self, equipment: Vec<Item>, gold: u32) -> Self {
+        self.equipment = equipment;
+        self.gold = gold;
+        self
+    }
+
+    pub fn get_equipment(&self) -> &[Item] {
+        &self.equipment
+    }
+
+    pub fn get_gold(&self) -> u32 {
+        self.gold
+    }
+
+    pub fn with_feature(mut self, feature: BackgroundFeature) -> Self {
+        self.feature = feature;
+        self
+    }
+
+    pub fn get_feature(&self) -> &BackgroundFeature {
+        &self.feature
+    }
+}
+
+impl Asset for Background {
+    fn get_uuid(&self) -> u128 {
+        self.uuid.as_u128()
+    }
+
+    fn get_owning_campaign(&self) -> Option<u128> {
+        self.campaign
+    }
+
+    fn set_owning_campaign(&mut self, campaign: Option<u128>) {
+        self.campaign = campaign;
+    }
+
+    fn get_overridden_asset(&self) -> Option<u128> {
+        self.overrides
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn getters() {
+        let mut p = Proficiencies::new();
+        p.add_language(String::from("Draconic"));
+        let background = Background::new(String::from("Sage"))
+            .with_skills(vec![Skill::ARCANA, Skill::HISTORY])
+            .with_proficiencies(p.clone())
+            .with_equipment(vec![Item::new(String::from("Bottle of ink"), 0.0, 1), Item::new(String::from("Quill"), 0.0, 1)], 10)
+            .with_feature(BackgroundFeature::new(String::from("Researcher"), String::from("You know where to look.")));
+
+        assert_eq!(background.get_name(), "Sage");
+        assert_eq!(background.get_skills(), [Skill::ARCANA, Skill::HISTORY]);
+        assert_eq!(*background.get_proficiencies(), p);
+        assert_eq!(background.get_equipment().len(), 2);
+        assert_eq!(background.get_gold(), 10);
+        assert_eq!(background.get_feature().get_name(), "Researcher");
+    }
+
+    #[test]
+    pub fn serde() {
+        let data = "
+uuid: 00000000000000000000000000000bac
+name: Acolyte
+skills: [Insight, Religion]
+proficiencies:
+  languages: [Celestial, Infernal]
+equipment: [Holy symbol, Prayer book]
+gold: 15
+feature:
+  name: Shelter of the Faithful
+";
+        let background: Background = serde_yaml::from_str(data).unwrap();
+        assert_eq!(background.get_uuid(), 0xbac);
+        assert_eq!(background.get_skills(), [Skill::INSIGHT, Skill::RELIGION]);
+        assert!(background.get_proficiencies().knows_language("Infernal"));
+        assert_eq!(background.get_gold(), 15);
+        assert_eq!(background.get_feature().get_name(), "Shelter of the Faithful");
+        assert_eq!(background.get_feature().get_description(), "");
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    LawfulGood,
+    NeutralGood,
+    ChaoticGood,
+    LawfulNeutral,
+    TrueNeutral,
+    ChaoticNeutral,
+    LawfulEvil,
+    NeutralEvil,
+    ChaoticEvil,
+    Unaligned,
+}
+
+impl Display for Alignment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Alignment::LawfulGood => "Lawful Good",
+            Alignment::NeutralGood => "Neutral Good",
+            Alignment::ChaoticGood => "Chaotic Good",
+            Alignment::LawfulNeutral => "Lawful Neutral",
+            Alignment::TrueNeutral => "True Neutral",
+            Alignment::ChaoticNeutral => "Chaotic Neutral",
+            Alignment::LawfulEvil => "Lawful Evil",
+            Alignment::NeutralEvil => "Neutral Evil",
+            Alignment::ChaoticEvil => "Chaotic Evil",
+            Alignment::Unaligned => "Unaligned",
+        })
+    }
+}
+
+/// The non-mechanical parts of a character sheet. Every field is optional so older Entity files still load.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct Biography {
+    #[serde(default)]
+    alignment: Option<Alignment>,
+    #[serde(default)]
+    age: Option<u16>,
+    #[serde(default)]
+    appearance: String,
+    #[serde(default)]
+    personality_traits: Vec<String>,
+    #[serde(default)]
+    ideals: Vec<String>,
+    #[serde(default)]
+    bonds: Vec<String>,
+    #[serde(default)]
+    flaws: Vec<String>,
+    #[serde(default)]
+    backstory: String,
+    /// Path or URL of the portrait image, relative to the server's static assets if not absolute.
+    #[serde(default)]
+    portrait: Option<String>,
+}
+
+impl Biography {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn get_alignment(&self) -> Option<Alignment> {
+        self.alignment
+    }
+
+    pub fn set_alignment(&mut self, alignment: Option<Alignment>) {
+        self.alignment = alignment;
+    }
+
+    pub fn get_age(&self) -> Option<u16> {
+        self.age
+    }
+
+    pub fn set_age(&mut self, age: Option<u16>) {
+        self.age = age;
+    }
+
+    pub fn get_appearance(&self) -> &str {
+        &self.appearance
+    }
+
+    pub fn set_appearance(&mut self, appearance: String) {
+        self.appearance = appearance;
+    }
+
+    pub fn get_personality_traits(&self) -> &[String] {
+        &self.personality_traits
+    }
+
+    pub fn set_personality_traits(&mut self, personality_traits: Vec<String>) {
+        self.personality_traits = personality_traits;
+    }
+
+    pub fn get_ideals(&self) -> &[String] {
+        &self.ideals
+    }
+
+    pub fn set_ideals(&mut self, ideals: Vec<String>) {
+        self.ideals = ideals;
+    }
+
+    pub fn get_bonds(&self) -> &[String] {
+        &self.bonds
+    }
+
+    pub fn set_bonds(&mut self, bonds: Vec<String>) {
+        self.bonds = bonds;
+    }
+
+    pub fn get_flaws(&self) -> &[String] {
+        &self.flaws
+    }
+
+    pub fn set_flaws(&mut self, flaws: Vec<String>) {
+        self.flaws = flaws;
+    }
+
+    pub fn get_backstory(&self) -> &str {
+        &self.backstory
+    }
+
+    pub fn set_backstory(&mut self, backstory: String) {
+        self.backstory = backstory;
+    }
+
+    pub fn get_portrait(&self) -> Option<&str> {
+        self.portrait.as_deref()
+    }
+
+    pub fn set_portrait(&mut self, portrait: Option<String>) {
+        self.portrait = portrait;
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn getters_and_setters() {
+        let mut b = Biography::new();
+        assert_eq!(b.get_alignment(), None);
+        assert_eq!(b.get_portrait(), None);
+
+        b.set_alignment(Some(Alignment::ChaoticGood));
+        b.set_age(Some(112));
+        b.set_appearance(String::from("Tall, with a crooked smile"));
+        b.set_personality_traits(vec![String::from("I quote scripture at every opportunity")]);
+        b.set_ideals(vec![String::from("Charity")]);
+        b.set_bonds(vec![String::from("My old temple")]);
+        b.set_flaws(vec![String::from("I trust too easily")]);
+        b.set_backstory(String::from("Raised by monks."));
+        b.set_portrait(Some(String::from("assets/images/portraits/nick.webp")));
+
+        assert_eq!(b.get_alignment(), Some(Alignment::ChaoticGood));
+        assert_eq!(b.get_age(), Some(112));
+        assert_eq!(b.get_appearance(), "Tall, with a crooked smile");
+        assert_eq!(b.get_personality_traits().len(), 1);
+        assert_eq!(b.get_ideals(), [String::from("Charity")]);
+        assert_eq!(b.get_bonds(), [String::from("My old temple")]);
+        assert_eq!(b.get_flaws(), [String::from("I trust too easily")]);
+        assert_eq!(b.get_backstory(), "Raised by monks.");
+        assert_eq!(b.get_portrait(), Some("assets/images/portraits/nick.webp"));
+        assert_eq!(format!("{}", b.get_alignment().unwrap()), "Chaotic Good");
+    }
+
+    #[test]
+    pub fn serde() {
+        let data = "
+alignment: LawfulEvil
+ideals: [Power]
+";
+        let b: Biography = serde_yaml::from_str(data).unwrap();
+        assert_eq!(b.get_alignment(), Some(Alignment::LawfulEvil));
+        assert_eq!(b.get_ideals(), [String::from("Power")]);
+        assert_eq!(b.get_backstory(), "");
+
+        let de: Biography = serde_yaml::from_str(&serde_yaml::to_string(&b).unwrap()).unwrap();
+        assert_eq!(de, b);
+    }
+}
@@ -3,12 +3,22 @@ use uuid::Uuid;
 
 use crate::{assets::asset::Asset, mechanics::dice::Dice};
 
+use super::{modifier::Modifier, proficiencies::Proficiencies};
+
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Class {
     #[serde(with = "uuid::serde::simple")]
     uuid: Uuid,
+    #[serde(skip)]
+    campaign: Option<u128>,
+    #[serde(default)]
+    overrides: Option<u128>,
     name: String, 
-    hit_die: Dice
+    hit_die: Dice,
+    #[serde(default)]
+    modifiers: Vec<Modifier>,
+    #[serde(default)]
+    proficiencies: Proficiencies,
 }
 
 impl Class {
@@ -16,8 +26,12 @@ impl Class {
     pub fn new(name: String, hit_die: Dice) -> Self {
         Self {
             uuid: Uuid::now_v7(),
+            campaign: None,
+            overrides: None,
             name, 
-            hit_die
+            hit_die,
+            modifiers: Vec::new(),
+            proficiencies: Proficiencies::new(),
         }
     }
 
@@ -28,6 +42,24 @@ impl Class {
     pub fn get_hit_die(&self) -> Dice {
         self.hit_die
     }
+
+    pub fn with_modifiers(mut self, modifiers: Vec<Modifier>) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub fn get_modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    pub fn with_proficiencies(mut self, proficiencies: Proficiencies) -> Self {
+        self.proficiencies = proficiencies;
+        self
+    }
+
+    pub fn get_proficiencies(&self) -> &Proficiencies {
+        &self.proficiencies
+    }
 }
 
 impl Asset for Class {
@@ -36,7 +68,15 @@ impl Asset for Class {
     }
     
     fn get_owning_campaign(&self) -> Option<u128> {
-        todo!()
+        self.campaign
+    }
+
+    fn set_owning_campaign(&mut self, campaign: Option<u128>) {
+        self.campaign = campaign;
+    }
+
+    fn get_overridden_asset(&self) -> Option<u128> {
+        self.overrides
     }
 }
 
@@ -44,14 +84,20 @@ impl Default for Class {
     fn default() -> Self {
         Self { 
             uuid: Uuid::nil(), 
+            campaign: None,
+            overrides: None,
             name: String::new(),
-            hit_die: Dice::D4 
+            hit_die: Dice::D4,
+            modifiers: Vec::new(),
+            proficiencies: Proficiencies::new(),
         }
     }
 }
 
 #[cfg(test)]
 pub mod tests {
+    use crate::entities::{modifier::{ModifierKind, Stat}, proficiencies::WeaponCategory};
+
     use super::*;
 
     #[test]
@@ -60,5 +106,23 @@ pub mod tests {
         
         assert_eq!(String::from("Nick Mason"), class.get_name());
         assert_eq!(Dice::D8, class.get_hit_die());
+        assert!(class.get_modifiers().is_empty());
+    }
+
+    #[test]
+    pub fn modifiers() {
+        let m = Modifier::new(Stat::ArmorClass, ModifierKind::Flat(1));
+        let class = Class::new(String::from("Fighter"), Dice::D10).with_modifiers(vec![m]);
+
+        assert_eq!(class.get_modifiers(), [m]);
+    }
+
+    #[test]
+    pub fn proficiencies() {
+        let mut p = Proficiencies::new();
+        p.add_weapon(WeaponCategory::Martial);
+        let class = Class::new(String::from("Fighter"), Dice::D10).with_proficiencies(p.clone());
+
+        assert_eq!(*class.get_proficiencies(), p);
     }
 }
\ No newline at end of file
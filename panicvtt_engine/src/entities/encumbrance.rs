@@ -0,0 +1,65 @@
+use serde::Serialize;
+
+use super::{abilities::AbilityScoreIntType, size::Size};
+
+/// Variant encumbrance tiers, from the weight carried relative to Strength.
+#[derive(Clone, Copy, Serialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Encumbrance {
+    Unencumbered,
+    /// Carrying more than 5 x STR lb.
+    Encumbered,
+    /// Carrying more than 10 x STR lb.
+    HeavilyEncumbered,
+    /// Carrying more than the carrying capacity of 15 x STR lb.
+    OverCapacity,
+}
+
+impl Encumbrance {
+    /// Pounds a creature can carry: 15 x STR, scaled by size.
+    pub fn get_carrying_capacity(strength: AbilityScoreIntType, size: Size) -> f32 {
+        strength as f32 * 15.0 * size.get_carrying_multiplier()
+    }
+
+    pub fn from_load(load: f32, strength: AbilityScoreIntType, size: Size) -> Self {
+        let per_strength = strength as f32 * size.get_carrying_multiplier();
+        if load > per_strength * 15.0 {
+            Encumbrance::OverCapacity
+        } else if load > per_strength * 10.0 {
+            Encumbrance::HeavilyEncumbered
+        } else if load > per_strength * 5.0 {
+            Encumbrance::Encumbered
+        } else {
+            Encumbrance::Unencumbered
+        }
+    }
+
+    /// Feet of speed lost. Going over capacity is treated as heavily encumbered.
+    pub fn get_speed_penalty(&self) -> u8 {
+        match self {
+            Encumbrance::Unencumbered => 0,
+            Encumbrance::Encumbered => 10,
+            Encumbrance::HeavilyEncumbered | Encumbrance::OverCapacity => 20,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn tiers() {
+        assert_eq!(Encumbrance::get_carrying_capacity(10, Size::Medium), 150.0);
+        assert_eq!(Encumbrance::get_carrying_capacity(10, Size::Tiny), 75.0);
+        assert_eq!(Encumbrance::get_carrying_capacity(10, Size::Large), 300.0);
+
+        assert_eq!(Encumbrance::from_load(50.0, 10, Size::Medium), Encumbrance::Unencumbered);
+        assert_eq!(Encumbrance::from_load(50.5, 10, Size::Medium), Encumbrance::Encumbered);
+        assert_eq!(Encumbrance::from_load(101.0, 10, Size::Medium), Encumbrance::HeavilyEncumbered);
+        assert_eq!(Encumbrance::from_load(151.0, 10, Size::Medium), Encumbrance::OverCapacity);
+        assert_eq!(Encumbrance::from_load(151.0, 10, Size::Huge), Encumbrance::Unencumbered);
+
+        assert_eq!(Encumbrance::Encumbered.get_speed_penalty(), 10);
+        assert_eq!(Encumbrance::OverCapacity.get_speed_penalty(), 20);
+    }
+}
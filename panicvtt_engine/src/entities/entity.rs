@@ -1,17 +1,22 @@
-use std::{error::Error, fmt::Display};
+use std::{collections::{BTreeMap, HashMap}, error::Error, fmt::Display};
 
 use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use uuid::Uuid;
 
 use crate::{assets::{asset::Asset, asset_manager::AssetManager}, mechanics::dice::Rng, util::enum_map::EnumMap};
 
-use super::{abilities::{Ability, AbilityScoreIntType, AbilityScores, SaveAttributes, SaveIntType}, class::Class, race::Race, skills::{Skill, SkillAttributes, SkillModifierIntType}};
+use super::{abilities::{Ability, AbilityScoreIntType, AbilityScores, SaveAttributes}, background::Background, biography::Biography, class::Class, encumbrance::Encumbrance, feat::FeatEffect, improvement::{AbilityScoreImprovement, ImprovementRecord, ASI_ABILITY_CAP, ASI_LEVELS}, item::Item, modifier::{AppliedModifier, Modifier, ModifierKind, ModifierSource, Stat, StatBreakdown, StatIntType}, monster::{ChallengeRating, HitPointMode, Monster}, proficiencies::{ArmorCategory, Proficiencies, WeaponCategory}, purse::{Coin, Purse}, race::Race, senses::Sense, size::Size, skills::{deserialize_skill_attributes, Skill, SkillAttributes, SkillModifierIntType}};
 
 /// An Entity is an agent within the engine that is able to be unique identified and interacted with. 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Entity {
     #[serde(with = "uuid::serde::simple")]
     uuid: Uuid,
+    #[serde(skip)]
+    campaign: Option<u128>,
+    #[serde(default)]
+    overrides: Option<u128>,
     name: String,
 
     hp: u16,
@@ -24,8 +29,40 @@ pub struct Entity {
     race: u128,
 
     abilities: AbilityScores,
-    skills: EnumMap<Skill, SkillAttributes>,
+    /// Skills without an entry are Normal. 
+    #[serde(deserialize_with = "deserialize_skill_attributes")]
+    skills: BTreeMap<Skill, SkillAttributes>,
     saves: EnumMap<Ability, SaveAttributes>,
+
+    #[serde(default)]
+    pending_improvements: u8,
+    #[serde(default)]
+    improvements: Vec<ImprovementRecord>,
+    #[serde(default)]
+    resources: HashMap<String, u8>,
+    #[serde(default)]
+    modifiers: Vec<AppliedModifier>,
+    #[serde(default)]
+    proficiencies: Proficiencies,
+
+    #[serde(default)]
+    background: Option<u128>,
+    #[serde(default)]
+    equipment: Vec<Item>,
+    #[serde(default)]
+    purse: Purse,
+
+    #[serde(default)]
+    size: Size,
+    #[serde(default)]
+    biography: Biography,
+
+    /// The stat block this Entity was instantiated from, if it is a monster. 
+    #[serde(default)]
+    monster: Option<u128>,
+    /// Monsters take their proficiency bonus from their challenge rating rather than their level. 
+    #[serde(default)]
+    challenge_rating: Option<ChallengeRating>,
 }
 
 impl Entity {
@@ -34,6 +71,8 @@ impl Entity {
         // Start with HP and level at 0, then level up once to not repeat leveling code 
         let mut s = Self {
             uuid: Uuid::now_v7(),
+            campaign: None,
+            overrides: None,
             name,
             hp: 0, 
             hp_max: 0, 
@@ -42,8 +81,20 @@ impl Entity {
             race, 
             class, 
             abilities, 
-            skills: EnumMap::from_fn(|_| SkillAttributes::Normal),
-            saves: EnumMap::from_fn(|_| SaveAttributes::Normal)
+            skills: BTreeMap::new(),
+            saves: EnumMap::from_value(SaveAttributes::Normal),
+            pending_improvements: 0,
+            improvements: Vec::new(),
+            resources: HashMap::new(),
+            modifiers: Vec::new(),
+            proficiencies: Proficiencies::new(),
+            background: None,
+            equipment: Vec::new(),
+            purse: Purse::new(),
+            size: Size::default(),
+            biography: Biography::new(),
+            monster: None,
+            challenge_rating: None,
         }; 
 
         // Leveling up the first time cannot fail - if it does, we have an engine problem
@@ -51,6 +102,45 @@ impl Entity {
         s
     }
 
+    /// Create an instance of a monster's stat block, using the default class and race. 
+    pub fn from_monster(monster: &Monster, hp_mode: HitPointMode, rng: &mut Rng) -> Self {
+        let hp = monster.roll_hit_points(hp_mode, rng);
+        let mut saves = EnumMap::from_value(SaveAttributes::Normal);
+        for ability in monster.get_saves() {
+            saves[*ability] = SaveAttributes::Proficient;
+        }
+        let mut biography = Biography::new();
+        biography.set_alignment(monster.get_alignment());
+
+        Self {
+            uuid: Uuid::now_v7(),
+            campaign: None,
+            overrides: None,
+            name: String::from(monster.get_name()),
+            hp,
+            hp_max: hp,
+            hp_temp: 0,
+            level: 0,
+            class: AssetManager::DEFAULT_CLASS_UUID,
+            race: AssetManager::DEFAULT_RACE_UUID,
+            abilities: monster.get_abilities().clone(),
+            skills: monster.get_skills().clone(),
+            saves,
+            pending_improvements: 0,
+            improvements: Vec::new(),
+            resources: HashMap::new(),
+            modifiers: Vec::new(),
+            proficiencies: Proficiencies::new(),
+            background: None,
+            equipment: Vec::new(),
+            purse: Purse::new(),
+            size: monster.get_size(),
+            biography,
+            monster: Some(monster.get_uuid()),
+            challenge_rating: Some(monster.get_challenge_rating()),
+        }
+    }
+
 
     pub fn get_name(&self) -> &str {
         &self.name
@@ -72,6 +162,23 @@ impl Entity {
         self.hp_temp
     }
 
+    /// Regain hit points, up to the maximum. Returns the hit points actually regained. 
+    pub fn heal(&mut self, amount: u16) -> u16 {
+        let healed = amount.min(self.hp_max.saturating_sub(self.hp));
+        self.hp += healed;
+        healed
+    }
+
+    /// Lose hit points, spending temporary hit points first. Returns the hit points actually lost, not counting temporary ones. 
+    pub fn take_damage(&mut self, amount: u16) -> u16 {
+        let absorbed = amount.min(self.hp_temp);
+        self.hp_temp -= absorbed;
+
+        let lost = (amount - absorbed).min(self.hp);
+        self.hp -= lost;
+        lost
+    }
+
     pub fn get_level(&self) -> u8 {
         self.level
     }
@@ -80,12 +187,107 @@ impl Entity {
         assets.get_class(self.class)
     }
 
+    pub fn get_class_uuid(&self) -> u128 {
+        self.class
+    }
+
+    pub fn get_race_uuid(&self) -> u128 {
+        self.race
+    }
+
     pub fn get_race<'e>(&'e self, assets: &'e AssetManager) -> Option<&'e Race> {
         assets.get_race(self.race)
     }
 
     pub fn get_speed(&self, assets: &AssetManager) -> Option<u8> {
-        Some(assets.get_race(self.race)?.get_speed())
+        Some(self.get_stat(Stat::Speed, assets)?.clamp(0, u8::MAX as StatIntType) as u8)
+    }
+
+    pub fn get_armor_class(&self, assets: &AssetManager) -> Option<StatIntType> {
+        self.get_stat(Stat::ArmorClass, assets)
+    }
+
+    pub fn get_initiative_bonus(&self, assets: &AssetManager) -> Option<StatIntType> {
+        self.get_stat(Stat::Initiative, assets)
+    }
+
+    /// Passive score = 10 + skill score, +5 with advantage or -5 with disadvantage on the skill. 
+    pub fn get_passive_score(&self, skill: Skill, assets: &AssetManager) -> Option<StatIntType> {
+        Some(self.explain_stat(Stat::Skill(skill), assets)?.get_passive())
+    }
+
+    pub fn get_passive_perception(&self, assets: &AssetManager) -> Option<StatIntType> {
+        self.get_passive_score(Skill::PERCEPTION, assets)
+    }
+
+    pub fn get_passive_investigation(&self, assets: &AssetManager) -> Option<StatIntType> {
+        self.get_passive_score(Skill::INVESTIGATION, assets)
+    }
+
+    pub fn get_passive_insight(&self, assets: &AssetManager) -> Option<StatIntType> {
+        self.get_passive_score(Skill::INSIGHT, assets)
+    }
+
+    /// Proficiencies held by this Entity itself, not counting its race or class. 
+    pub fn get_own_proficiencies(&self) -> &Proficiencies {
+        &self.proficiencies
+    }
+
+    /// Every tool, language, weapon and armor proficiency from this Entity, its race and its class. 
+    /// 
+    /// Returns None if this Entity's race or class cannot be found. 
+    pub fn get_proficiencies(&self, assets: &AssetManager) -> Option<Proficiencies> {
+        let mut proficiencies = assets.get_race(self.race)?.get_proficiencies().clone();
+        proficiencies.merge(assets.get_class(self.class)?.get_proficiencies());
+        proficiencies.merge(&self.proficiencies);
+        Some(proficiencies)
+    }
+
+    pub fn set_tool_attribute(&mut self, tool: String, attribute: SkillAttributes) -> SkillAttributes {
+        self.proficiencies.set_tool_attribute(tool, attribute)
+    }
+
+    pub fn add_language(&mut self, language: String) -> bool {
+        self.proficiencies.add_language(language)
+    }
+
+    pub fn add_weapon_proficiency(&mut self, weapon: WeaponCategory) -> bool {
+        self.proficiencies.add_weapon(weapon)
+    }
+
+    pub fn add_armor_proficiency(&mut self, armor: ArmorCategory) -> bool {
+        self.proficiencies.add_armor(armor)
+    }
+
+    /// Tool check = ability modifier + (tool attribute offset * proficiency). 
+    pub fn get_tool_check(&self, tool: &str, ability: Ability, assets: &AssetManager) -> Option<StatIntType> {
+        let attribute = self.get_proficiencies(assets)?.get_tool_attribute(tool);
+        let modifiers = self.collect_modifiers(assets)?;
+
+        Some(self.compute_ability_modifier(ability, &modifiers, assets) + self.get_proficiency_offset(attribute) as StatIntType)
+    }
+
+    /// Attack bonus = ability modifier + proficiency if proficient with the weapon's category. 
+    pub fn get_attack_bonus(&self, weapon: WeaponCategory, ability: Ability, assets: &AssetManager) -> Option<StatIntType> {
+        let proficient = self.get_proficiencies(assets)?.is_proficient_with_weapon(weapon);
+        let modifiers = self.collect_modifiers(assets)?;
+        let prof_offset = if proficient { self.get_proficiency_bonus() as StatIntType } else { 0 };
+
+        Some(self.compute_ability_modifier(ability, &modifiers, assets) + prof_offset)
+    }
+
+    /// The range in feet of each Sense this Entity has. Senses with no range are omitted. 
+    pub fn get_senses(&self, assets: &AssetManager) -> Option<Vec<(Sense, u16)>> {
+        let modifiers = self.collect_modifiers(assets)?;
+        Some(Sense::iter()
+            .map(|sense| (sense, self.compute_stat(Stat::Sense(sense), &modifiers, assets).get_total()))
+            .filter(|(_, range)| *range > 0)
+            .map(|(sense, range)| (sense, range as u16))
+            .collect())
+    }
+
+    pub fn get_sense_range(&self, sense: Sense, assets: &AssetManager) -> Option<u16> {
+        Some(self.get_stat(Stat::Sense(sense), assets)?.max(0) as u16)
     }
 
     pub fn get_class_name<'a>(&'a self, assets: &'a AssetManager) -> Option<&'a str> {
@@ -108,40 +310,174 @@ impl Entity {
         self.abilities.get_ability_modifier(ability)
     }
 
-    pub fn get_skill_score(&self, skill: Skill) -> SkillModifierIntType {
-        // Skill = ability[skill.ability] + (attribute.offset * proficiency)
-        let attr = &self.skills[skill];
-        let prof_multiplier = attr.get_proficiency_modifier();
-        let prof_offset = (prof_multiplier * (self.get_proficiency_bonus() as f64)).floor() as u8; 
-        
-        let ability_modifier = self.get_ability_modifier(skill.get_ability());
+    /// The skill score with every modifier applied; see `explain_stat` for where it comes from. 
+    /// 
+    /// Returns None if the skill is not defined in `assets`, or this Entity's race or class cannot be found. 
+    pub fn get_skill_score(&self, skill: Skill, assets: &AssetManager) -> Option<StatIntType> {
+        self.get_stat(Stat::Skill(skill), assets)
+    }
 
-        ability_modifier.checked_add(prof_offset as i8).unwrap()
+    /// The save score with every modifier applied; see `explain_stat` for where it comes from. 
+    /// 
+    /// Returns None if this Entity's race or class cannot be found. 
+    pub fn get_save_score(&self, ability: Ability, assets: &AssetManager) -> Option<StatIntType> {
+        self.get_stat(Stat::Save(ability), assets)
     }
 
-    pub fn get_save_score(&self, ability: Ability) -> SaveIntType {
-        // Save = ability[skill.ability] + proficiency
-        let ab_offset = self.get_ability_modifier(ability);
-        let proficiency = self.saves[ability] as SaveIntType;
-        let prof_offset = proficiency * self.get_proficiency_bonus() as SaveIntType;
+    pub fn get_skill_attribute(&self, skill: Skill) -> SkillAttributes {
+        self.skills.get(&skill).copied().unwrap_or(SkillAttributes::Normal)
+    }
 
-        ab_offset + prof_offset
+    /// The attribute recorded for each skill; skills missing from it are Normal. 
+    pub fn get_skills(&self) -> &BTreeMap<Skill, SkillAttributes> {
+        &self.skills
     }
 
-    pub fn get_skill_scores(&self) -> EnumMap<Skill, SkillModifierIntType> {
-        EnumMap::from_fn(|s| self.get_skill_score(s))
+    pub fn get_save_attribute(&self, ability: Ability) -> SaveAttributes {
+        self.saves[ability]
+    }
+
+    /// Scores for every skill defined in `assets`, including campaign homebrew. 
+    /// 
+    /// Fails if this Entity has an attribute for a skill `assets` doesn't define, or its race or class cannot be found. 
+    pub fn get_skill_scores(&self, assets: &AssetManager) -> Result<BTreeMap<Skill, StatIntType>, EntityError> {
+        if let Some(skill) = self.skills.keys().find(|s| assets.get_skill(**s).is_none()) {
+            return Err(EntityError::from_kind(EntityErrorKind::SkillNotFound { skill: *skill }));
+        }
+        let Some(modifiers) = self.collect_modifiers(assets) else {
+            let kind = match assets.get_class(self.class) {
+                None => EntityErrorKind::ClassNotFound { class: self.class },
+                Some(_) => EntityErrorKind::RaceNotFound { race: self.race },
+            };
+            return Err(EntityError::from_kind(kind));
+        };
+
+        Ok(assets.get_skills().iter()
+            .map(|d| (d.get_skill(), self.compute_stat(Stat::Skill(d.get_skill()), &modifiers, assets).get_total()))
+            .collect())
     }
 
     pub fn get_proficiency_bonus(&self) -> u8 {
-        ((self.level - 1) / 4) + 2
+        match self.challenge_rating {
+            Some(cr) => cr.get_proficiency_bonus(),
+            None => ((self.level - 1) / 4) + 2,
+        }
+    }
+
+    fn get_proficiency_offset(&self, attribute: SkillAttributes) -> u8 {
+        (attribute.get_proficiency_modifier() * (self.get_proficiency_bonus() as f64)).floor() as u8
+    }
+
+    pub fn get_modifiers(&self) -> &[AppliedModifier] {
+        &self.modifiers
+    }
+
+    pub fn add_modifier(&mut self, source: ModifierSource, modifier: Modifier) {
+        self.modifiers.push(AppliedModifier::new(source, modifier));
+    }
+
+    /// Remove every modifier granted by `source`, e.g. when an item is unequipped or a condition ends. 
+    /// 
+    /// Returns the number of modifiers removed. 
+    pub fn remove_modifiers_from(&mut self, source: &ModifierSource) -> usize {
+        let before = self.modifiers.len();
+        self.modifiers.retain(|m| m.get_source() != source);
+        before - self.modifiers.len()
+    }
+
+    /// Compute a Stat along with every modifier contributing to it from this Entity's race, class, feats, items, conditions and spells. 
+    /// 
+    /// Returns None if this Entity's race or class, or the skill being explained, cannot be found. 
+    pub fn explain_stat(&self, stat: Stat, assets: &AssetManager) -> Option<StatBreakdown> {
+        if let Stat::Skill(skill) = stat {
+            assets.get_skill(skill)?;
+        }
+
+        let modifiers = self.collect_modifiers(assets)?;
+        Some(self.compute_stat(stat, &modifiers, assets))
+    }
+
+    pub fn get_stat(&self, stat: Stat, assets: &AssetManager) -> Option<StatIntType> {
+        Some(self.explain_stat(stat, assets)?.get_total())
+    }
+
+    fn collect_modifiers(&self, assets: &AssetManager) -> Option<Vec<AppliedModifier>> {
+        let race = assets.get_race(self.race)?;
+        let class = assets.get_class(self.class)?;
+
+        let race_source = ModifierSource::Race { race: self.race };
+        let class_source = ModifierSource::Class { class: self.class };
+
+        Some(race.get_modifiers().iter().map(|m| AppliedModifier::new(race_source.clone(), *m))
+            .chain(class.get_modifiers().iter().map(|m| AppliedModifier::new(class_source.clone(), *m)))
+            .chain(self.modifiers.iter().cloned())
+            .collect())
+    }
+
+    fn compute_stat(&self, stat: Stat, modifiers: &[AppliedModifier], assets: &AssetManager) -> StatBreakdown {
+        let relevant: Vec<&AppliedModifier> = modifiers.iter()
+            .filter(|m| m.get_modifier().get_stat() == stat)
+            .collect();
+
+        // Proficiency grants raise the proficiency used for the base value rather than stacking on top of it 
+        let granted = relevant.iter().filter_map(|m| match m.get_modifier().get_kind() {
+            ModifierKind::Proficiency(attribute) => Some(attribute), 
+            _ => None
+        }).max().unwrap_or(SkillAttributes::Normal);
+
+        let base = match stat {
+            Stat::Ability(ability) => self.get_ability_score(ability) as StatIntType,
+            Stat::Skill(skill) => {
+                let attribute = self.get_skill_attribute(skill).max(granted);
+                let ability_modifier = assets.get_skill(skill)
+                    .map_or(0, |d| self.compute_ability_modifier(d.get_ability(), modifiers, assets));
+                ability_modifier + self.get_proficiency_offset(attribute) as StatIntType
+            },
+            Stat::Save(ability) => {
+                let proficient = self.saves[ability] == SaveAttributes::Proficient || granted != SkillAttributes::Normal;
+                let prof_offset = if proficient { self.get_proficiency_bonus() as StatIntType } else { 0 };
+                self.compute_ability_modifier(ability, modifiers, assets) + prof_offset
+            },
+            Stat::Speed => match self.get_monster(assets) {
+                Some(monster) => monster.get_speed() as StatIntType,
+                None => assets.get_race(self.race).map_or(0, |r| r.get_speed() as StatIntType),
+            },
+            // A stat block's armor class already accounts for Dexterity and natural armor 
+            Stat::ArmorClass => match self.get_monster(assets) {
+                Some(monster) => monster.get_armor_class() as StatIntType,
+                None => 10 + self.compute_ability_modifier(Ability::Dexterity, modifiers, assets),
+            },
+            Stat::Initiative => self.compute_ability_modifier(Ability::Dexterity, modifiers, assets),
+            Stat::Sense(_) => 0,
+        };
+
+        let mut breakdown = StatBreakdown::new(stat, base);
+        for m in relevant {
+            breakdown.apply(m);
+        }
+
+        // Carrying too much slows an Entity down 
+        if stat == Stat::Speed {
+            let encumbrance = self.compute_encumbrance(modifiers, assets);
+            if encumbrance != Encumbrance::Unencumbered {
+                let penalty = -(encumbrance.get_speed_penalty() as StatIntType);
+                breakdown.apply(&AppliedModifier::new(ModifierSource::Condition { name: format!("{:?}", encumbrance) }, 
+                    Modifier::new(Stat::Speed, ModifierKind::Flat(penalty))));
+            }
+        }
+
+        breakdown
+    }
+
+    fn compute_ability_modifier(&self, ability: Ability, modifiers: &[AppliedModifier], assets: &AssetManager) -> StatIntType {
+        let score = self.compute_stat(Stat::Ability(ability), modifiers, assets).get_total();
+        (score - 10).div_euclid(2)
     }
 
 
     pub fn set_skill_attribute(&mut self, skill: Skill, attribute: SkillAttributes) -> SkillAttributes {
         // Change the attribute for this skill and return the old one 
-        let old_attribute = self.skills[skill];
-        self.skills[skill] = attribute;
-        old_attribute
+        self.skills.insert(skill, attribute).unwrap_or(SkillAttributes::Normal)
     }
 
     pub fn set_save_attribute(&mut self, ability: Ability, attribute: SaveAttributes) -> SaveAttributes {
@@ -151,12 +487,113 @@ impl Entity {
         old_attribute
     }
 
+    pub fn get_pending_improvements(&self) -> u8 {
+        self.pending_improvements
+    }
+
+    pub fn get_improvements(&self) -> &[ImprovementRecord] {
+        &self.improvements
+    }
+
+    pub fn get_resources(&self) -> &HashMap<String, u8> {
+        &self.resources
+    }
+
+    pub fn get_monster_uuid(&self) -> Option<u128> {
+        self.monster
+    }
+
+    pub fn get_monster<'e>(&'e self, assets: &'e AssetManager) -> Option<&'e Monster> {
+        assets.get_monster(self.monster?)
+    }
+
+    pub fn get_challenge_rating(&self) -> Option<ChallengeRating> {
+        self.challenge_rating
+    }
+
+    pub fn get_background_uuid(&self) -> Option<u128> {
+        self.background
+    }
+
+    pub fn get_equipment(&self) -> &[Item] {
+        &self.equipment
+    }
+
+    pub fn add_equipment(&mut self, item: Item) {
+        self.equipment.push(item);
+    }
+
+    pub fn get_purse(&self) -> &Purse {
+        &self.purse
+    }
+
+    pub fn get_purse_mut(&mut self) -> &mut Purse {
+        &mut self.purse
+    }
+
+    /// Pounds carried, counting equipment and coins. 
+    pub fn get_load(&self) -> f32 {
+        self.equipment.iter().map(|i| i.get_total_weight()).sum::<f32>() + self.purse.get_weight()
+    }
+
+    pub fn get_carrying_capacity(&self, assets: &AssetManager) -> Option<f32> {
+        let strength = self.get_stat(Stat::Ability(Ability::Strength), assets)?;
+        Some(Encumbrance::get_carrying_capacity(strength.clamp(0, AbilityScoreIntType::MAX as StatIntType) as AbilityScoreIntType, self.size))
+    }
+
+    pub fn get_encumbrance(&self, assets: &AssetManager) -> Option<Encumbrance> {
+        let modifiers = self.collect_modifiers(assets)?;
+        Some(self.compute_encumbrance(&modifiers, assets))
+    }
+
+    fn compute_encumbrance(&self, modifiers: &[AppliedModifier], assets: &AssetManager) -> Encumbrance {
+        let strength = self.compute_stat(Stat::Ability(Ability::Strength), modifiers, assets).get_total();
+        Encumbrance::from_load(self.get_load(), strength.clamp(0, AbilityScoreIntType::MAX as StatIntType) as AbilityScoreIntType, self.size)
+    }
+
+    pub fn get_size(&self) -> Size {
+        self.size
+    }
+
+    pub fn set_size(&mut self, size: Size) -> Size {
+        std::mem::replace(&mut self.size, size)
+    }
+
+    pub fn get_biography(&self) -> &Biography {
+        &self.biography
+    }
+
+    pub fn get_biography_mut(&mut self) -> &mut Biography {
+        &mut self.biography
+    }
+
+    /// Grant a background's skills, proficiencies, equipment and gold. Skills already known at a higher level are kept. 
+    pub fn apply_background(&mut self, background: &Background) {
+        self.background = Some(background.get_uuid());
+
+        for skill in background.get_skills() {
+            let attribute = self.get_skill_attribute(*skill).max(SkillAttributes::Proficient);
+            self.skills.insert(*skill, attribute);
+        }
+
+        self.proficiencies.merge(background.get_proficiencies());
+        self.equipment.extend(background.get_equipment().iter().cloned());
+        self.purse.add(Coin::Gold, background.get_gold());
+    }
+
     pub fn level_up(&mut self, assets: &AssetManager, rng: &mut Rng) -> Result<u16, EntityError> {
         // !TODO this will eventually be much more involved 
+        // Nothing changes if the class is missing 
+        let Some(class) = assets.get_class(self.class) else { 
+            return Err(EntityError::from_kind(EntityErrorKind::ClassNotFound { class: self.class })); 
+        };
         self.level += 1; 
 
+        if ASI_LEVELS.contains(&self.level) {
+            self.pending_improvements += 1;
+        }
+
         // HP can reduce! Mind the signed bounds here 
-        let Some(class) = assets.get_class(self.class) else { return Err(EntityError::new()); };
         let roll = class.get_hit_die().roll(rng) as u16;
         let con = self.abilities.get_ability_modifier(Ability::Constitution); 
 
@@ -167,6 +604,108 @@ impl Entity {
 
         Ok(roll)
     }
+
+    /// Spend a pending Ability Score Improvement on either ability score increases or a Feat. 
+    pub fn apply_improvement(&mut self, improvement: AbilityScoreImprovement, assets: &AssetManager) -> Result<(), EntityError> {
+        if self.pending_improvements == 0 {
+            return Err(EntityError::from_kind(EntityErrorKind::NoImprovementAvailable));
+        }
+
+        let mut record = ImprovementRecord::new(self.level, improvement.clone());
+        match improvement {
+            AbilityScoreImprovement::PlusTwo { ability } => {
+                let applied = self.abilities.increase_ability_score(ability, 2, ASI_ABILITY_CAP);
+                record.record_ability_increase(ability, applied);
+            },
+            AbilityScoreImprovement::PlusOnePlusOne { first, second } => {
+                // +1/+1 must be split across two abilities; +2 to one ability is its own choice
+                if first == second {
+                    return Err(EntityError::from_kind(EntityErrorKind::InvalidImprovement));
+                }
+
+                let applied = self.abilities.increase_ability_score(first, 1, ASI_ABILITY_CAP);
+                record.record_ability_increase(first, applied);
+                let applied = self.abilities.increase_ability_score(second, 1, ASI_ABILITY_CAP);
+                record.record_ability_increase(second, applied);
+            },
+            AbilityScoreImprovement::Feat { feat } => {
+                let Some(feat) = assets.get_feat(feat) else {
+                    return Err(EntityError::from_kind(EntityErrorKind::FeatNotFound { feat }));
+                };
+
+                if let Some(prerequisite) = feat.get_unmet_prerequisite(self) {
+                    return Err(EntityError::from_kind(EntityErrorKind::PrerequisiteNotMet { prerequisite: format!("{:?}", prerequisite) }));
+                }
+
+                let source = ModifierSource::Feat { feat: feat.get_uuid() };
+                for effect in feat.get_effects() {
+                    self.apply_feat_effect(effect, &source, &mut record);
+                }
+            },
+        }
+
+        self.pending_improvements -= 1;
+        self.improvements.push(record);
+        Ok(())
+    }
+
+    /// Undo every Ability Score Improvement this Entity has taken, making each available to choose again. 
+    /// 
+    /// Returns the number of Ability Score Improvements now pending. 
+    pub fn respec_improvements(&mut self) -> u8 {
+        // Undo in reverse so that overlapping changes unwind to their original values 
+        while let Some(record) = self.improvements.pop() {
+            for (ability, applied) in record.get_ability_increases() {
+                self.abilities.decrease_ability_score(*ability, *applied);
+            }
+            for (skill, previous) in record.get_replaced_skills() {
+                self.skills.insert(*skill, *previous);
+            }
+            for (ability, previous) in record.get_replaced_saves() {
+                self.saves[*ability] = *previous;
+            }
+            for (name, uses) in record.get_granted_resources() {
+                let remaining = self.resources.get(name).copied().unwrap_or_default().saturating_sub(*uses);
+                if remaining == 0 {
+                    self.resources.remove(name);
+                } else {
+                    self.resources.insert(name.clone(), remaining);
+                }
+            }
+            if let AbilityScoreImprovement::Feat { feat } = record.get_improvement() {
+                self.remove_modifiers_from(&ModifierSource::Feat { feat: *feat });
+            }
+
+            self.pending_improvements += 1;
+        }
+
+        self.pending_improvements
+    }
+
+    fn apply_feat_effect(&mut self, effect: &FeatEffect, source: &ModifierSource, record: &mut ImprovementRecord) {
+        match effect {
+            FeatEffect::AbilityIncrease { ability, amount } => {
+                let applied = self.abilities.increase_ability_score(*ability, *amount, ASI_ABILITY_CAP);
+                record.record_ability_increase(*ability, applied);
+            },
+            FeatEffect::SkillProficiency { skill, attribute } => {
+                let previous = self.set_skill_attribute(*skill, *attribute);
+                record.record_skill_replacement(*skill, previous);
+            },
+            FeatEffect::SaveProficiency { ability } => {
+                let previous = self.set_save_attribute(*ability, SaveAttributes::Proficient);
+                record.record_save_replacement(*ability, previous);
+            },
+            FeatEffect::Resource { name, uses } => {
+                *self.resources.entry(name.clone()).or_default() += uses;
+                record.record_resource(name.clone(), *uses);
+            },
+            FeatEffect::Modifier { modifier } => {
+                // Feat modifiers are removed by source when the improvement is undone 
+                self.add_modifier(source.clone(), *modifier);
+            },
+        }
+    }
 }
 
 impl Asset for Entity {
@@ -175,7 +714,15 @@ impl Asset for Entity {
     }
     
     fn get_owning_campaign(&self) -> Option<u128> {
-        todo!()
+        self.campaign
+    }
+
+    fn set_owning_campaign(&mut self, campaign: Option<u128>) {
+        self.campaign = campaign;
+    }
+
+    fn get_overridden_asset(&self) -> Option<u128> {
+        self.overrides
     }
 }
 
@@ -186,11 +733,47 @@ impl Display for Entity {
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum EntityErrorKind {
+    ClassNotFound { class: u128 },
+    RaceNotFound { race: u128 },
+    SkillNotFound { skill: Skill },
+    FeatNotFound { feat: u128 },
+    PrerequisiteNotMet { prerequisite: String },
+    NoImprovementAvailable,
+    InvalidImprovement,
+}
+
+impl Display for EntityErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntityErrorKind::ClassNotFound { class } => write!(f, "No class with UUID {:x}", class),
+            EntityErrorKind::RaceNotFound { race } => write!(f, "No race with UUID {:x}", race),
+            EntityErrorKind::SkillNotFound { skill } => write!(f, "No definition for {}", skill),
+            EntityErrorKind::FeatNotFound { feat } => write!(f, "No feat with UUID {:x}", feat),
+            EntityErrorKind::PrerequisiteNotMet { prerequisite } => write!(f, "Prerequisite not met: {}", prerequisite),
+            EntityErrorKind::NoImprovementAvailable => write!(f, "No Ability Score Improvement is available"),
+            EntityErrorKind::InvalidImprovement => write!(f, "+1/+1 improvements must name two different abilities"),
+        }
+    }
+}
+
 #[derive(Debug)]
-pub struct EntityError {}
+pub struct EntityError {
+    kind: Option<EntityErrorKind>,
+}
+
 impl EntityError {
     pub fn new() -> Self {
-        EntityError {}
+        EntityError { kind: None }
+    }
+
+    pub fn from_kind(kind: EntityErrorKind) -> Self {
+        EntityError { kind: Some(kind) }
+    }
+
+    pub fn get_kind(&self) -> Option<&EntityErrorKind> {
+        self.kind.as_ref()
     }
 }
 
@@ -206,17 +789,19 @@ impl Error for EntityError {
 }
 
 impl Display for EntityError {
-    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            Some(kind) => write!(f, "{}", kind),
+            None => Ok(()),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use rand::rngs::mock::StepRng;
-    use strum::IntoEnumIterator;
 
-    use crate::{assets::asset::Asset, mechanics::dice::Dice};
+    use crate::{assets::asset::Asset, entities::{biography::Alignment, feat::FeatPrerequisite, modifier::ModifierKind}, mechanics::dice::Dice};
 
     use super::*;
 
@@ -280,11 +865,23 @@ mod tests {
         let race = assets.get_testing_race();
         let mut rng = StepRng::new(5, 1);
         let entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), AbilityScores::from_defaults(), &assets, &mut rng);
-        let map = entity.get_skill_scores();
+        let map = entity.get_skill_scores(&assets).unwrap();
+        assert_eq!(map.len(), assets.get_skills().len());
 
         for (skill, score) in map {
-            assert_eq!(entity.get_skill_score(skill), score);
+            assert_eq!(entity.get_skill_score(skill, &assets), Some(score));
         }
+
+        assert_eq!(entity.get_skill_score(Skill::from_uuid(0xdead), &assets), None);
+
+        // Knowing a skill nobody defines is an error, not a missing row 
+        let mut entity = entity;
+        entity.set_skill_attribute(Skill::from_uuid(0xdead), SkillAttributes::Proficient);
+        let e = entity.get_skill_scores(&assets).unwrap_err();
+        assert!(matches!(e.get_kind(), Some(EntityErrorKind::SkillNotFound { skill }) if skill.get_uuid() == 0xdead));
+        entity.skills.clear();
+        entity.race = 55;
+        assert!(matches!(entity.get_skill_scores(&assets).unwrap_err().get_kind(), Some(EntityErrorKind::RaceNotFound { race: 55 })));
     }
 
     #[test]
@@ -296,8 +893,8 @@ mod tests {
         let entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), AbilityScores::from_defaults(), &assets, &mut rng);
         
         // Each score should be 0 - no proficiency or skill bonus
-        for skill in Skill::iter() {
-            assert_eq!(entity.get_skill_score(skill), 0);
+        for skill in Skill::STANDARD {
+            assert_eq!(entity.get_skill_score(skill, &assets), Some(0));
         }
     }
 
@@ -321,8 +918,8 @@ mod tests {
             let entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(),
                 AbilityScores::new(i, i, i, i, i, i), &assets, &mut rng);
                         
-            for skill in Skill::iter() {
-                assert_eq!(entity.get_skill_score(skill), expected_modifiers[i as usize]);
+            for skill in Skill::STANDARD {
+                assert_eq!(entity.get_skill_score(skill, &assets), Some(expected_modifiers[i as usize]));
             }
         }
     }
@@ -354,12 +951,12 @@ mod tests {
 
             // Order: Normal, HalfProficient, Proficient, Expertise 
             for (j, attr) in SkillAttributes::iter().enumerate() {
-                for skill in Skill::iter() {
+                for skill in Skill::STANDARD {
                     entity.set_skill_attribute(skill, attr);
 
                     let bonus = bonuses.get(j).unwrap();
-                    let expected = expected_modifiers[i as usize] + *bonus as i8;
-                    assert_eq!(entity.get_skill_score(skill), expected);
+                    let expected = expected_modifiers[i as usize] + *bonus as StatIntType;
+                    assert_eq!(entity.get_skill_score(skill, &assets), Some(expected));
                 }
             }
         }
@@ -402,7 +999,7 @@ mod tests {
 
         // Each score should be 0 - no proficiency bonus
         for ability in Ability::iter() {
-            assert_eq!(entity.get_save_score(ability), 0);
+            assert_eq!(entity.get_save_score(ability, &assets), Some(0));
         }
     }
 
@@ -428,7 +1025,7 @@ mod tests {
                 AbilityScores::new(i, i, i, i, i, i), &assets, &mut rng);
             
             for ability in Ability::iter() {
-                assert_eq!(entity.get_save_score(ability), expected_modifiers[i as usize]);
+                assert_eq!(entity.get_save_score(ability, &assets), Some(expected_modifiers[i as usize]));
             }
         }
     }
@@ -457,9 +1054,9 @@ mod tests {
                 entity.set_save_attribute(ability, SaveAttributes::Proficient);
             }
 
-            let expected = expected_modifiers[i as usize] + entity.get_proficiency_bonus() as SaveIntType;
+            let expected = expected_modifiers[i as usize] + entity.get_proficiency_bonus() as StatIntType;
             for ability in Ability::iter() {
-                assert_eq!(entity.get_save_score(ability), expected);
+                assert_eq!(entity.get_save_score(ability, &assets), Some(expected));
             }
         }
     }
@@ -492,6 +1089,25 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn level_up_without_class() {
+        let assets = AssetManager::from_test_config();
+        let mut rng = StepRng::new(1, 1);
+        let mut entity = Entity::new(String::new(), assets.get_testing_class().get_uuid(), assets.get_testing_race().get_uuid(), 
+            AbilityScores::new(10, 10, 14, 10, 10, 10), &assets, &mut rng);
+        for _ in 2..4 {
+            entity.level_up(&assets, &mut rng).unwrap();
+        }
+
+        // Failing to reach level 4 grants no Ability Score Improvement 
+        entity.class = 0xdead;
+        let hp = entity.get_hp();
+        assert!(entity.level_up(&assets, &mut rng).is_err());
+        assert_eq!(entity.get_level(), 3);
+        assert_eq!(entity.get_pending_improvements(), 0);
+        assert_eq!(entity.get_hp(), hp);
+    }
+
     #[test]
     pub fn serde() {
         let assets = AssetManager::from_test_config();
@@ -500,7 +1116,8 @@ mod tests {
 
         let mut rng = StepRng::new(0, 0);
         let abilities = AbilityScores::new(0, 5, 10, 15, 20, 25);
-        let entity = Entity::new(String::from("Entity Named Finger:"), class.get_uuid(), race.get_uuid(), abilities, &assets, &mut rng);
+        let mut entity = Entity::new(String::from("Entity Named Finger:"), class.get_uuid(), race.get_uuid(), abilities, &assets, &mut rng);
+        entity.set_skill_attribute(Skill::STEALTH, SkillAttributes::Expertise);
 
         let expected_uuid = entity.uuid;
         let expected_hp = entity.hp;
@@ -509,6 +1126,9 @@ mod tests {
         let data = serde_yaml::to_string(&entity).unwrap();
         let de: Entity = serde_yaml::from_str(&data).unwrap();
         
+        // Standard skills are saved by name so files stay hand-editable 
+        assert!(data.contains("stealth: Expertise"));
+        assert_eq!(de.get_skill_attribute(Skill::STEALTH), SkillAttributes::Expertise);
         assert_eq!(de.uuid, expected_uuid);
         assert_eq!(de.name, String::from("Entity Named Finger:"));
 
@@ -526,7 +1146,7 @@ mod tests {
 
         assert_eq!(de.abilities, AbilityScores::new(0, 5, 10, 15, 20, 25));
         
-        assert_eq!(de.skills, EnumMap::from_value(SkillAttributes::Normal));
+        assert!(de.skills.iter().all(|(s, a)| *s == Skill::STEALTH || *a == SkillAttributes::Normal));
         assert_eq!(de.saves, EnumMap::from_value(SaveAttributes::Normal));
     }
 
@@ -538,5 +1158,369 @@ mod tests {
 
         let f = format!("{}", e2);
         assert_eq!(f, String::new()); 
+
+        let e = EntityError::from_kind(EntityErrorKind::NoImprovementAvailable);
+        assert_eq!(e.get_kind(), Some(&EntityErrorKind::NoImprovementAvailable));
+        assert_eq!(format!("{}", e), "No Ability Score Improvement is available");
+    }
+
+    #[test]
+    pub fn improvements_offered_at_asi_levels() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), AbilityScores::from_defaults(), &assets, &mut rng);
+
+        let mut expected = 0;
+        for level in 2..=20 {
+            entity.level_up(&assets, &mut rng).unwrap();
+            if ASI_LEVELS.contains(&level) {
+                expected += 1;
+            }
+            assert_eq!(entity.get_pending_improvements(), expected);
+        }
+        assert_eq!(expected, 5);
+    }
+
+    #[test]
+    pub fn ability_improvements() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), 
+            AbilityScores::new(19, 10, 10, 10, 10, 10), &assets, &mut rng);
+
+        // Nothing to spend at level 1 
+        let e = entity.apply_improvement(AbilityScoreImprovement::PlusTwo { ability: Ability::Strength }, &assets).unwrap_err();
+        assert_eq!(e.get_kind(), Some(&EntityErrorKind::NoImprovementAvailable));
+
+        for _ in 1..8 {
+            entity.level_up(&assets, &mut rng).unwrap();
+        }
+        assert_eq!(entity.get_pending_improvements(), 2);
+
+        // Strength caps at 20
+        entity.apply_improvement(AbilityScoreImprovement::PlusTwo { ability: Ability::Strength }, &assets).unwrap();
+        assert_eq!(entity.get_ability_score(Ability::Strength), 20);
+
+        let e = entity.apply_improvement(AbilityScoreImprovement::PlusOnePlusOne { first: Ability::Wisdom, second: Ability::Wisdom }, &assets).unwrap_err();
+        assert_eq!(e.get_kind(), Some(&EntityErrorKind::InvalidImprovement));
+
+        entity.apply_improvement(AbilityScoreImprovement::PlusOnePlusOne { first: Ability::Wisdom, second: Ability::Charisma }, &assets).unwrap();
+        assert_eq!(entity.get_ability_score(Ability::Wisdom), 11);
+        assert_eq!(entity.get_ability_score(Ability::Charisma), 11);
+        assert_eq!(entity.get_pending_improvements(), 0);
+        assert_eq!(entity.get_improvements().len(), 2);
+        assert_eq!(entity.get_improvements()[0].get_ability_increases(), [(Ability::Strength, 1)]);
+
+        // Respec puts everything back
+        assert_eq!(entity.respec_improvements(), 2);
+        assert_eq!(*entity.get_ability_scores(), AbilityScores::new(19, 10, 10, 10, 10, 10));
+        assert!(entity.get_improvements().is_empty());
+    }
+
+    #[test]
+    pub fn feat_improvements() {
+        let mut assets = AssetManager::from_test_config();
+        let tough = assets.create_feat(String::from("Strong Stealthy Lucky"), 
+            vec![FeatPrerequisite::AbilityMinimum { ability: Ability::Strength, minimum: 13 }],
+            vec![
+                FeatEffect::AbilityIncrease { ability: Ability::Strength, amount: 1 }, 
+                FeatEffect::SkillProficiency { skill: Skill::STEALTH, attribute: SkillAttributes::Proficient }, 
+                FeatEffect::SaveProficiency { ability: Ability::Strength }, 
+                FeatEffect::Resource { name: String::from("Luck"), uses: 3 }, 
+            ]).unwrap().get_uuid();
+        
+        let class = assets.get_testing_class().get_uuid();
+        let race = assets.get_testing_race().get_uuid();
+        let mut rng = StepRng::new(5, 1);
+
+        // Too weak for the feat 
+        let mut weak = Entity::new(String::new(), class, race, AbilityScores::from_defaults(), &assets, &mut rng);
+        for _ in 1..4 {
+            weak.level_up(&assets, &mut rng).unwrap();
+        }
+        let e = weak.apply_improvement(AbilityScoreImprovement::Feat { feat: tough }, &assets).unwrap_err();
+        assert!(matches!(e.get_kind(), Some(EntityErrorKind::PrerequisiteNotMet { .. })));
+        let e = weak.apply_improvement(AbilityScoreImprovement::Feat { feat: 55 }, &assets).unwrap_err();
+        assert_eq!(e.get_kind(), Some(&EntityErrorKind::FeatNotFound { feat: 55 }));
+        assert_eq!(weak.get_pending_improvements(), 1);
+
+        let mut strong = Entity::new(String::new(), class, race, AbilityScores::new(13, 10, 10, 10, 10, 10), &assets, &mut rng);
+        for _ in 1..4 {
+            strong.level_up(&assets, &mut rng).unwrap();
+        }
+        strong.apply_improvement(AbilityScoreImprovement::Feat { feat: tough }, &assets).unwrap();
+        assert_eq!(strong.get_ability_score(Ability::Strength), 14);
+        assert_eq!(strong.get_skill_attribute(Skill::STEALTH), SkillAttributes::Proficient);
+        assert_eq!(strong.get_save_attribute(Ability::Strength), SaveAttributes::Proficient);
+        assert_eq!(strong.get_resources().get("Luck"), Some(&3));
+
+        // The record survives serialization 
+        let de: Entity = serde_yaml::from_str(&serde_yaml::to_string(&strong).unwrap()).unwrap();
+        assert_eq!(de.get_improvements(), strong.get_improvements());
+
+        strong.respec_improvements();
+        assert_eq!(strong.get_ability_score(Ability::Strength), 13);
+        assert_eq!(strong.get_skill_attribute(Skill::STEALTH), SkillAttributes::Normal);
+        assert_eq!(strong.get_save_attribute(Ability::Strength), SaveAttributes::Normal);
+        assert!(strong.get_resources().is_empty());
+    }
+
+    #[test]
+    pub fn modified_stats() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), 
+            AbilityScores::new(10, 14, 10, 8, 10, 10), &assets, &mut rng);
+
+        // With no modifiers skills and saves only use ability scores 
+        assert_eq!(entity.get_skill_score(Skill::STEALTH, &assets), Some(2));
+        assert_eq!(entity.get_save_score(Ability::Intelligence, &assets), Some(-1));
+        assert_eq!(entity.get_armor_class(&assets).unwrap(), 12);
+        assert_eq!(entity.get_stat(Stat::Initiative, &assets).unwrap(), 2);
+        assert_eq!(entity.get_speed(&assets).unwrap(), 123);
+
+        let headband = ModifierSource::Item { name: String::from("Headband of Intellect") };
+        let boots = ModifierSource::Item { name: String::from("Boots of Elvenkind") };
+        let haste = ModifierSource::Spell { name: String::from("Haste") };
+        entity.add_modifier(headband.clone(), Modifier::new(Stat::Ability(Ability::Intelligence), ModifierKind::SetTo(19)));
+        entity.add_modifier(boots.clone(), Modifier::new(Stat::Skill(Skill::STEALTH), ModifierKind::Advantage));
+        entity.add_modifier(boots.clone(), Modifier::new(Stat::Skill(Skill::STEALTH), ModifierKind::Proficiency(SkillAttributes::Proficient)));
+        entity.add_modifier(haste.clone(), Modifier::new(Stat::ArmorClass, ModifierKind::Flat(2)));
+        entity.add_modifier(haste.clone(), Modifier::new(Stat::Speed, ModifierKind::Flat(30)));
+        entity.add_modifier(haste.clone(), Modifier::new(Stat::Save(Ability::Dexterity), ModifierKind::Advantage));
+
+        // Ability changes flow into the skills and saves that use them 
+        assert_eq!(entity.get_stat(Stat::Ability(Ability::Intelligence), &assets).unwrap(), 19);
+        assert_eq!(entity.get_stat(Stat::Skill(Skill::ARCANA), &assets).unwrap(), 4);
+        assert_eq!(entity.get_save_score(Ability::Intelligence, &assets), Some(4));
+        assert_eq!(entity.get_skill_score(Skill::STEALTH, &assets), Some(4));
+
+        let stealth = entity.explain_stat(Stat::Skill(Skill::STEALTH), &assets).unwrap();
+        assert_eq!(stealth.get_total(), 4);
+        assert!(stealth.has_advantage());
+        assert_eq!(stealth.get_contributions().len(), 2);
+
+        assert_eq!(entity.get_armor_class(&assets).unwrap(), 14);
+        assert_eq!(entity.get_speed(&assets).unwrap(), 153);
+        assert!(entity.explain_stat(Stat::Save(Ability::Dexterity), &assets).unwrap().has_advantage());
+
+        // Removing a source drops everything it granted 
+        assert_eq!(entity.remove_modifiers_from(&haste), 3);
+        assert_eq!(entity.get_armor_class(&assets).unwrap(), 12);
+        assert_eq!(entity.get_modifiers().len(), 3);
+
+        // The modifiers persist with the entity 
+        let de: Entity = serde_yaml::from_str(&serde_yaml::to_string(&entity).unwrap()).unwrap();
+        assert_eq!(de.get_modifiers(), entity.get_modifiers());
+    }
+
+    #[test]
+    pub fn feat_modifiers() {
+        let mut assets = AssetManager::from_test_config();
+        let alert = assets.create_feat(String::from("Alert"), Vec::new(),
+            vec![FeatEffect::Modifier { modifier: Modifier::new(Stat::Initiative, ModifierKind::Flat(5)) }]).unwrap().get_uuid();
+        let class = assets.get_testing_class().get_uuid();
+        let race = assets.get_testing_race().get_uuid();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class, race, AbilityScores::from_defaults(), &assets, &mut rng);
+        for _ in 1..4 {
+            entity.level_up(&assets, &mut rng).unwrap();
+        }
+
+        entity.apply_improvement(AbilityScoreImprovement::Feat { feat: alert }, &assets).unwrap();
+        let initiative = entity.explain_stat(Stat::Initiative, &assets).unwrap();
+        assert_eq!(initiative.get_total(), 5);
+        assert_eq!(initiative.get_contributions()[0].get_source(), &ModifierSource::Feat { feat: alert });
+
+        entity.respec_improvements();
+        assert_eq!(entity.get_stat(Stat::Initiative, &assets).unwrap(), 0);
+        assert!(entity.get_modifiers().is_empty());
+    }
+
+    #[test]
+    pub fn missing_assets_have_no_stats() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), AbilityScores::from_defaults(), &assets, &mut rng);
+        entity.race = 55;
+
+        assert!(entity.explain_stat(Stat::ArmorClass, &assets).is_none());
+        assert!(entity.get_speed(&assets).is_none());
+    }
+
+    #[test]
+    pub fn passives_and_senses() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), 
+            AbilityScores::new(10, 16, 10, 12, 14, 10), &assets, &mut rng);
+        entity.set_skill_attribute(Skill::PERCEPTION, SkillAttributes::Proficient);
+
+        assert_eq!(entity.get_passive_perception(&assets).unwrap(), 14);
+        assert_eq!(entity.get_passive_investigation(&assets).unwrap(), 11);
+        assert_eq!(entity.get_passive_insight(&assets).unwrap(), 12);
+        assert_eq!(entity.get_initiative_bonus(&assets).unwrap(), 3);
+        assert!(entity.get_senses(&assets).unwrap().is_empty());
+
+        // Advantage and disadvantage shift passives by 5 
+        let observant = ModifierSource::Item { name: String::from("Sentinel Shield") };
+        entity.add_modifier(observant, Modifier::new(Stat::Skill(Skill::PERCEPTION), ModifierKind::Advantage));
+        entity.add_modifier(ModifierSource::Condition { name: String::from("Distracted") }, 
+            Modifier::new(Stat::Skill(Skill::INSIGHT), ModifierKind::Disadvantage));
+        assert_eq!(entity.get_passive_perception(&assets).unwrap(), 19);
+        assert_eq!(entity.get_passive_insight(&assets).unwrap(), 7);
+
+        // Senses take the best SetTo and stack flat bonuses 
+        let goggles = ModifierSource::Item { name: String::from("Goggles of Night") };
+        entity.add_modifier(goggles.clone(), Modifier::new(Stat::Sense(Sense::Darkvision), ModifierKind::SetTo(60)));
+        entity.add_modifier(ModifierSource::Spell { name: String::from("True Seeing") }, 
+            Modifier::new(Stat::Sense(Sense::Truesight), ModifierKind::SetTo(120)));
+        assert_eq!(entity.get_senses(&assets).unwrap(), [(Sense::Darkvision, 60), (Sense::Truesight, 120)]);
+        assert_eq!(entity.get_sense_range(Sense::Blindsight, &assets).unwrap(), 0);
+
+        entity.remove_modifiers_from(&goggles);
+        assert_eq!(entity.get_sense_range(Sense::Darkvision, &assets).unwrap(), 0);
+    }
+
+    #[test]
+    pub fn tool_and_attack_proficiencies() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), 
+            AbilityScores::new(16, 14, 10, 12, 10, 10), &assets, &mut rng);
+
+        // Unproficient: ability modifier only 
+        assert_eq!(entity.get_tool_check("Thieves' Tools", Ability::Dexterity, &assets).unwrap(), 2);
+        assert_eq!(entity.get_attack_bonus(WeaponCategory::Martial, Ability::Strength, &assets).unwrap(), 3);
+
+        assert_eq!(entity.set_tool_attribute(String::from("Thieves' Tools"), SkillAttributes::Expertise), SkillAttributes::Normal);
+        assert!(entity.add_weapon_proficiency(WeaponCategory::Martial));
+        assert!(entity.add_armor_proficiency(ArmorCategory::Medium));
+        assert!(entity.add_language(String::from("Thieves' Cant")));
+
+        assert_eq!(entity.get_tool_check("Thieves' Tools", Ability::Dexterity, &assets).unwrap(), 6);
+        assert_eq!(entity.get_attack_bonus(WeaponCategory::Martial, Ability::Strength, &assets).unwrap(), 5);
+        assert_eq!(entity.get_attack_bonus(WeaponCategory::Simple, Ability::Dexterity, &assets).unwrap(), 2);
+
+        let proficiencies = entity.get_proficiencies(&assets).unwrap();
+        assert!(proficiencies.is_proficient_with_armor(ArmorCategory::Medium));
+        assert!(proficiencies.knows_language("Thieves' Cant"));
+        assert_eq!(*entity.get_own_proficiencies(), proficiencies);
+
+        let de: Entity = serde_yaml::from_str(&serde_yaml::to_string(&entity).unwrap()).unwrap();
+        assert_eq!(de.get_own_proficiencies(), entity.get_own_proficiencies());
+    }
+
+    #[test]
+    pub fn background_grants() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), AbilityScores::from_defaults(), &assets, &mut rng);
+        entity.set_skill_attribute(Skill::HISTORY, SkillAttributes::Expertise);
+
+        let mut p = Proficiencies::new();
+        p.set_tool_attribute(String::from("Calligrapher's Supplies"), SkillAttributes::Proficient);
+        p.add_language(String::from("Draconic"));
+        let sage = Background::new(String::from("Sage"))
+            .with_skills(vec![Skill::ARCANA, Skill::HISTORY])
+            .with_proficiencies(p)
+            .with_equipment(vec![Item::new(String::from("Bottle of ink"), 0.0, 1)], 10);
+        entity.apply_background(&sage);
+
+        assert_eq!(entity.get_background_uuid(), Some(sage.get_uuid()));
+        assert_eq!(entity.get_skill_attribute(Skill::ARCANA), SkillAttributes::Proficient);
+        assert_eq!(entity.get_skill_attribute(Skill::HISTORY), SkillAttributes::Expertise);
+        assert_eq!(entity.get_own_proficiencies().get_tool_attribute("Calligrapher's Supplies"), SkillAttributes::Proficient);
+        assert!(entity.get_own_proficiencies().knows_language("Draconic"));
+        assert_eq!(entity.get_equipment()[0].get_name(), "Bottle of ink");
+        assert_eq!(*entity.get_purse(), Purse::from_coins(0, 0, 0, 10, 0));
+    }
+
+    #[test]
+    pub fn size_and_biography() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), AbilityScores::from_defaults(), &assets, &mut rng);
+        assert_eq!(entity.get_size(), Size::Medium);
+        assert_eq!(*entity.get_biography(), Biography::new());
+
+        assert_eq!(entity.set_size(Size::Small), Size::Medium);
+        entity.get_biography_mut().set_alignment(Some(Alignment::NeutralGood));
+        entity.get_biography_mut().set_backstory(String::from("Grew up on a farm."));
+
+        let de: Entity = serde_yaml::from_str(&serde_yaml::to_string(&entity).unwrap()).unwrap();
+        assert_eq!(de.get_size(), Size::Small);
+        assert_eq!(de.get_biography().get_alignment(), Some(Alignment::NeutralGood));
+        assert_eq!(de.get_biography().get_backstory(), "Grew up on a farm.");
+    }
+
+    #[test]
+    pub fn encumbrance() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let mut entity = Entity::new(String::new(), class.get_uuid(), race.get_uuid(), 
+            AbilityScores::new(10, 10, 10, 10, 10, 10), &assets, &mut rng);
+        assert_eq!(entity.get_carrying_capacity(&assets).unwrap(), 150.0);
+        assert_eq!(entity.get_encumbrance(&assets).unwrap(), Encumbrance::Unencumbered);
+        assert_eq!(entity.get_speed(&assets).unwrap(), 123);
+
+        // 40 lb of gear and 500 coins (10 lb) is right at the limit 
+        entity.add_equipment(Item::new(String::from("Chain mail"), 40.0, 1));
+        entity.get_purse_mut().add(Coin::Gold, 500);
+        assert_eq!(entity.get_load(), 50.0);
+        assert_eq!(entity.get_encumbrance(&assets).unwrap(), Encumbrance::Unencumbered);
+
+        entity.add_equipment(Item::new(String::from("Rations"), 2.0, 10));
+        assert_eq!(entity.get_encumbrance(&assets).unwrap(), Encumbrance::Encumbered);
+        assert_eq!(entity.get_speed(&assets).unwrap(), 113);
+        let speed = entity.explain_stat(Stat::Speed, &assets).unwrap();
+        assert_eq!(speed.get_base(), 123);
+        assert_eq!(format!("{}", speed), "Speed: 123 (base), -10 (condition Encumbered) = 113");
+
+        // More Strength lifts the penalty; being Small doesn't change capacity 
+        entity.add_modifier(ModifierSource::Item { name: String::from("Belt of Giant Strength") }, 
+            Modifier::new(Stat::Ability(Ability::Strength), ModifierKind::SetTo(21)));
+        entity.set_size(Size::Small);
+        assert_eq!(entity.get_encumbrance(&assets).unwrap(), Encumbrance::Unencumbered);
+        assert_eq!(entity.get_carrying_capacity(&assets).unwrap(), 315.0);
+
+        entity.add_equipment(Item::new(String::from("Anvil"), 300.0, 1));
+        assert_eq!(entity.get_encumbrance(&assets).unwrap(), Encumbrance::OverCapacity);
+        assert_eq!(entity.get_speed(&assets).unwrap(), 103);
+    }
+
+    #[test]
+    fn take_damage() {
+        let mut rng = Rng::new(0, 0);
+        let assets = AssetManager::from_test_config();
+        let mut entity = Entity::new(String::from("Target"), assets.get_testing_class().get_uuid(), assets.get_testing_race().get_uuid(), 
+            AbilityScores::from_defaults(), &assets, &mut rng);
+        entity.hp = 10;
+        entity.hp_temp = 5;
+
+        assert_eq!(entity.take_damage(3), 0);
+        assert_eq!(entity.get_hp_temp(), 2);
+        assert_eq!(entity.take_damage(6), 4);
+        assert_eq!((entity.get_hp(), entity.get_hp_temp()), (6, 0));
+        assert_eq!(entity.take_damage(50), 6);
+        assert_eq!(entity.get_hp(), 0);
     }
 }
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::assets::asset::Asset;
+
+use super::{abilities::{Ability, AbilityScoreIntType, SaveAttributes}, entity::Entity, modifier::Modifier, skills::{Skill, SkillAttributes}};
+
+/// A requirement an Entity must meet before it can take a Feat.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum FeatPrerequisite {
+    AbilityMinimum { ability: Ability, minimum: AbilityScoreIntType },
+    Race { race: u128 },
+    SkillProficiency { skill: Skill },
+    SaveProficiency { ability: Ability },
+}
+
+impl FeatPrerequisite {
+    pub fn is_met_by(&self, entity: &Entity) -> bool {
+        match self {
+            FeatPrerequisite::AbilityMinimum { ability, minimum } => entity.get_ability_score(*ability) >= *minimum,
+            FeatPrerequisite::Race { race } => entity.get_race_uuid() == *race,
+            FeatPrerequisite::SkillProficiency { skill } => matches!(entity.get_skill_attribute(*skill),
+                SkillAttributes::Proficient | SkillAttributes::Expertise),
+            FeatPrerequisite::SaveProficiency { ability } => entity.get_save_attribute(*ability) == SaveAttributes::Proficient,
+        }
+    }
+}
+
+/// A mechanical change granted to an Entity when it takes a Feat.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum FeatEffect {
+    AbilityIncrease { ability: Ability, amount: AbilityScoreIntType },
+    SkillProficiency { skill: Skill, attribute: SkillAttributes },
+    SaveProficiency { ability: Ability },
+    Resource { name: String, uses: u8 },
+    Modifier { modifier: Modifier },
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Feat {
+    #[serde(with = "uuid::serde::simple")]
+    uuid: Uuid,
+    #[serde(skip)]
+    campaign: Option<u128>,
+    #[serde(default)]
+    overrides: Option<u128>,
+    name: String,
+    #[serde(default)]
+    prerequisites: Vec<FeatPrerequisite>,
+    #[serde(default)]
+    effects: Vec<FeatEffect>,
+}
+
+impl Feat {
+    pub fn new(name: String, prerequisites: Vec<FeatPrerequisite>, effects: Vec<FeatEffect>) -> Self {
+        Self {
+            uuid: Uuid::now_v7(),
+            campaign: None,
+            overrides: None,
+            name,
+            prerequisites,
+            effects
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_prerequisites(&self) -> &[FeatPrerequisite] {
+        &self.prerequisites
+    }
+
+    pub fn get_effects(&self) -> &[FeatEffect] {
+        &self.effects
+    }
+
+    /// Find the first prerequisite of this Feat that the Entity does not meet, if any.
+    pub fn get_unmet_prerequisite(&self, entity: &Entity) -> Option<&FeatPrerequisite> {
+        self.prerequisites.iter().find(|p| !p.is_met_by(entity))
+    }
+}
+
+impl Asset for Feat {
+    fn get_uuid(&self) -> u128 {
+        self.uuid.as_u128()
+    }
+
+    fn get_owning_campaign(&self) -> Option<u128> {
+        self.campaign
+    }
+
+    fn set_owning_campaign(&mut self, campaign: Option<u128>) {
+        self.campaign = campaign;
+    }
+
+    fn get_overridden_asset(&self) -> Option<u128> {
+        self.overrides
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn getters() {
+        let feat = Feat::new(String::from("Roger Waters"),
+            vec![FeatPrerequisite::AbilityMinimum { ability: Ability::Charisma, minimum: 13 }],
+            vec![FeatEffect::AbilityIncrease { ability: Ability::Charisma, amount: 1 }]);
+
+        assert_eq!(feat.get_name(), "Roger Waters");
+        assert_eq!(feat.get_prerequisites(), [FeatPrerequisite::AbilityMinimum { ability: Ability::Charisma, minimum: 13 }]);
+        assert_eq!(feat.get_effects(), [FeatEffect::AbilityIncrease { ability: Ability::Charisma, amount: 1 }]);
+    }
+
+    #[test]
+    pub fn serde() {
+        let data = "
+uuid: 0000000000000000000000000000fea7
+name: Serialized Feat
+prerequisites:
+  - !AbilityMinimum { ability: Dexterity, minimum: 13 }
+effects:
+  - !SkillProficiency { skill: Stealth, attribute: Proficient }
+  - !Resource { name: Luck, uses: 3 }
+";
+        let feat: Feat = serde_yaml::from_str(data).unwrap();
+        assert_eq!(feat.get_uuid(), 0xfea7);
+        assert_eq!(feat.get_prerequisites(), [FeatPrerequisite::AbilityMinimum { ability: Ability::Dexterity, minimum: 13 }]);
+        assert_eq!(feat.get_effects(), [
+            FeatEffect::SkillProficiency { skill: Skill::STEALTH, attribute: SkillAttributes::Proficient },
+            FeatEffect::Resource { name: String::from("Luck"), uses: 3 },
+        ]);
+    }
+}
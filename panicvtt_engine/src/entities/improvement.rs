@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use super::{abilities::{Ability, AbilityScoreIntType, SaveAttributes}, skills::{Skill, SkillAttributes}};
+
+/// Levels at which an Entity is offered an Ability Score Improvement.
+pub const ASI_LEVELS: [u8; 5] = [4, 8, 12, 16, 19];
+
+/// Ability scores cannot be raised past this by an Ability Score Improvement.
+pub const ASI_ABILITY_CAP: AbilityScoreIntType = 20;
+
+/// The choice an Entity makes when offered an Ability Score Improvement.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum AbilityScoreImprovement {
+    PlusTwo { ability: Ability },
+    PlusOnePlusOne { first: Ability, second: Ability },
+    Feat { feat: u128 },
+}
+
+/// A taken Ability Score Improvement along with exactly what it changed, so that it can be undone.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ImprovementRecord {
+    level: u8,
+    improvement: AbilityScoreImprovement,
+    #[serde(default)]
+    ability_increases: Vec<(Ability, AbilityScoreIntType)>,
+    #[serde(default)]
+    replaced_skills: Vec<(Skill, SkillAttributes)>,
+    #[serde(default)]
+    replaced_saves: Vec<(Ability, SaveAttributes)>,
+    #[serde(default)]
+    granted_resources: Vec<(String, u8)>,
+}
+
+impl ImprovementRecord {
+    pub(crate) fn new(level: u8, improvement: AbilityScoreImprovement) -> Self {
+        Self {
+            level,
+            improvement,
+            ability_increases: Vec::new(),
+            replaced_skills: Vec::new(),
+            replaced_saves: Vec::new(),
+            granted_resources: Vec::new(),
+        }
+    }
+
+    pub fn get_level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn get_improvement(&self) -> &AbilityScoreImprovement {
+        &self.improvement
+    }
+
+    pub fn get_ability_increases(&self) -> &[(Ability, AbilityScoreIntType)] {
+        &self.ability_increases
+    }
+
+    pub(crate) fn record_ability_increase(&mut self, ability: Ability, applied: AbilityScoreIntType) {
+        self.ability_increases.push((ability, applied));
+    }
+
+    pub(crate) fn record_skill_replacement(&mut self, skill: Skill, previous: SkillAttributes) {
+        self.replaced_skills.push((skill, previous));
+    }
+
+    pub(crate) fn record_save_replacement(&mut self, ability: Ability, previous: SaveAttributes) {
+        self.replaced_saves.push((ability, previous));
+    }
+
+    pub(crate) fn record_resource(&mut self, name: String, uses: u8) {
+        self.granted_resources.push((name, uses));
+    }
+
+    pub(crate) fn get_replaced_skills(&self) -> &[(Skill, SkillAttributes)] {
+        &self.replaced_skills
+    }
+
+    pub(crate) fn get_replaced_saves(&self) -> &[(Ability, SaveAttributes)] {
+        &self.replaced_saves
+    }
+
+    pub(crate) fn get_granted_resources(&self) -> &[(String, u8)] {
+        &self.granted_resources
+    }
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// A carried item. Written in asset files either as just a name or with its weight (in pounds) and quantity.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(from = "ItemRepr")]
+pub struct Item {
+    name: String,
+    weight: f32,
+    quantity: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ItemRepr {
+    Name(String),
+    Full {
+        name: String,
+        #[serde(default)]
+        weight: f32,
+        #[serde(default = "default_quantity")]
+        quantity: u32,
+    },
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+impl From<ItemRepr> for Item {
+    fn from(repr: ItemRepr) -> Self {
+        match repr {
+            ItemRepr::Name(name) => Item::new(name, 0.0, 1),
+            ItemRepr::Full { name, weight, quantity } => Item::new(name, weight, quantity),
+        }
+    }
+}
+
+impl Item {
+    pub fn new(name: String, weight: f32, quantity: u32) -> Self {
+        Self { name, weight, quantity }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Weight of one of this item in pounds.
+    pub fn get_weight(&self) -> f32 {
+        self.weight
+    }
+
+    pub fn get_quantity(&self) -> u32 {
+        self.quantity
+    }
+
+    pub fn get_total_weight(&self) -> f32 {
+        self.weight * self.quantity as f32
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn serde() {
+        let items: Vec<Item> = serde_yaml::from_str("
+- Holy symbol
+- { name: Rations, weight: 2, quantity: 5 }
+- { name: Crowbar, weight: 5 }
+").unwrap();
+
+        assert_eq!(items[0], Item::new(String::from("Holy symbol"), 0.0, 1));
+        assert_eq!(items[1].get_total_weight(), 10.0);
+        assert_eq!(items[2].get_quantity(), 1);
+
+        let de: Vec<Item> = serde_yaml::from_str(&serde_yaml::to_string(&items).unwrap()).unwrap();
+        assert_eq!(de, items);
+    }
+}
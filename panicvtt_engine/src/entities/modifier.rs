@@ -0,0 +1,242 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use super::{abilities::Ability, senses::Sense, skills::{Skill, SkillAttributes}};
+
+pub type StatIntType = i16;
+
+/// A derived value on an Entity that modifiers can contribute to.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum Stat {
+    Ability(Ability),
+    Skill(Skill),
+    Save(Ability),
+    Speed,
+    ArmorClass,
+    Initiative,
+    /// Range in feet; 0 means the Entity lacks the Sense.
+    Sense(Sense),
+}
+
+impl Display for Stat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stat::Ability(ability) => write!(f, "{}", ability),
+            Stat::Skill(skill) => write!(f, "{}", skill),
+            Stat::Save(ability) => write!(f, "{} save", ability),
+            Stat::Speed => write!(f, "Speed"),
+            Stat::ArmorClass => write!(f, "AC"),
+            Stat::Initiative => write!(f, "Initiative"),
+            Stat::Sense(sense) => write!(f, "{:?}", sense),
+        }
+    }
+}
+
+/// Where a modifier came from, so it can be explained and later removed.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum ModifierSource {
+    Race { race: u128 },
+    Class { class: u128 },
+    Feat { feat: u128 },
+    Item { name: String },
+    Condition { name: String },
+    Spell { name: String },
+}
+
+impl Display for ModifierSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModifierSource::Race { race } => write!(f, "race {:x}", race),
+            ModifierSource::Class { class } => write!(f, "class {:x}", class),
+            ModifierSource::Feat { feat } => write!(f, "feat {:x}", feat),
+            ModifierSource::Item { name } => write!(f, "item {}", name),
+            ModifierSource::Condition { name } => write!(f, "condition {}", name),
+            ModifierSource::Spell { name } => write!(f, "spell {}", name),
+        }
+    }
+}
+
+/// How a modifier changes the Stat it targets.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum ModifierKind {
+    /// Add to (or subtract from) the Stat.
+    Flat(StatIntType),
+    Advantage,
+    Disadvantage,
+    /// Raise the Stat's base to this value if it is lower. The highest SetTo wins.
+    SetTo(StatIntType),
+    /// Grant at least this level of proficiency. Only Skills and Saves use it.
+    Proficiency(SkillAttributes),
+}
+
+impl Display for ModifierKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModifierKind::Flat(n) => write!(f, "{:+}", n),
+            ModifierKind::Advantage => write!(f, "advantage"),
+            ModifierKind::Disadvantage => write!(f, "disadvantage"),
+            ModifierKind::SetTo(n) => write!(f, "set to {}", n),
+            ModifierKind::Proficiency(attribute) => write!(f, "{:?}", attribute),
+        }
+    }
+}
+
+/// A typed contribution to a single Stat.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct Modifier {
+    stat: Stat,
+    kind: ModifierKind,
+}
+
+impl Modifier {
+    pub fn new(stat: Stat, kind: ModifierKind) -> Self {
+        Self { stat, kind }
+    }
+
+    pub fn get_stat(&self) -> Stat {
+        self.stat
+    }
+
+    pub fn get_kind(&self) -> ModifierKind {
+        self.kind
+    }
+}
+
+/// A Modifier attached to an Entity along with its source.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct AppliedModifier {
+    source: ModifierSource,
+    modifier: Modifier,
+}
+
+impl AppliedModifier {
+    pub fn new(source: ModifierSource, modifier: Modifier) -> Self {
+        Self { source, modifier }
+    }
+
+    pub fn get_source(&self) -> &ModifierSource {
+        &self.source
+    }
+
+    pub fn get_modifier(&self) -> &Modifier {
+        &self.modifier
+    }
+}
+
+/// The computed value of a Stat along with every modifier that went into it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatBreakdown {
+    stat: Stat,
+    base: StatIntType,
+    set_to: Option<StatIntType>,
+    flat: StatIntType,
+    advantage: bool,
+    disadvantage: bool,
+    contributions: Vec<AppliedModifier>,
+}
+
+impl StatBreakdown {
+    pub(crate) fn new(stat: Stat, base: StatIntType) -> Self {
+        Self {
+            stat, base,
+            set_to: None,
+            flat: 0,
+            advantage: false,
+            disadvantage: false,
+            contributions: Vec::new(),
+        }
+    }
+
+    pub(crate) fn apply(&mut self, applied: &AppliedModifier) {
+        match applied.modifier.kind {
+            ModifierKind::Flat(n) => { self.flat += n; },
+            ModifierKind::Advantage => { self.advantage = true; },
+            ModifierKind::Disadvantage => { self.disadvantage = true; },
+            ModifierKind::SetTo(n) => { self.set_to = Some(self.set_to.map_or(n, |s| s.max(n))); },
+            // Proficiency is folded into the base by the Entity; it's recorded here for explanation only
+            ModifierKind::Proficiency(_) => (),
+        }
+
+        self.contributions.push(applied.clone());
+    }
+
+    pub fn get_stat(&self) -> Stat {
+        self.stat
+    }
+
+    pub fn get_base(&self) -> StatIntType {
+        self.base
+    }
+
+    pub fn get_total(&self) -> StatIntType {
+        self.base.max(self.set_to.unwrap_or(StatIntType::MIN)) + self.flat
+    }
+
+    /// Advantage and disadvantage cancel each other out.
+    pub fn has_advantage(&self) -> bool {
+        self.advantage && !self.disadvantage
+    }
+
+    pub fn has_disadvantage(&self) -> bool {
+        self.disadvantage && !self.advantage
+    }
+
+    /// Passive score = 10 + total, +5 with advantage or -5 with disadvantage. 
+    pub fn get_passive(&self) -> StatIntType {
+        let situational = if self.has_advantage() { 5 } else if self.has_disadvantage() { -5 } else { 0 };
+        10 + self.get_total() + situational
+    }
+
+    pub fn get_contributions(&self) -> &[AppliedModifier] {
+        &self.contributions
+    }
+}
+
+impl Display for StatBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} (base)", self.stat, self.base)?;
+        for c in &self.contributions {
+            write!(f, ", {} ({})", c.modifier.kind, c.source)?;
+        }
+        write!(f, " = {}", self.get_total())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn breakdown_total() {
+        let mut b = StatBreakdown::new(Stat::ArmorClass, 12);
+        assert_eq!(b.get_total(), 12);
+
+        b.apply(&AppliedModifier::new(ModifierSource::Item { name: String::from("Shield") }, Modifier::new(Stat::ArmorClass, ModifierKind::Flat(2))));
+        b.apply(&AppliedModifier::new(ModifierSource::Spell { name: String::from("Shield of Faith") }, Modifier::new(Stat::ArmorClass, ModifierKind::Flat(2))));
+        assert_eq!(b.get_total(), 16);
+
+        // SetTo only matters when it beats the base, and flats still stack on top
+        b.apply(&AppliedModifier::new(ModifierSource::Spell { name: String::from("Barkskin") }, Modifier::new(Stat::ArmorClass, ModifierKind::SetTo(10))));
+        assert_eq!(b.get_total(), 16);
+        b.apply(&AppliedModifier::new(ModifierSource::Spell { name: String::from("Mage Armor") }, Modifier::new(Stat::ArmorClass, ModifierKind::SetTo(15))));
+        assert_eq!(b.get_total(), 19);
+
+        assert_eq!(b.get_base(), 12);
+        assert_eq!(b.get_contributions().len(), 4);
+        assert_eq!(format!("{}", b), "AC: 12 (base), +2 (item Shield), +2 (spell Shield of Faith), set to 10 (spell Barkskin), set to 15 (spell Mage Armor) = 19");
+    }
+
+    #[test]
+    pub fn breakdown_advantage() {
+        let source = ModifierSource::Condition { name: String::from("Poisoned") };
+        let mut b = StatBreakdown::new(Stat::Skill(Skill::STEALTH), 0);
+        assert!(!b.has_advantage() && !b.has_disadvantage());
+
+        b.apply(&AppliedModifier::new(source.clone(), Modifier::new(Stat::Skill(Skill::STEALTH), ModifierKind::Disadvantage)));
+        assert!(!b.has_advantage() && b.has_disadvantage());
+
+        b.apply(&AppliedModifier::new(source, Modifier::new(Stat::Skill(Skill::STEALTH), ModifierKind::Advantage)));
+        assert!(!b.has_advantage() && !b.has_disadvantage());
+    }
+}
@@ -0,0 +1,423 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{assets::asset::Asset, mechanics::dice::{Dice, DiceFormula, Rng}};
+
+use super::{abilities::{Ability, AbilityScores}, biography::Alignment, size::Size, skills::{deserialize_skill_attributes, Skill, SkillAttributes}};
+
+/// A challenge rating: 0, 1/8, 1/4, 1/2, or 1 through 30.
+///
+/// Written in asset files as a whole number or a fraction string such as "1/4".
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+#[serde(try_from = "ChallengeRatingRepr", into = "ChallengeRatingRepr")]
+pub struct ChallengeRating(u8);
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ChallengeRatingRepr {
+    Whole(u8),
+    Fraction(String),
+}
+
+impl ChallengeRating {
+    pub const EIGHTH: ChallengeRating = ChallengeRating(1);
+    pub const QUARTER: ChallengeRating = ChallengeRating(2);
+    pub const HALF: ChallengeRating = ChallengeRating(3);
+
+    const FRACTIONS: [&'static str; 3] = ["1/8", "1/4", "1/2"];
+
+    /// XP awarded for each challenge rating, indexed the same way as ChallengeRating.
+    const XP: [u32; 34] = [
+        10, 25, 50, 100,
+        200, 450, 700, 1100, 1800, 2300, 2900, 3900, 5000, 5900,
+        7200, 8400, 10000, 11500, 13000, 15000, 18000, 20000, 22000, 25000,
+        33000, 41000, 50000, 62000, 75000, 90000, 105000, 120000, 135000, 155000,
+    ];
+
+    pub fn from_whole(cr: u8) -> Option<Self> {
+        match cr {
+            0 => Some(Self(0)),
+            1..=30 => Some(Self(cr + 3)),
+            _ => None,
+        }
+    }
+
+    pub fn get_xp(&self) -> u32 {
+        Self::XP[self.0 as usize]
+    }
+
+    pub fn get_proficiency_bonus(&self) -> u8 {
+        match self.as_f32() {
+            cr if cr < 5.0 => 2,
+            cr => ((cr as u8 - 1) / 4) + 2,
+        }
+    }
+
+    pub fn as_f32(&self) -> f32 {
+        match self.0 {
+            0 => 0.0,
+            1 => 0.125,
+            2 => 0.25,
+            3 => 0.5,
+            n => (n - 3) as f32,
+        }
+    }
+
+    /// Every challenge rating, lowest first.
+    pub fn all() -> impl Iterator<Item = ChallengeRating> {
+        (0..Self::XP.len() as u8).map(ChallengeRating)
+    }
+}
+
+impl TryFrom<ChallengeRatingRepr> for ChallengeRating {
+    type Error = String;
+
+    fn try_from(value: ChallengeRatingRepr) -> Result<Self, Self::Error> {
+        match value {
+            ChallengeRatingRepr::Whole(cr) => Self::from_whole(cr).ok_or_else(|| format!("invalid challenge rating {}", cr)),
+            ChallengeRatingRepr::Fraction(f) => match Self::FRACTIONS.iter().position(|s| *s == f) {
+                Some(i) => Ok(Self(i as u8 + 1)),
+                None => f.parse::<u8>().ok().and_then(Self::from_whole).ok_or_else(|| format!("invalid challenge rating {}", f)),
+            },
+        }
+    }
+}
+
+impl From<ChallengeRating> for ChallengeRatingRepr {
+    fn from(value: ChallengeRating) -> Self {
+        match value.0 {
+            1..=3 => ChallengeRatingRepr::Fraction(String::from(ChallengeRating::FRACTIONS[value.0 as usize - 1])),
+            0 => ChallengeRatingRepr::Whole(0),
+            n => ChallengeRatingRepr::Whole(n - 3),
+        }
+    }
+}
+
+impl Display for ChallengeRating {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match ChallengeRatingRepr::from(*self) {
+            ChallengeRatingRepr::Whole(cr) => write!(f, "{}", cr),
+            ChallengeRatingRepr::Fraction(cr) => write!(f, "{}", cr),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash)]
+pub enum CreatureType {
+    Aberration,
+    Beast,
+    Celestial,
+    Construct,
+    Dragon,
+    Elemental,
+    Fey,
+    Fiend,
+    Giant,
+    Humanoid,
+    Monstrosity,
+    Ooze,
+    Plant,
+    Undead,
+}
+
+/// A named entry in a stat block: a trait, action, reaction or legendary action.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct MonsterAction {
+    name: String,
+    #[serde(default)]
+    description: String,
+    /// Legendary actions can cost more than one action from the pool.
+    #[serde(default = "default_cost")]
+    cost: u8,
+}
+
+fn default_cost() -> u8 {
+    1
+}
+
+impl MonsterAction {
+    pub fn new(name: String, description: String, cost: u8) -> Self {
+        Self { name, description, cost }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn get_cost(&self) -> u8 {
+        self.cost
+    }
+}
+
+/// How a monster's hit points are determined when it is placed into a Scene.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub enum HitPointMode {
+    Rolled,
+    Average,
+}
+
+/// A creature or NPC's stat block. Instances of it are Entities.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Monster {
+    #[serde(with = "uuid::serde::simple")]
+    uuid: Uuid,
+    #[serde(skip)]
+    campaign: Option<u128>,
+    #[serde(default)]
+    overrides: Option<u128>,
+    name: String,
+    #[serde(default)]
+    size: Size,
+    creature_type: CreatureType,
+    #[serde(default)]
+    alignment: Option<Alignment>,
+    challenge_rating: ChallengeRating,
+    /// Overrides the XP for the challenge rating, for monsters that award a nonstandard amount.
+    #[serde(default)]
+    xp: Option<u32>,
+    armor_class: u8,
+    hit_dice: DiceFormula,
+    speed: u8,
+    abilities: AbilityScores,
+    #[serde(default)]
+    saves: Vec<Ability>,
+    #[serde(default, deserialize_with = "deserialize_skill_attributes")]
+    skills: BTreeMap<Skill, SkillAttributes>,
+    #[serde(default)]
+    traits: Vec<MonsterAction>,
+    #[serde(default)]
+    actions: Vec<MonsterAction>,
+    #[serde(default)]
+    reactions: Vec<MonsterAction>,
+    #[serde(default)]
+    legendary_actions: Vec<MonsterAction>,
+    /// Legendary actions available each round.
+    #[serde(default)]
+    legendary_action_count: u8,
+    /// Actions the monster's lair takes on initiative count 20.
+    #[serde(default)]
+    lair_actions: Vec<MonsterAction>,
+}
+
+impl Monster {
+    pub fn new(name: String, creature_type: CreatureType, challenge_rating: ChallengeRating) -> Self {
+        Self {
+            uuid: Uuid::now_v7(),
+            campaign: None,
+            overrides: None,
+            name,
+            size: Size::default(),
+            creature_type,
+            alignment: None,
+            challenge_rating,
+            xp: None,
+            armor_class: 10,
+            hit_dice: DiceFormula::new(1, Dice::D8, 0),
+            speed: 30,
+            abilities: AbilityScores::from_defaults(),
+            saves: Vec::new(),
+            skills: BTreeMap::new(),
+            traits: Vec::new(),
+            actions: Vec::new(),
+            reactions: Vec::new(),
+            legendary_actions: Vec::new(),
+            legendary_action_count: 0,
+            lair_actions: Vec::new(),
+        }
+    }
+
+    pub fn with_defenses(mut self, armor_class: u8, hit_dice: DiceFormula) -> Self {
+        self.armor_class = armor_class;
+        self.hit_dice = hit_dice;
+        self
+    }
+
+    pub fn with_abilities(mut self, abilities: AbilityScores) -> Self {
+        self.abilities = abilities;
+        self
+    }
+
+    pub fn with_actions(mut self, actions: Vec<MonsterAction>) -> Self {
+        self.actions = actions;
+        self
+    }
+
+    pub fn with_legendary_actions(mut self, legendary_actions: Vec<MonsterAction>, count: u8) -> Self {
+        self.legendary_actions = legendary_actions;
+        self.legendary_action_count = count;
+        self
+    }
+
+    pub fn with_lair_actions(mut self, lair_actions: Vec<MonsterAction>) -> Self {
+        self.lair_actions = lair_actions;
+        self
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_size(&self) -> Size {
+        self.size
+    }
+
+    pub fn get_creature_type(&self) -> CreatureType {
+        self.creature_type
+    }
+
+    pub fn get_alignment(&self) -> Option<Alignment> {
+        self.alignment
+    }
+
+    pub fn get_challenge_rating(&self) -> ChallengeRating {
+        self.challenge_rating
+    }
+
+    pub fn get_xp(&self) -> u32 {
+        self.xp.unwrap_or(self.challenge_rating.get_xp())
+    }
+
+    pub fn get_armor_class(&self) -> u8 {
+        self.armor_class
+    }
+
+    pub fn get_hit_dice(&self) -> DiceFormula {
+        self.hit_dice
+    }
+
+    pub fn get_speed(&self) -> u8 {
+        self.speed
+    }
+
+    pub fn get_abilities(&self) -> &AbilityScores {
+        &self.abilities
+    }
+
+    pub fn get_saves(&self) -> &[Ability] {
+        &self.saves
+    }
+
+    pub fn get_skills(&self) -> &BTreeMap<Skill, SkillAttributes> {
+        &self.skills
+    }
+
+    pub fn get_traits(&self) -> &[MonsterAction] {
+        &self.traits
+    }
+
+    pub fn get_actions(&self) -> &[MonsterAction] {
+        &self.actions
+    }
+
+    pub fn get_reactions(&self) -> &[MonsterAction] {
+        &self.reactions
+    }
+
+    pub fn get_legendary_actions(&self) -> &[MonsterAction] {
+        &self.legendary_actions
+    }
+
+    pub fn get_legendary_action_count(&self) -> u8 {
+        self.legendary_action_count
+    }
+
+    pub fn get_lair_actions(&self) -> &[MonsterAction] {
+        &self.lair_actions
+    }
+
+    /// Look up a legendary action by name.
+    pub fn get_legendary_action(&self, name: &str) -> Option<&MonsterAction> {
+        self.legendary_actions.iter().find(|a| a.get_name() == name)
+    }
+
+    pub fn roll_hit_points(&self, mode: HitPointMode, rng: &mut Rng) -> u16 {
+        match mode {
+            HitPointMode::Rolled => self.hit_dice.roll(rng),
+            HitPointMode::Average => self.hit_dice.average(),
+        }
+    }
+}
+
+impl Asset for Monster {
+    fn get_uuid(&self) -> u128 {
+        self.uuid.as_u128()
+    }
+
+    fn get_owning_campaign(&self) -> Option<u128> {
+        self.campaign
+    }
+
+    fn set_owning_campaign(&mut self, campaign: Option<u128>) {
+        self.campaign = campaign;
+    }
+
+    fn get_overridden_asset(&self) -> Option<u128> {
+        self.overrides
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use rand::rngs::mock::StepRng;
+
+    use super::*;
+
+    #[test]
+    pub fn challenge_rating() {
+        assert_eq!(ChallengeRating::from_whole(0).unwrap().get_xp(), 10);
+        assert_eq!(ChallengeRating::EIGHTH.get_xp(), 25);
+        assert_eq!(ChallengeRating::HALF.get_xp(), 100);
+        assert_eq!(ChallengeRating::from_whole(5).unwrap().get_xp(), 1800);
+        assert_eq!(ChallengeRating::from_whole(30).unwrap().get_xp(), 155000);
+        assert!(ChallengeRating::from_whole(31).is_none());
+
+        assert_eq!(ChallengeRating::QUARTER.get_proficiency_bonus(), 2);
+        assert_eq!(ChallengeRating::from_whole(4).unwrap().get_proficiency_bonus(), 2);
+        assert_eq!(ChallengeRating::from_whole(5).unwrap().get_proficiency_bonus(), 3);
+        assert_eq!(ChallengeRating::from_whole(17).unwrap().get_proficiency_bonus(), 6);
+        assert_eq!(ChallengeRating::from_whole(30).unwrap().get_proficiency_bonus(), 9);
+
+        assert!(ChallengeRating::QUARTER < ChallengeRating::from_whole(1).unwrap());
+        assert_eq!(ChallengeRating::all().count(), 34);
+        assert_eq!(format!("{}", ChallengeRating::QUARTER), "1/4");
+        assert_eq!(format!("{}", ChallengeRating::from_whole(12).unwrap()), "12");
+    }
+
+    #[test]
+    pub fn challenge_rating_serde() {
+        let crs: Vec<ChallengeRating> = serde_yaml::from_str("[0, 1/8, '1/2', 7, '21']").unwrap();
+        assert_eq!(crs, [ChallengeRating::from_whole(0).unwrap(), ChallengeRating::EIGHTH, ChallengeRating::HALF,
+            ChallengeRating::from_whole(7).unwrap(), ChallengeRating::from_whole(21).unwrap()]);
+        assert!(serde_yaml::from_str::<ChallengeRating>("1/3").is_err());
+        assert!(serde_yaml::from_str::<ChallengeRating>("40").is_err());
+
+        let de: Vec<ChallengeRating> = serde_yaml::from_str(&serde_yaml::to_string(&crs).unwrap()).unwrap();
+        assert_eq!(de, crs);
+    }
+
+    #[test]
+    pub fn getters() {
+        let claw = MonsterAction::new(String::from("Claw"), String::from("Melee Weapon Attack: +4 to hit."), 1);
+        let monster = Monster::new(String::from("Owlbear"), CreatureType::Monstrosity, ChallengeRating::from_whole(3).unwrap())
+            .with_defenses(13, DiceFormula::new(7, Dice::D10, 21))
+            .with_abilities(AbilityScores::new(20, 12, 17, 3, 12, 7))
+            .with_actions(vec![claw.clone()]);
+
+        assert_eq!(monster.get_name(), "Owlbear");
+        assert_eq!(monster.get_creature_type(), CreatureType::Monstrosity);
+        assert_eq!(monster.get_xp(), 700);
+        assert_eq!(monster.get_armor_class(), 13);
+        assert_eq!(monster.get_actions(), [claw]);
+        assert!(monster.get_legendary_actions().is_empty());
+
+        let mut rng = StepRng::new(0, 1);
+        assert_eq!(monster.roll_hit_points(HitPointMode::Average, &mut rng), 59);
+        assert_eq!(monster.roll_hit_points(HitPointMode::Rolled, &mut rng), 1 + 2 + 3 + 4 + 5 + 6 + 7 + 21);
+    }
+}
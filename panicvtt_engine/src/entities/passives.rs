@@ -0,0 +1,90 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::assets::asset_manager::AssetManager;
+
+use super::{entity::Entity, modifier::StatIntType, senses::Sense};
+
+/// The values a GM needs at a glance for one Entity, without rolling.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PassiveSummary {
+    #[serde(with = "uuid::serde::simple")]
+    entity: Uuid,
+    name: String,
+    passive_perception: StatIntType,
+    passive_investigation: StatIntType,
+    passive_insight: StatIntType,
+    initiative_bonus: StatIntType,
+    senses: Vec<(Sense, u16)>,
+}
+
+impl PassiveSummary {
+    /// Summarize an Entity. Returns None if its race or class cannot be found.
+    pub fn from_entity(entity: &Entity, assets: &AssetManager) -> Option<Self> {
+        Some(Self {
+            entity: Uuid::from_u128(entity.get_uuid()),
+            name: String::from(entity.get_name()),
+            passive_perception: entity.get_passive_perception(assets)?,
+            passive_investigation: entity.get_passive_investigation(assets)?,
+            passive_insight: entity.get_passive_insight(assets)?,
+            initiative_bonus: entity.get_initiative_bonus(assets)?,
+            senses: entity.get_senses(assets)?,
+        })
+    }
+
+    pub fn get_entity(&self) -> u128 {
+        self.entity.as_u128()
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_passive_perception(&self) -> StatIntType {
+        self.passive_perception
+    }
+
+    pub fn get_passive_investigation(&self) -> StatIntType {
+        self.passive_investigation
+    }
+
+    pub fn get_passive_insight(&self) -> StatIntType {
+        self.passive_insight
+    }
+
+    pub fn get_initiative_bonus(&self) -> StatIntType {
+        self.initiative_bonus
+    }
+
+    pub fn get_senses(&self) -> &[(Sense, u16)] {
+        &self.senses
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use rand::rngs::mock::StepRng;
+
+    use crate::{assets::asset::Asset, entities::abilities::AbilityScores};
+
+    use super::*;
+
+    #[test]
+    pub fn from_entity() {
+        let assets = AssetManager::from_test_config();
+        let class = assets.get_testing_class();
+        let race = assets.get_testing_race();
+        let mut rng = StepRng::new(5, 1);
+        let entity = Entity::new(String::from("Dave"), class.get_uuid(), race.get_uuid(),
+            AbilityScores::new(10, 12, 10, 14, 8, 10), &assets, &mut rng);
+
+        let summary = PassiveSummary::from_entity(&entity, &assets).unwrap();
+        assert_eq!(summary.get_entity(), entity.get_uuid());
+        assert_eq!(summary.get_name(), "Dave");
+        assert_eq!(summary.get_passive_perception(), 9);
+        assert_eq!(summary.get_passive_investigation(), 12);
+        assert_eq!(summary.get_passive_insight(), 9);
+        assert_eq!(summary.get_initiative_bonus(), 1);
+        assert!(summary.get_senses().is_empty());
+    }
+}
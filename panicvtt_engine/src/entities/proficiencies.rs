@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::skills::SkillAttributes;
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WeaponCategory {
+    Simple,
+    Martial,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ArmorCategory {
+    Light,
+    Medium,
+    Heavy,
+    Shield,
+}
+
+/// Proficiencies outside of skills and saves. Races, classes and Entities each carry a set, and an Entity's
+/// effective proficiencies are the union of all of them.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct Proficiencies {
+    #[serde(default)]
+    tools: BTreeMap<String, SkillAttributes>,
+    #[serde(default)]
+    languages: BTreeSet<String>,
+    #[serde(default)]
+    weapons: BTreeSet<WeaponCategory>,
+    #[serde(default)]
+    armor: BTreeSet<ArmorCategory>,
+}
+
+impl Proficiencies {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Tools the holder has no entry for are used with the Normal attribute.
+    pub fn get_tool_attribute(&self, tool: &str) -> SkillAttributes {
+        self.tools.get(tool).copied().unwrap_or(SkillAttributes::Normal)
+    }
+
+    pub fn get_tools(&self) -> &BTreeMap<String, SkillAttributes> {
+        &self.tools
+    }
+
+    pub fn get_languages(&self) -> &BTreeSet<String> {
+        &self.languages
+    }
+
+    pub fn knows_language(&self, language: &str) -> bool {
+        self.languages.contains(language)
+    }
+
+    pub fn is_proficient_with_weapon(&self, weapon: WeaponCategory) -> bool {
+        self.weapons.contains(&weapon)
+    }
+
+    pub fn is_proficient_with_armor(&self, armor: ArmorCategory) -> bool {
+        self.armor.contains(&armor)
+    }
+
+    /// Set the attribute for a tool, returning the previous one.
+    pub fn set_tool_attribute(&mut self, tool: String, attribute: SkillAttributes) -> SkillAttributes {
+        self.tools.insert(tool, attribute).unwrap_or(SkillAttributes::Normal)
+    }
+
+    pub fn add_language(&mut self, language: String) -> bool {
+        self.languages.insert(language)
+    }
+
+    pub fn add_weapon(&mut self, weapon: WeaponCategory) -> bool {
+        self.weapons.insert(weapon)
+    }
+
+    pub fn add_armor(&mut self, armor: ArmorCategory) -> bool {
+        self.armor.insert(armor)
+    }
+
+    /// Fold another set of proficiencies into this one, keeping the better attribute for shared tools.
+    pub fn merge(&mut self, other: &Proficiencies) {
+        for (tool, attribute) in &other.tools {
+            let entry = self.tools.entry(tool.clone()).or_insert(*attribute);
+            *entry = (*entry).max(*attribute);
+        }
+
+        self.languages.extend(other.languages.iter().cloned());
+        self.weapons.extend(other.weapons.iter().copied());
+        self.armor.extend(other.armor.iter().copied());
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn getters_and_setters() {
+        let mut p = Proficiencies::new();
+        assert_eq!(p.get_tool_attribute("Thieves' Tools"), SkillAttributes::Normal);
+        assert!(!p.knows_language("Elvish"));
+        assert!(!p.is_proficient_with_weapon(WeaponCategory::Simple));
+        assert!(!p.is_proficient_with_armor(ArmorCategory::Shield));
+
+        assert_eq!(p.set_tool_attribute(String::from("Thieves' Tools"), SkillAttributes::Proficient), SkillAttributes::Normal);
+        assert_eq!(p.set_tool_attribute(String::from("Thieves' Tools"), SkillAttributes::Expertise), SkillAttributes::Proficient);
+        assert!(p.add_language(String::from("Elvish")));
+        assert!(!p.add_language(String::from("Elvish")));
+        assert!(p.add_weapon(WeaponCategory::Simple));
+        assert!(p.add_armor(ArmorCategory::Shield));
+
+        assert_eq!(p.get_tool_attribute("Thieves' Tools"), SkillAttributes::Expertise);
+        assert!(p.knows_language("Elvish"));
+        assert!(p.is_proficient_with_weapon(WeaponCategory::Simple));
+        assert!(!p.is_proficient_with_weapon(WeaponCategory::Martial));
+        assert!(p.is_proficient_with_armor(ArmorCategory::Shield));
+    }
+
+    #[test]
+    pub fn merge() {
+        let mut a = Proficiencies::new();
+        a.set_tool_attribute(String::from("Herbalism Kit"), SkillAttributes::Expertise);
+        a.set_tool_attribute(String::from("Lute"), SkillAttributes::HalfProficient);
+        a.add_language(String::from("Common"));
+
+        let mut b = Proficiencies::new();
+        b.set_tool_attribute(String::from("Herbalism Kit"), SkillAttributes::Proficient);
+        b.set_tool_attribute(String::from("Lute"), SkillAttributes::Proficient);
+        b.add_language(String::from("Dwarvish"));
+        b.add_armor(ArmorCategory::Heavy);
+
+        a.merge(&b);
+        assert_eq!(a.get_tool_attribute("Herbalism Kit"), SkillAttributes::Expertise);
+        assert_eq!(a.get_tool_attribute("Lute"), SkillAttributes::Proficient);
+        assert_eq!(a.get_languages().len(), 2);
+        assert!(a.is_proficient_with_armor(ArmorCategory::Heavy));
+    }
+
+    #[test]
+    pub fn serde() {
+        let data = "
+tools:
+  Smith's Tools: Proficient
+languages: [Common, Giant]
+weapons: [Simple, Martial]
+";
+        let p: Proficiencies = serde_yaml::from_str(data).unwrap();
+        assert_eq!(p.get_tool_attribute("Smith's Tools"), SkillAttributes::Proficient);
+        assert!(p.knows_language("Giant"));
+        assert!(p.is_proficient_with_weapon(WeaponCategory::Martial));
+        assert!(!p.is_proficient_with_armor(ArmorCategory::Light));
+    }
+}
@@ -0,0 +1,304 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use strum::{EnumIter, IntoEnumIterator};
+
+/// Coins weigh 1 lb per 50, regardless of denomination.
+pub const COINS_PER_POUND: u32 = 50;
+
+#[derive(Clone, Copy, EnumIter, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Coin {
+    Copper,
+    Silver,
+    Electrum,
+    Gold,
+    Platinum,
+}
+
+impl Coin {
+    pub fn get_value_in_copper(&self) -> u32 {
+        match self {
+            Coin::Copper => 1,
+            Coin::Silver => 10,
+            Coin::Electrum => 50,
+            Coin::Gold => 100,
+            Coin::Platinum => 1000,
+        }
+    }
+
+    /// Denominations change is made in, largest first. Nobody hands out electrum.
+    fn change_denominations() -> [Coin; 4] {
+        [Coin::Platinum, Coin::Gold, Coin::Silver, Coin::Copper]
+    }
+}
+
+impl Display for Coin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", match self {
+            Coin::Copper => "cp",
+            Coin::Silver => "sp",
+            Coin::Electrum => "ep",
+            Coin::Gold => "gp",
+            Coin::Platinum => "pp",
+        })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct Purse {
+    #[serde(default)]
+    cp: u32,
+    #[serde(default)]
+    sp: u32,
+    #[serde(default)]
+    ep: u32,
+    #[serde(default)]
+    gp: u32,
+    #[serde(default)]
+    pp: u32,
+}
+
+impl Purse {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn from_coins(cp: u32, sp: u32, ep: u32, gp: u32, pp: u32) -> Self {
+        Self { cp, sp, ep, gp, pp }
+    }
+
+    /// The fewest coins (without electrum) worth `copper`.
+    pub fn from_copper_value(copper: u32) -> Self {
+        let mut purse = Self::new();
+        let mut remaining = copper;
+        for coin in Coin::change_denominations() {
+            purse.add(coin, remaining / coin.get_value_in_copper());
+            remaining %= coin.get_value_in_copper();
+        }
+
+        purse
+    }
+
+    pub fn get(&self, coin: Coin) -> u32 {
+        match coin {
+            Coin::Copper => self.cp,
+            Coin::Silver => self.sp,
+            Coin::Electrum => self.ep,
+            Coin::Gold => self.gp,
+            Coin::Platinum => self.pp,
+        }
+    }
+
+    fn get_mut(&mut self, coin: Coin) -> &mut u32 {
+        match coin {
+            Coin::Copper => &mut self.cp,
+            Coin::Silver => &mut self.sp,
+            Coin::Electrum => &mut self.ep,
+            Coin::Gold => &mut self.gp,
+            Coin::Platinum => &mut self.pp,
+        }
+    }
+
+    pub fn add(&mut self, coin: Coin, amount: u32) {
+        *self.get_mut(coin) += amount;
+    }
+
+    pub fn add_purse(&mut self, other: &Purse) {
+        for coin in Coin::iter() {
+            self.add(coin, other.get(coin));
+        }
+    }
+
+    /// Counted in u64, since a purse can hold more than u32::MAX copper pieces' worth.
+    pub fn get_value_in_copper(&self) -> u64 {
+        Coin::iter().map(|c| self.get(c) as u64 * c.get_value_in_copper() as u64).sum()
+    }
+
+    pub fn get_coin_count(&self) -> u32 {
+        Coin::iter().map(|c| self.get(c)).sum()
+    }
+
+    /// Weight of the coins in pounds.
+    pub fn get_weight(&self) -> f32 {
+        self.get_coin_count() as f32 / COINS_PER_POUND as f32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.get_coin_count() == 0
+    }
+
+    /// Trade `amount` coins of one denomination for another at a money changer.
+    ///
+    /// Returns false, changing nothing, if there aren't enough coins, they don't convert evenly, or there would be
+    /// more coins than the purse can count.
+    pub fn exchange(&mut self, from: Coin, to: Coin, amount: u32) -> bool {
+        let value = amount as u64 * from.get_value_in_copper() as u64;
+        if self.get(from) < amount || !value.is_multiple_of(to.get_value_in_copper() as u64) {
+            return false;
+        }
+
+        let Some(total) = u32::try_from(value / to.get_value_in_copper() as u64).ok()
+            .and_then(|coins| coins.checked_add(self.get(to))) else {
+            return false;
+        };
+
+        *self.get_mut(from) -= amount;
+        *self.get_mut(to) = total;
+        true
+    }
+
+    /// Pay a price, breaking larger coins and taking change as needed.
+    ///
+    /// Smaller coins are spent first. Returns false, changing nothing, if the purse can't cover the price.
+    pub fn spend(&mut self, coin: Coin, amount: u32) -> bool {
+        let price = amount as u64 * coin.get_value_in_copper() as u64;
+        if self.get_value_in_copper() < price {
+            return false;
+        }
+
+        // Pay as much as possible exactly, smallest coins first
+        let mut remaining = price;
+        for c in Coin::iter() {
+            let value = c.get_value_in_copper() as u64;
+            // Fewer than the coins held, so it fits in a u32
+            let used = (self.get(c) as u64).min(remaining / value) as u32;
+            *self.get_mut(c) -= used;
+            remaining -= used as u64 * value;
+        }
+
+        // Every coin left is now worth more than what's owed, so hand over the smallest and take change
+        if remaining > 0 {
+            let c = Coin::iter().find(|c| self.get(*c) > 0).unwrap();
+            *self.get_mut(c) -= 1;
+            self.add_purse(&Self::from_copper_value(c.get_value_in_copper() - remaining as u32));
+        }
+
+        true
+    }
+
+    /// Split this purse evenly `ways` ways. Each denomination is divided first, then what's left over is pooled
+    /// and divided with change-making.
+    ///
+    /// Returns each share and whatever couldn't be divided.
+    pub fn split(&self, ways: u32) -> (Purse, Purse) {
+        if ways == 0 {
+            return (Purse::new(), self.clone());
+        }
+
+        let mut share = Purse::new();
+        let mut leftover = 0u64;
+        for c in Coin::iter() {
+            share.add(c, self.get(c) / ways);
+            leftover += (self.get(c) % ways) as u64 * c.get_value_in_copper() as u64;
+        }
+
+        // Less than one of each coin per share is left over, and less than `ways` copper after that
+        let ways = ways as u64;
+        share.add_purse(&Self::from_copper_value((leftover / ways) as u32));
+        (share, Self::from_copper_value((leftover % ways) as u32))
+    }
+}
+
+impl Display for Purse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coins: Vec<String> = Coin::iter().rev()
+            .filter(|c| self.get(*c) > 0)
+            .map(|c| format!("{} {}", self.get(c), c))
+            .collect();
+
+        if coins.is_empty() {
+            write!(f, "0 cp")
+        } else {
+            write!(f, "{}", coins.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn values() {
+        let purse = Purse::from_coins(1, 2, 3, 4, 5);
+        assert_eq!(purse.get_value_in_copper(), 1 + 20 + 150 + 400 + 5000);
+        assert_eq!(purse.get_coin_count(), 15);
+        assert_eq!(format!("{}", purse), "5 pp, 4 gp, 3 ep, 2 sp, 1 cp");
+        assert_eq!(format!("{}", Purse::new()), "0 cp");
+
+        assert_eq!(Purse::from_copper_value(1234), Purse::from_coins(4, 3, 0, 2, 1));
+        assert_eq!(Purse::from_coins(0, 0, 0, 100, 0).get_weight(), 2.0);
+    }
+
+    #[test]
+    pub fn exchange() {
+        let mut purse = Purse::from_coins(0, 25, 0, 1, 0);
+        assert!(purse.exchange(Coin::Silver, Coin::Gold, 20));
+        assert_eq!(purse, Purse::from_coins(0, 5, 0, 3, 0));
+
+        assert!(purse.exchange(Coin::Gold, Coin::Copper, 1));
+        assert_eq!(purse, Purse::from_coins(100, 5, 0, 2, 0));
+
+        // Uneven conversions and overdrafts change nothing
+        assert!(!purse.exchange(Coin::Silver, Coin::Gold, 5));
+        assert!(!purse.exchange(Coin::Gold, Coin::Platinum, 3));
+        assert_eq!(purse, Purse::from_coins(100, 5, 0, 2, 0));
+
+        // So do exchanges for more coins than a purse can count
+        let mut hoard = Purse::from_coins(0, 0, 0, 0, 5_000_000);
+        assert!(!hoard.exchange(Coin::Platinum, Coin::Copper, 5_000_000));
+        assert_eq!(hoard, Purse::from_coins(0, 0, 0, 0, 5_000_000));
+        assert!(hoard.exchange(Coin::Platinum, Coin::Gold, 5_000_000));
+        assert_eq!(hoard, Purse::from_coins(0, 0, 0, 50_000_000, 0));
+    }
+
+    #[test]
+    pub fn spend() {
+        // Exact payment
+        let mut purse = Purse::from_coins(5, 3, 0, 0, 0);
+        assert!(purse.spend(Coin::Copper, 35));
+        assert!(purse.is_empty());
+
+        // Breaking a gold piece for a 15 sp bill leaves 5 sp of change
+        let mut purse = Purse::from_coins(0, 10, 0, 1, 0);
+        assert!(purse.spend(Coin::Silver, 15));
+        assert_eq!(purse, Purse::from_coins(0, 5, 0, 0, 0));
+
+        // Breaking platinum for 3 cp
+        let mut purse = Purse::from_coins(0, 0, 0, 0, 1);
+        assert!(purse.spend(Coin::Copper, 3));
+        assert_eq!(purse, Purse::from_coins(7, 9, 0, 9, 0));
+
+        // Can't afford it
+        let mut purse = Purse::from_coins(0, 9, 0, 0, 0);
+        assert!(!purse.spend(Coin::Gold, 1));
+        assert_eq!(purse, Purse::from_coins(0, 9, 0, 0, 0));
+
+        // Hoards worth more than u32::MAX copper
+        let mut hoard = Purse::from_coins(0, 0, 0, 0, 5_000_000);
+        assert_eq!(hoard.get_value_in_copper(), 5_000_000_000);
+        assert!(!hoard.spend(Coin::Platinum, 5_000_001));
+        assert!(hoard.spend(Coin::Platinum, 4_999_999));
+        assert_eq!(hoard, Purse::from_coins(0, 0, 0, 0, 1));
+        assert!(!Purse::new().spend(Coin::Platinum, 5_000_000));
+    }
+
+    #[test]
+    pub fn split() {
+        let loot = Purse::from_coins(0, 0, 0, 10, 0);
+        let (share, remainder) = loot.split(3);
+        assert_eq!(share, Purse::from_coins(3, 3, 0, 3, 0));
+        assert_eq!(remainder, Purse::from_coins(1, 0, 0, 0, 0));
+        assert_eq!(share.get_value_in_copper() * 3 + remainder.get_value_in_copper(), loot.get_value_in_copper());
+
+        let (share, remainder) = loot.split(0);
+        assert!(share.is_empty());
+        assert_eq!(remainder, loot);
+    }
+
+    #[test]
+    pub fn serde() {
+        let purse: Purse = serde_yaml::from_str("gp: 15\nsp: 3").unwrap();
+        assert_eq!(purse, Purse::from_coins(0, 3, 0, 15, 0));
+    }
+}
@@ -3,20 +3,34 @@ use uuid::Uuid;
 
 use crate::assets::asset::Asset;
 
+use super::{modifier::Modifier, proficiencies::Proficiencies};
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Race {
     #[serde(with = "uuid::serde::simple")]
     uuid: Uuid,
+    #[serde(skip)]
+    campaign: Option<u128>,
+    #[serde(default)]
+    overrides: Option<u128>,
     name: String, 
     speed: u8, 
+    #[serde(default)]
+    modifiers: Vec<Modifier>,
+    #[serde(default)]
+    proficiencies: Proficiencies,
 }
 
 impl Race {
     pub fn new(name: String, speed: u8) -> Self {
         Self {
             uuid: Uuid::now_v7(),
+            campaign: None,
+            overrides: None,
             name, 
-            speed
+            speed,
+            modifiers: Vec::new(),
+            proficiencies: Proficiencies::new(),
         }
     }
 
@@ -27,6 +41,24 @@ impl Race {
     pub fn get_speed(&self) -> u8 {
         self.speed
     }
+
+    pub fn with_modifiers(mut self, modifiers: Vec<Modifier>) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub fn get_modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    pub fn with_proficiencies(mut self, proficiencies: Proficiencies) -> Self {
+        self.proficiencies = proficiencies;
+        self
+    }
+
+    pub fn get_proficiencies(&self) -> &Proficiencies {
+        &self.proficiencies
+    }
 }
 
 impl Asset for Race {
@@ -35,7 +67,15 @@ impl Asset for Race {
     }
     
     fn get_owning_campaign(&self) -> Option<u128> {
-        todo!()
+        self.campaign
+    }
+
+    fn set_owning_campaign(&mut self, campaign: Option<u128>) {
+        self.campaign = campaign;
+    }
+
+    fn get_overridden_asset(&self) -> Option<u128> {
+        self.overrides
     }
 }
 
@@ -43,14 +83,20 @@ impl Default for Race {
     fn default() -> Self {
         Self { 
             uuid: Uuid::nil(), 
+            campaign: None,
+            overrides: None,
             name: String::new(), 
-            speed: 0 
+            speed: 0,
+            modifiers: Vec::new(),
+            proficiencies: Proficiencies::new(),
         }
     }
 }
 
 #[cfg(test)]
 pub mod tests {
+    use crate::entities::modifier::{ModifierKind, Stat};
+
     use super::*;
 
     #[test]
@@ -59,5 +105,23 @@ pub mod tests {
 
         assert_eq!(String::from("Syd Barrett"), race.get_name());
         assert_eq!(123, race.get_speed());
+        assert!(race.get_modifiers().is_empty());
+    }
+
+    #[test]
+    pub fn modifiers() {
+        let m = Modifier::new(Stat::Speed, ModifierKind::Flat(5));
+        let race = Race::new(String::from("Wood Elf"), 30).with_modifiers(vec![m]);
+
+        assert_eq!(race.get_modifiers(), [m]);
+    }
+
+    #[test]
+    pub fn proficiencies() {
+        let mut p = Proficiencies::new();
+        p.add_language(String::from("Elvish"));
+        let race = Race::new(String::from("High Elf"), 30).with_proficiencies(p.clone());
+
+        assert_eq!(*race.get_proficiencies(), p);
     }
 }
\ No newline at end of file
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+/// A special sense, granted through modifiers on a race, class, feat or item.
+/// 
+/// An Entity's range with each Sense is computed like any other Stat, so races grant them with a SetTo modifier. 
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Hash, EnumIter)]
+pub enum Sense {
+    Darkvision,
+    Blindsight,
+    Tremorsense,
+    Truesight,
+}
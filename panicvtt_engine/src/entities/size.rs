@@ -0,0 +1,60 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+/// A creature's size category.
+#[derive(Clone, Copy, Default, EnumIter, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Size {
+    Tiny,
+    Small,
+    #[default]
+    Medium,
+    Large,
+    Huge,
+    Gargantuan,
+}
+
+impl Size {
+    /// Carrying capacity scales with size: halved for Tiny, doubled for each size above Medium. 
+    pub fn get_carrying_multiplier(&self) -> f32 {
+        match self {
+            Size::Tiny => 0.5,
+            Size::Small | Size::Medium => 1.0,
+            Size::Large => 2.0,
+            Size::Huge => 4.0,
+            Size::Gargantuan => 8.0,
+        }
+    }
+
+    /// Grid cells along each side of the space a creature controls. Tiny creatures still take a whole cell. 
+    pub fn get_footprint(&self) -> u8 {
+        match self {
+            Size::Tiny | Size::Small | Size::Medium => 1,
+            Size::Large => 2,
+            Size::Huge => 3,
+            Size::Gargantuan => 4,
+        }
+    }
+}
+
+impl Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn ordering() {
+        assert_eq!(Size::default(), Size::Medium);
+        assert!(Size::Tiny < Size::Small);
+        assert!(Size::Huge < Size::Gargantuan);
+        assert_eq!(format!("{}", Size::Large), "Large");
+        assert_eq!(Size::Tiny.get_footprint(), 1);
+        assert_eq!(Size::Gargantuan.get_footprint(), 4);
+    }
+}
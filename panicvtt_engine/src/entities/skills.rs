@@ -1,82 +1,219 @@
-use enum_map::Enum; 
+use std::{collections::BTreeMap, fmt::Display};
 
-use num_derive::FromPrimitive;
-use serde::{Deserialize, Serialize};
-use strum::{EnumCount, EnumIter};
+use serde::{de::{self, MapAccess, Visitor}, Deserialize, Deserializer, Serialize, Serializer};
+use strum::EnumIter;
+use uuid::Uuid;
+
+use crate::assets::asset::Asset;
 
 use super::abilities::Ability;
 
 pub type SkillModifierIntType = i8;
 
-#[repr(u8)]
-#[derive(Debug, Enum, EnumIter, PartialEq, Eq, Hash, Clone, Copy, EnumCount, FromPrimitive)]
-pub enum Skill {
-    Acrobatics      = 0, 
-    AnimalHandling  = 1,
-    Arcana          = 2,
-    Athletics       = 3,
-    Deception       = 4,
-    History         = 5,
-    Insight         = 6, 
-    Intimidation    = 7, 
-    Investigation   = 8, 
-    Medicine        = 9, 
-    Nature          = 10,
-    Perception      = 11,
-    Performance     = 12, 
-    Persuasion      = 13, 
-    Religion        = 14, 
-    SlightOfHand    = 15, 
-    Stealth         = 16, 
-    Survival        = 17, 
+/// Identifies a skill by the UUID of its SkillDefinition.
+///
+/// The standard skills have fixed, well-known IDs; campaigns can define more in `skills/` asset directories.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Skill(u128);
+
+impl Skill {
+    pub const ACROBATICS: Skill        = Skill(1);
+    pub const ANIMAL_HANDLING: Skill   = Skill(2);
+    pub const ARCANA: Skill            = Skill(3);
+    pub const ATHLETICS: Skill         = Skill(4);
+    pub const DECEPTION: Skill         = Skill(5);
+    pub const HISTORY: Skill           = Skill(6);
+    pub const INSIGHT: Skill           = Skill(7);
+    pub const INTIMIDATION: Skill      = Skill(8);
+    pub const INVESTIGATION: Skill     = Skill(9);
+    pub const MEDICINE: Skill          = Skill(10);
+    pub const NATURE: Skill            = Skill(11);
+    pub const PERCEPTION: Skill        = Skill(12);
+    pub const PERFORMANCE: Skill       = Skill(13);
+    pub const PERSUASION: Skill        = Skill(14);
+    pub const RELIGION: Skill          = Skill(15);
+    pub const SLIGHT_OF_HAND: Skill    = Skill(16);
+    pub const STEALTH: Skill           = Skill(17);
+    pub const SURVIVAL: Skill          = Skill(18);
+
+    /// The standard skills, in the order the old positional skill lists used.
+    pub const STANDARD: [Skill; 18] = [
+        Skill::ACROBATICS, Skill::ANIMAL_HANDLING, Skill::ARCANA, Skill::ATHLETICS,
+        Skill::DECEPTION, Skill::HISTORY, Skill::INSIGHT, Skill::INTIMIDATION,
+        Skill::INVESTIGATION, Skill::MEDICINE, Skill::NATURE, Skill::PERCEPTION,
+        Skill::PERFORMANCE, Skill::PERSUASION, Skill::RELIGION, Skill::SLIGHT_OF_HAND,
+        Skill::STEALTH, Skill::SURVIVAL,
+    ];
+
+    const STANDARD_NAMES: [&'static str; 18] = [
+        "Acrobatics", "AnimalHandling", "Arcana", "Athletics",
+        "Deception", "History", "Insight", "Intimidation",
+        "Investigation", "Medicine", "Nature", "Perception",
+        "Performance", "Persuasion", "Religion", "SlightOfHand",
+        "Stealth", "Survival",
+    ];
+
+    const STANDARD_ABILITIES: [Ability; 18] = [
+        Ability::Dexterity, Ability::Wisdom, Ability::Intelligence, Ability::Strength,
+        Ability::Charisma, Ability::Intelligence, Ability::Wisdom, Ability::Charisma,
+        Ability::Intelligence, Ability::Wisdom, Ability::Intelligence, Ability::Wisdom,
+        Ability::Charisma, Ability::Charisma, Ability::Intelligence, Ability::Dexterity,
+        Ability::Dexterity, Ability::Wisdom,
+    ];
+
+    pub fn from_uuid(uuid: u128) -> Self {
+        Self(uuid)
+    }
+
+    pub fn get_uuid(&self) -> u128 {
+        self.0
+    }
+
+    /// Look up a standard skill by name in any case, with or without underscores, e.g. "SlightOfHand" or "slight_of_hand".
+    pub fn from_standard_name(name: &str) -> Option<Self> {
+        let name: String = name.chars().filter(|c| *c != '_').collect();
+        Self::STANDARD_NAMES.iter().position(|n| n.eq_ignore_ascii_case(&name)).map(|i| Self::STANDARD[i])
+    }
+
+    /// A standard skill's name in snake_case, as `EnumMap` writes its keys.
+    fn get_standard_key(&self) -> Option<String> {
+        let name = Self::STANDARD_NAMES[self.get_standard_index()?];
+        let mut key = String::with_capacity(name.len() + 2);
+        for (i, c) in name.char_indices() {
+            if i > 0 && c.is_ascii_uppercase() {
+                key.push('_');
+            }
+            key.push(c.to_ascii_lowercase());
+        }
+        Some(key)
+    }
+
+    fn get_standard_index(&self) -> Option<usize> {
+        Self::STANDARD.iter().position(|s| s == self)
+    }
+}
+
+impl Display for Skill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.get_standard_index() {
+            Some(i) => write!(f, "{}", Self::STANDARD_NAMES[i]),
+            None => write!(f, "skill {:x}", self.0),
+        }
+    }
 }
 
-impl From<Skill> for usize {
-    fn from(val: Skill) -> Self {
-        val as usize
+/// Standard skills serialize by name so saved files stay hand-editable; homebrew skills serialize by UUID. 
+impl Serialize for Skill {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S : Serializer {
+        match self.get_standard_key() {
+            Some(key) => serializer.serialize_str(&key),
+            None => serializer.serialize_u128(self.0),
+        }
     }
 }
 
-impl From<usize> for Skill {
-    fn from(value: usize) -> Self {
-        num::FromPrimitive::from_usize(value).unwrap()
+/// Skills deserialize from their UUID, or from a standard skill's name so that hand-written assets stay readable.
+impl<'de> Deserialize<'de> for Skill {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D : Deserializer<'de> {
+        deserializer.deserialize_any(SkillVisitor)
     }
 }
 
-pub type SkillIntType = u8;
+struct SkillVisitor;
 
-impl Skill { 
-    pub fn get_ability(&self) -> Ability {
-        match self {
-            Skill::Acrobatics => Ability::Dexterity,
-            Skill::AnimalHandling => Ability::Wisdom,
-            Skill::Arcana => Ability::Intelligence,
-            Skill::Athletics => Ability::Strength,
-            Skill::Deception => Ability::Charisma,
-            Skill::History => Ability::Intelligence,
-            Skill::Insight => Ability::Wisdom,
-            Skill::Intimidation => Ability::Charisma,
-            Skill::Investigation => Ability::Intelligence,
-            Skill::Medicine => Ability::Wisdom,
-            Skill::Nature => Ability::Intelligence,
-            Skill::Perception => Ability::Wisdom,
-            Skill::Performance => Ability::Charisma,
-            Skill::Persuasion => Ability::Charisma,
-            Skill::Religion => Ability::Intelligence,
-            Skill::SlightOfHand => Ability::Dexterity,
-            Skill::Stealth => Ability::Dexterity,
-            Skill::Survival => Ability::Wisdom,
+impl<'de> Visitor<'de> for SkillVisitor {
+    type Value = Skill;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a skill UUID or standard skill name")
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> where E : de::Error {
+        Ok(Skill(v as u128))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E> where E : de::Error {
+        Ok(Skill(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> where E : de::Error {
+        Skill::from_standard_name(v).ok_or_else(|| E::custom(format!("unknown standard skill {}", v)))
+    }
+}
+
+/// Defines a skill: its display name and the ability that governs it.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct SkillDefinition {
+    #[serde(with = "uuid::serde::simple")]
+    uuid: Uuid,
+    #[serde(skip)]
+    campaign: Option<u128>,
+    #[serde(default)]
+    overrides: Option<u128>,
+    name: String,
+    ability: Ability,
+}
+
+impl SkillDefinition {
+    pub fn new(name: String, ability: Ability) -> Self {
+        Self {
+            uuid: Uuid::now_v7(),
+            campaign: None,
+            overrides: None,
+            name,
+            ability
         }
     }
+
+    /// The definitions of the standard skills, which are always loaded.
+    pub fn standard() -> Vec<Self> {
+        Skill::STANDARD.iter().enumerate().map(|(i, skill)| Self {
+            uuid: Uuid::from_u128(skill.get_uuid()),
+            campaign: None,
+            overrides: None,
+            name: String::from(Skill::STANDARD_NAMES[i]),
+            ability: Skill::STANDARD_ABILITIES[i],
+        }).collect()
+    }
+
+    pub fn get_skill(&self) -> Skill {
+        Skill(self.uuid.as_u128())
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_ability(&self) -> Ability {
+        self.ability
+    }
+}
+
+impl Asset for SkillDefinition {
+    fn get_uuid(&self) -> u128 {
+        self.uuid.as_u128()
+    }
+
+    fn get_owning_campaign(&self) -> Option<u128> {
+        self.campaign
+    }
+
+    fn set_owning_campaign(&mut self, campaign: Option<u128>) {
+        self.campaign = campaign;
+    }
+
+    fn get_overridden_asset(&self) -> Option<u128> {
+        self.overrides
+    }
 }
 
 
-#[derive(Debug, Clone, Copy, EnumIter, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, EnumIter, Serialize, Deserialize, Eq, PartialEq, PartialOrd, Ord)]
 pub enum SkillAttributes {
-    Normal          = 0, 
-    HalfProficient  = 1,   
-    Proficient      = 2, 
-    Expertise       = 3, 
+    Normal          = 0,
+    HalfProficient  = 1,
+    Proficient      = 2,
+    Expertise       = 3,
 }
 
 impl SkillAttributes {
@@ -89,3 +226,152 @@ impl SkillAttributes {
         }
     }
 }
+
+/// Deserialize an Entity's skill attributes keyed by skill, also accepting the old positional format
+/// (`map: [Normal, HalfProficient, ...]`) in standard skill order.
+pub(crate) fn deserialize_skill_attributes<'de, D>(deserializer: D) -> Result<BTreeMap<Skill, SkillAttributes>, D::Error> where D : Deserializer<'de> {
+    // A key is either a skill or the `map` field of the positional format 
+    enum Key {
+        Positional,
+        Skill(Skill),
+    }
+
+    impl<'de> Deserialize<'de> for Key {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D : Deserializer<'de> {
+            struct KeyVisitor;
+
+            impl<'de> Visitor<'de> for KeyVisitor {
+                type Value = Key;
+
+                fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    SkillVisitor.expecting(formatter)
+                }
+
+                fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> where E : de::Error {
+                    SkillVisitor.visit_u64(v).map(Key::Skill)
+                }
+
+                fn visit_u128<E>(self, v: u128) -> Result<Self::Value, E> where E : de::Error {
+                    SkillVisitor.visit_u128(v).map(Key::Skill)
+                }
+
+                fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> where E : de::Error {
+                    if v == "map" { Ok(Key::Positional) } else { SkillVisitor.visit_str(v).map(Key::Skill) }
+                }
+            }
+
+            deserializer.deserialize_any(KeyVisitor)
+        }
+    }
+
+    struct SkillAttributesVisitor;
+
+    impl<'de> Visitor<'de> for SkillAttributesVisitor {
+        type Value = BTreeMap<Skill, SkillAttributes>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a map of skills to attributes")
+        }
+
+        fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error> where A : MapAccess<'de> {
+            let mut skills = BTreeMap::new();
+            while let Some(key) = access.next_key::<Key>()? {
+                match key {
+                    Key::Positional => {
+                        let map: Vec<SkillAttributes> = access.next_value()?;
+                        if map.len() > Skill::STANDARD.len() {
+                            return Err(de::Error::invalid_length(map.len(), &self));
+                        }
+                        skills.extend(Skill::STANDARD.iter().copied().zip(map));
+                    },
+                    Key::Skill(skill) => { skills.insert(skill, access.next_value()?); },
+                }
+            }
+
+            Ok(skills)
+        }
+    }
+
+    deserializer.deserialize_map(SkillAttributesVisitor)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn standard_skills() {
+        let standard = SkillDefinition::standard();
+        assert_eq!(standard.len(), 18);
+
+        for (definition, skill) in standard.iter().zip(Skill::STANDARD) {
+            assert_eq!(definition.get_skill(), skill);
+        }
+
+        assert_eq!(standard[0].get_name(), "Acrobatics");
+        assert_eq!(standard[0].get_ability(), Ability::Dexterity);
+        assert_eq!(standard[3].get_ability(), Ability::Strength);
+        assert_eq!(standard[17].get_name(), "Survival");
+
+        assert_eq!(Skill::from_standard_name("SlightOfHand"), Some(Skill::SLIGHT_OF_HAND));
+        assert_eq!(Skill::from_standard_name("slight_of_hand"), Some(Skill::SLIGHT_OF_HAND));
+        assert_eq!(Skill::from_standard_name("STEALTH"), Some(Skill::STEALTH));
+        assert_eq!(Skill::from_standard_name("Cooking"), None);
+        assert_eq!(format!("{}", Skill::STEALTH), "Stealth");
+        assert_eq!(format!("{}", Skill::from_uuid(0xc00c)), "skill c00c");
+    }
+
+    #[test]
+    pub fn custom_skill() {
+        let cooking = SkillDefinition::new(String::from("Cooking"), Ability::Wisdom);
+        assert_eq!(cooking.get_name(), "Cooking");
+        assert_eq!(cooking.get_ability(), Ability::Wisdom);
+        assert_eq!(cooking.get_skill().get_uuid(), cooking.get_uuid());
+        assert!(!Skill::STANDARD.contains(&cooking.get_skill()));
+    }
+
+    #[test]
+    pub fn skill_serde() {
+        let skills: Vec<Skill> = serde_yaml::from_str("[Stealth, 17, 49164]").unwrap();
+        assert_eq!(skills, [Skill::STEALTH, Skill::STEALTH, Skill::from_uuid(0xc00c)]);
+        assert!(serde_yaml::from_str::<Skill>("Cooking").is_err());
+
+        assert_eq!(serde_yaml::to_string(&Skill::STEALTH).unwrap(), "stealth\n");
+        assert_eq!(serde_yaml::to_string(&Skill::ANIMAL_HANDLING).unwrap(), "animal_handling\n");
+        assert_eq!(serde_yaml::to_string(&Skill::from_uuid(0xc00c)).unwrap(), "49164\n");
+    }
+
+    #[test]
+    pub fn skill_attributes_serde() {
+        #[derive(Deserialize)]
+        struct Holder {
+            #[serde(deserialize_with = "deserialize_skill_attributes")]
+            skills: BTreeMap<Skill, SkillAttributes>,
+        }
+
+        // Old positional format
+        let h: Holder = serde_yaml::from_str("skills:\n  map:\n  - Normal\n  - HalfProficient\n  - Proficient\n").unwrap();
+        assert_eq!(h.skills.get(&Skill::ACROBATICS), Some(&SkillAttributes::Normal));
+        assert_eq!(h.skills.get(&Skill::ANIMAL_HANDLING), Some(&SkillAttributes::HalfProficient));
+        assert_eq!(h.skills.get(&Skill::ARCANA), Some(&SkillAttributes::Proficient));
+        assert_eq!(h.skills.get(&Skill::STEALTH), None);
+
+        // Positional lists can't be longer than the standard skills 
+        let data = format!("skills:\n  map:\n{}", "  - Normal\n".repeat(19));
+        let e = serde_yaml::from_str::<Holder>(&data).err().unwrap();
+        assert!(e.to_string().contains("invalid length 19"));
+
+        // Keyed format
+        let h: Holder = serde_yaml::from_str("skills:\n  17: Expertise\n  49164: Proficient\n  animal_handling: Normal\n").unwrap();
+        assert_eq!(h.skills.get(&Skill::STEALTH), Some(&SkillAttributes::Expertise));
+        assert_eq!(h.skills.get(&Skill::ANIMAL_HANDLING), Some(&SkillAttributes::Normal));
+        assert_eq!(h.skills.get(&Skill::from_uuid(0xc00c)), Some(&SkillAttributes::Proficient));
+
+        // Homebrew skills with full-width UUIDs survive a round trip 
+        let homebrew = SkillDefinition::new(String::from("Cooking"), Ability::Wisdom).get_skill();
+        let skills = BTreeMap::from([(homebrew, SkillAttributes::Expertise), (Skill::ARCANA, SkillAttributes::HalfProficient)]);
+        let data = format!("skills:\n{}", serde_yaml::to_string(&skills).unwrap().lines().map(|l| format!("  {}\n", l)).collect::<String>());
+        let h: Holder = serde_yaml::from_str(&data).unwrap();
+        assert_eq!(h.skills, skills);
+    }
+}
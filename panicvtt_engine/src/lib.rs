@@ -7,7 +7,7 @@ pub mod campaigns;
 pub mod entities;
 pub mod engine;
 mod mechanics;
-mod util;
+pub mod util;
 mod version;
 
 pub fn version() -> &'static str {
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
@@ -28,8 +30,96 @@ impl Dice {
     pub fn max(&self) -> u8 {
         *self as u8
     }
+
+    pub fn from_sides(sides: u8) -> Option<Self> {
+        match sides {
+            100 => Some(Dice::D100),
+            20 => Some(Dice::D20),
+            12 => Some(Dice::D12),
+            10 => Some(Dice::D10),
+            8 => Some(Dice::D8),
+            6 => Some(Dice::D6),
+            4 => Some(Dice::D4),
+            _ => None,
+        }
+    }
+}
+
+/// A dice expression such as `10d10+30`. Serialized in that form. 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DiceFormula {
+    count: u8,
+    die: Dice,
+    bonus: i16,
+}
+
+impl DiceFormula {
+    pub fn new(count: u8, die: Dice, bonus: i16) -> Self {
+        Self { count, die, bonus }
+    }
+
+    pub fn get_count(&self) -> u8 {
+        self.count
+    }
+
+    pub fn get_die(&self) -> Dice {
+        self.die
+    }
+
+    pub fn get_bonus(&self) -> i16 {
+        self.bonus
+    }
+
+    /// Roll the formula. Results below 1 are raised to 1. 
+    pub fn roll(&self, rng: &mut Rng) -> u16 {
+        let total: i32 = (0..self.count).map(|_| self.die.roll(rng) as i32).sum::<i32>() + self.bonus as i32;
+        total.clamp(1, u16::MAX as i32) as u16
+    }
+
+    /// The rounded-down average, as printed in stat blocks. 
+    pub fn average(&self) -> u16 {
+        let average = (self.count as i32 * (self.die.max() as i32 + 1)) / 2 + self.bonus as i32;
+        average.clamp(1, u16::MAX as i32) as u16
+    }
 }
 
+impl TryFrom<String> for DiceFormula {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let error = || format!("invalid dice formula {}", value);
+        let compact: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+
+        // Split off the bonus, keeping its sign 
+        let (dice, bonus) = match compact.find(['+', '-']) {
+            Some(i) => (&compact[..i], compact[i..].trim_start_matches('+').parse::<i16>().map_err(|_| error())?),
+            None => (compact.as_str(), 0),
+        };
+
+        let (count, sides) = dice.split_once('d').ok_or_else(error)?;
+        let count = if count.is_empty() { 1 } else { count.parse::<u8>().map_err(|_| error())? };
+        let die = sides.parse::<u8>().ok().and_then(Dice::from_sides).ok_or_else(error)?;
+
+        Ok(Self::new(count, die, bonus))
+    }
+}
+
+impl From<DiceFormula> for String {
+    fn from(value: DiceFormula) -> Self {
+        value.to_string()
+    }
+}
+
+impl Display for DiceFormula {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}d{}", self.count, self.die.max())?;
+        match self.bonus {
+            0 => Ok(()),
+            b => write!(f, "{:+}", b),
+        }
+    }
+}
 
 // pub fn roll_nonstandard(lower: u8, upper: u8) -> u8 {
 //     rand::thread_rng().gen_range(lower..=upper)
@@ -73,6 +163,31 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn formula() {
+        let f = DiceFormula::try_from(String::from("10d10+30")).unwrap();
+        assert_eq!(f, DiceFormula::new(10, Dice::D10, 30));
+        assert_eq!(f.average(), 85);
+        assert_eq!(f.to_string(), "10d10+30");
+
+        let f = DiceFormula::try_from(String::from("2d6 - 1")).unwrap();
+        assert_eq!(f, DiceFormula::new(2, Dice::D6, -1));
+        assert_eq!(f.average(), 6);
+        assert_eq!(f.to_string(), "2d6-1");
+
+        assert_eq!(DiceFormula::try_from(String::from("d8")).unwrap(), DiceFormula::new(1, Dice::D8, 0));
+        assert!(DiceFormula::try_from(String::from("3d7")).is_err());
+        assert!(DiceFormula::try_from(String::from("banana")).is_err());
+
+        // StepRng(0, 1) rolls 1, 2, 3, ... 
+        let mut rng = StepRng::new(0, 1);
+        assert_eq!(DiceFormula::new(3, Dice::D6, 2).roll(&mut rng), 1 + 2 + 3 + 2);
+        assert_eq!(DiceFormula::new(1, Dice::D4, -10).roll(&mut rng), 1);
+
+        let de: DiceFormula = serde_yaml::from_str("4d8+4").unwrap();
+        assert_eq!(serde_yaml::to_string(&de).unwrap(), "4d8+4\n");
+    }
+
     // #[test]
     // pub fn roll_nonstandard_bounds() {
     //     for lower in 0..u8::MAX - 1 {
@@ -1,2 +1,5 @@
 pub(crate) mod asset_key_error;
-pub(crate) mod enum_map;
\ No newline at end of file
+pub mod dangling_reference;
+pub mod duplicate_asset_error;
+pub(crate) mod enum_map;
+pub(crate) mod simple_uuids;
\ No newline at end of file
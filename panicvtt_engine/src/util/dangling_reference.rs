@@ -0,0 +1,53 @@
+use std::{fmt::Display, path::PathBuf};
+
+/// A reference from one Asset to another that isn't loaded, or can't be seen from where the reference is made. 
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DanglingReference {
+    path: Option<PathBuf>,
+    asset: u128,
+    field: String,
+    target: u128,
+    loaded: bool,
+}
+
+impl DanglingReference {
+    pub fn new(path: Option<PathBuf>, asset: u128, field: String, target: u128, loaded: bool) -> Self {
+        Self { path, asset, field, target, loaded }
+    }
+
+    /// The file the referring Asset was read from, or None if it hasn't been saved yet. 
+    pub fn get_path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    pub fn get_asset(&self) -> u128 {
+        self.asset
+    }
+
+    /// Where in the referring Asset the reference is, such as `class` or `scenes.<uuid>.tokens`. 
+    pub fn get_field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn get_target(&self) -> u128 {
+        self.target
+    }
+
+    /// Whether the target is loaded, but belongs to a Campaign the referring Asset can't see. 
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+}
+
+impl Display for DanglingReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}", path.display())?,
+            None => write!(f, "(unsaved {:032x})", self.asset)?,
+        }
+        match self.loaded {
+            true => write!(f, ": {} refers to {:032x}, which belongs to another Campaign", self.field, self.target),
+            false => write!(f, ": {} refers to {:032x}, which is not loaded", self.field, self.target),
+        }
+    }
+}
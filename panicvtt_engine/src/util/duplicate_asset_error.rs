@@ -0,0 +1,44 @@
+use std::{error::Error, fmt::Display, path::PathBuf};
+
+/// Two Asset files of the same kind share a UUID. Unlike `AssetKeyError`, which borrows the new Asset and the map 
+/// it clashed with, this owns everything it reports, so it can be wrapped in an `io::Error` while loading and kept 
+/// after the load has finished. 
+#[derive(Debug)]
+pub struct DuplicateAssetError {
+    message: String, 
+    uuid: u128, 
+    first_path: PathBuf, 
+    second_path: PathBuf, 
+}
+
+impl DuplicateAssetError {
+    pub fn new(message: String, uuid: u128, first_path: PathBuf, second_path: PathBuf) -> Self {
+        Self { message, uuid, first_path, second_path }
+    }
+
+    pub fn get_uuid(&self) -> u128 {
+        self.uuid
+    }
+
+    /// The file read first, in path order. 
+    pub fn get_first_path(&self) -> &PathBuf {
+        &self.first_path
+    }
+
+    pub fn get_second_path(&self) -> &PathBuf {
+        &self.second_path
+    }
+}
+
+impl Display for DuplicateAssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "\t(UUID {:032x})", self.uuid)?;
+        writeln!(f, "\tFirst found in: {}", self.first_path.display())?;
+        writeln!(f, "\tAlso found in: {}", self.second_path.display())
+    }
+}
+
+impl Error for DuplicateAssetError {
+
+}
@@ -1,27 +1,22 @@
 use std::ops::{Index, IndexMut};
 
-use serde::{Deserialize, Serialize};
+use serde::{de::{MapAccess, Visitor}, ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
 use strum::{EnumCount, IntoEnumIterator};
 
-#[derive(Serialize, Deserialize, Eq, PartialEq, Debug)]
+/// A value for every variant of an enum. 
+/// 
+/// Serializes as a map keyed by variant name (`strength: Proficient`). Keys left out when deserializing get the default value, 
+/// and the old positional format (`map: [Normal, Proficient, ...]`) still loads. 
+#[derive(Eq, PartialEq, Debug)]
 pub struct EnumMap<E, V> {
     map: Vec<V>, 
-    #[serde(skip_serializing, skip_deserializing)]
     _e: std::marker::PhantomData<E>
 }
 
 impl<E, V> EnumMap<E, V> where E : EnumCount + IntoEnumIterator, V : Clone { 
 
     pub fn from_value(initial: V) -> Self {
-        let mut v = Vec::with_capacity(E::COUNT);
-        for _ in E::iter() {
-            v.push(initial.clone());
-        }
-
-        Self {
-            map: v, 
-            _e: Default::default()
-        }
+        Self::from_fn(|_| initial.clone())
     }
 
     pub fn from_fn<F>(cb: F) -> Self where F : Fn(E) -> V {
@@ -92,163 +87,122 @@ impl<E, V> Iterator for EnumMapIter<E, V> where
         }
     }
 
-} 
+}
 
+impl<E, V> Serialize for EnumMap<E, V> where E : IntoEnumIterator + Into<&'static str>, V : Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S : Serializer {
+        let mut state = serializer.serialize_map(Some(self.map.len()))?;
+        for (e, v) in E::iter().zip(&self.map) {
+            let key: &'static str = e.into();
+            state.serialize_entry(key, v)?;
+        }
 
-// impl<K, V> IntoIterator for SerdeEnumMap<K, V>
-//     where K : enum_map::EnumArray<V>, K : Enum, V : Clone {
-//     type Item = (K, V);
+        state.end()
+    }
+}
 
-//     type IntoIter = IntoIter<K, V>;
-    
-//     fn into_iter(self) -> Self::IntoIter {
-//         self.map.into_iter()
-//     }
-// }
-
-// use std::ops::{Index, IndexMut};
-
-// use enum_map::{Enum, EnumMap, IntoIter, Iter, IterMut};
-// use serde::de::Visitor;
-// use serde::{Deserialize, Serialize};
-// use serde::ser::SerializeStruct;
-
-// pub struct SerdeEnumMap<K, V> 
-//     where K : enum_map::EnumArray<V> + Enum, V : Clone {
-//     map: EnumMap<K, V>,
-// }
-
-// impl<K, V> SerdeEnumMap<K, V>
-//     where K : enum_map::EnumArray<V> + Enum, V : Clone {
-//     pub fn from_default_value(v: V) -> Self {
-//         Self {
-//             map: EnumMap::from_fn(|_| v.clone())
-//         }
-//     }
-
-//     pub fn from_fn<F>(f: F) -> Self 
-//     where F : Fn(K) -> V {
-//         Self {
-//             map: EnumMap::from_fn(f)
-//         }
-//     }
-
-//     pub fn iter(&self) -> Iter<K, V> {
-//         self.map.iter()
-//     }
-
-//     pub fn iter_mut(&mut self) -> IterMut<K, V> {
-//         self.map.iter_mut()
-//     }
-// }
-
-// impl<K, V> Index<K> for SerdeEnumMap<K, V> 
-//     where K : enum_map::EnumArray<V>, K : Enum, V : Clone {
-//     type Output = V;
-
-//     fn index(&self, index: K) -> &Self::Output {
-//         &self.map[index]
-//     }
-// }
-
-// impl<K, V> IndexMut<K> for SerdeEnumMap<K, V> 
-//     where K : enum_map::EnumArray<V>, K : Enum, V : Clone {
-
-//     fn index_mut(&mut self, index: K) -> &mut Self::Output {
-//         &mut self.map[index]
-//     }
-// }
-
-// impl<K, V> IntoIterator for SerdeEnumMap<K, V>
-//     where K : enum_map::EnumArray<V>, K : Enum, V : Clone {
-//     type Item = (K, V);
-
-//     type IntoIter = IntoIter<K, V>;
-    
-//     fn into_iter(self) -> Self::IntoIter {
-//         self.map.into_iter()
-//     }
-// }
-
-
-// impl<K, V> Serialize for SerdeEnumMap<K, V>
-//     where K : enum_map::EnumArray<V>, K : Enum, K : Into<&'static str>, V : Clone, V : Serialize {
-
-//     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-//         where S: serde::Serializer {
-//             let mut state = serializer.serialize_struct("map", self.map.len())?;
-            
-//             for (k, v) in &self.map {
-//                 let key: &'static str = k.into();
-//                 state.serialize_field(key, v)?;
-//             }
-
-//             state.end()
-//         }
-// }
-
-// struct DesVisitor<K, V>    
-//     where K : enum_map::EnumArray<V>, K : Enum, K : Into<&'static str>, V : Clone, V : Serialize {
-
-//     _k: std::marker::PhantomData<K>,
-//     _v: std::marker::PhantomData<V>
-// }  
-
-// impl<K, V> DesVisitor<K, V> 
-//     where K : enum_map::EnumArray<V>, K : Enum, K : Into<&'static str>, V : Clone, V : Serialize {
-
-//     fn new() -> Self {
-//         Self {
-//             _k: Default::default(),
-//             _v: Default::default()
-//         }
-//     }
-// }
-
-// impl<'de, K, V> Visitor<'de> for DesVisitor<K, V> 
-//     where K : enum_map::EnumArray<V>, K : Enum, K : Into<&'static str>, K : Deserialize<'de>, V : Clone, V : Serialize, V : Deserialize<'de>, V: Default {
-
-//     type Value = SerdeEnumMap<K, V>;
-
-//     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-//         formatter.write_str("idk")
-//     }
-
-//     fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-//         where
-//             A: serde::de::MapAccess<'de>, {
-//         let mut em: SerdeEnumMap<K, V> = SerdeEnumMap::from_default_value(V::default());
-
-//         while let Some(key) = map.next_key::<K>()? {
-//             em[key] = map.next_value()?;
-//         }
-
-//         Ok(em)
-//     }
-// }
-
-
-// impl<'de, K, V> Deserialize<'de> for SerdeEnumMap<K, V>
-//     where K : enum_map::EnumArray<V>, K : Enum, V : Clone {
-
-//     fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
-//         where D: serde::Deserializer<'de> {
-//         let fields: Vec<&str> = std::vec::Vec::new();
-//         _deserializer.deserialize_struct("SerdeEnumMap", &fields, DesVisitor::new())
-//     }
-// }
-
-// #[cfg(test)]
-// pub mod tests {
-//     use crate::entities::skills::Skill;
-
-//     use super::*;
-
-//     #[test]
-//     pub fn test() {
-//         let map: SerdeEnumMap<Skill, i32> = SerdeEnumMap::from_default_value(0);
-//         let s = serde_yaml::to_string(&map);
-//         println!("{}", s.unwrap());
-//         panic!("")
-//     }
-// }
\ No newline at end of file
+struct EnumMapVisitor<E, V> {
+    _e: std::marker::PhantomData<E>,
+    _v: std::marker::PhantomData<V>,
+}
+
+impl<'de, E, V> Visitor<'de> for EnumMapVisitor<E, V> 
+    where E : EnumCount + IntoEnumIterator + Into<&'static str> + Into<usize> + Copy, V : Clone + Default + Deserialize<'de> {
+    type Value = EnumMap<E, V>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a map keyed by variant name")
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error> where A : MapAccess<'de> {
+        let mut em: EnumMap<E, V> = EnumMap::from_value(V::default());
+
+        while let Some(key) = access.next_key::<String>()? {
+            // Positional format from before maps were keyed 
+            if key == "map" {
+                let values: Vec<V> = access.next_value()?;
+                if values.len() > E::COUNT {
+                    return Err(serde::de::Error::invalid_length(values.len(), &self));
+                }
+                
+                for (e, v) in E::iter().zip(values) {
+                    em[e] = v;
+                }
+                continue;
+            }
+
+            // Accept hand-written keys regardless of case 
+            let variant = E::iter().find(|e| {
+                let name: &'static str = (*e).into();
+                name.eq_ignore_ascii_case(&key)
+            });
+
+            match variant {
+                Some(e) => { em[e] = access.next_value()?; },
+                None => {
+                    let names: Vec<&'static str> = E::iter().map(|e| e.into()).collect();
+                    return Err(serde::de::Error::custom(format!("unknown key `{}`, expected one of {}", key, names.join(", "))));
+                }
+            }
+        }
+
+        Ok(em)
+    }
+}
+
+impl<'de, E, V> Deserialize<'de> for EnumMap<E, V> 
+    where E : EnumCount + IntoEnumIterator + Into<&'static str> + Into<usize> + Copy, V : Clone + Default + Deserialize<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D : Deserializer<'de> {
+        deserializer.deserialize_map(EnumMapVisitor { _e: Default::default(), _v: Default::default() })
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::entities::abilities::{Ability, SaveAttributes};
+
+    use super::*;
+
+    #[test]
+    pub fn serialize_keyed() {
+        let mut map: EnumMap<Ability, SaveAttributes> = EnumMap::from_value(SaveAttributes::Normal);
+        map[Ability::Dexterity] = SaveAttributes::Proficient;
+
+        let s = serde_yaml::to_string(&map).unwrap();
+        assert_eq!(s, "strength: Normal\ndexterity: Proficient\nconstitution: Normal\nintelligence: Normal\nwisdom: Normal\ncharisma: Normal\n");
+
+        let de: EnumMap<Ability, SaveAttributes> = serde_yaml::from_str(&s).unwrap();
+        assert_eq!(de, map);
+    }
+
+    #[test]
+    pub fn deserialize_omitted_keys() {
+        let de: EnumMap<Ability, SaveAttributes> = serde_yaml::from_str("wisdom: Proficient\nCharisma: Proficient\n").unwrap();
+        assert_eq!(de[Ability::Strength], SaveAttributes::Normal);
+        assert_eq!(de[Ability::Wisdom], SaveAttributes::Proficient);
+        assert_eq!(de[Ability::Charisma], SaveAttributes::Proficient);
+
+        let de: EnumMap<Ability, SaveAttributes> = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(de, EnumMap::from_value(SaveAttributes::Normal));
+    }
+
+    #[test]
+    pub fn deserialize_positional() {
+        let de: EnumMap<Ability, SaveAttributes> = serde_yaml::from_str("map: [Normal, Proficient, Normal, Normal, Normal, Proficient]").unwrap();
+        assert_eq!(de[Ability::Dexterity], SaveAttributes::Proficient);
+        assert_eq!(de[Ability::Charisma], SaveAttributes::Proficient);
+        assert_eq!(de[Ability::Wisdom], SaveAttributes::Normal);
+
+        // Short lists leave the rest at their defaults; long lists are rejected 
+        let de: EnumMap<Ability, SaveAttributes> = serde_yaml::from_str("map: [Proficient]").unwrap();
+        assert_eq!(de[Ability::Strength], SaveAttributes::Proficient);
+        assert_eq!(de[Ability::Charisma], SaveAttributes::Normal);
+        assert!(serde_yaml::from_str::<EnumMap<Ability, SaveAttributes>>("map: [Normal, Normal, Normal, Normal, Normal, Normal, Normal]").is_err());
+    }
+
+    #[test]
+    pub fn deserialize_unknown_key() {
+        let e = serde_yaml::from_str::<EnumMap<Ability, SaveAttributes>>("stealth: Proficient").unwrap_err();
+        assert!(e.to_string().contains("unknown key `stealth`"));
+    }
+}
@@ -0,0 +1,29 @@
+//! Serde helpers writing lists of UUIDs in the simple format, like `uuid::serde::simple` does for a single UUID. 
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use uuid::{fmt::Simple, Uuid};
+
+pub(crate) fn serialize<S>(uuids: &[Uuid], serializer: S) -> Result<S::Ok, S::Error> where S : Serializer {
+    serializer.collect_seq(uuids.iter().copied().map(Uuid::simple))
+}
+
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Uuid>, D::Error> where D : Deserializer<'de> {
+    Ok(Vec::<Simple>::deserialize(deserializer)?.into_iter().map(Uuid::from).collect())
+}
+
+/// The same, for a map whose values are lists of UUIDs. 
+pub(crate) mod map {
+    use super::*;
+
+    pub(crate) fn serialize<S>(map: &BTreeMap<String, Vec<Uuid>>, serializer: S) -> Result<S::Ok, S::Error> where S : Serializer {
+        let map: BTreeMap<&String, Vec<Simple>> = map.iter().map(|(k, v)| (k, v.iter().copied().map(Uuid::simple).collect())).collect();
+        map.serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<BTreeMap<String, Vec<Uuid>>, D::Error> where D : Deserializer<'de> {
+        let map = BTreeMap::<String, Vec<Simple>>::deserialize(deserializer)?;
+        Ok(map.into_iter().map(|(k, v)| (k, v.into_iter().map(Uuid::from).collect())).collect())
+    }
+}
@@ -80,26 +80,35 @@ fn define_initial_state(state_path: Option<&str>, state: &mut PanicState) -> Vec
 
 
 #[main]
-async fn main() -> Result<(), rocket::Error> {
+async fn main() -> Result<(), Box<rocket::Error>> {
     let Ok(args) = PanicArgs::new(std::env::args()) else {
         eprintln!("Usage: panicvtt_server <asset_root>");
-        return Err(rocket::Error::from(rocket::error::ErrorKind::Io(
+        return Err(Box::new(rocket::Error::from(rocket::error::ErrorKind::Io(
             std::io::Error::from(std::io::ErrorKind::InvalidInput)
-        )));
+        ))));
     };
 
     let mut state = PanicState::new(panicvtt_engine::initialize(Path::new(&args.asset_root)));
+    for reference in state.engine.validate_assets() {
+        eprintln!("Dangling reference: {}", reference);
+    }
+    for duplicate in state.engine.get_duplicates() {
+        eprintln!("Duplicate asset: {}", duplicate);
+    }
 
     let init_file = option_env!("PANICVTT_SERVER_INIT_FILE"); 
     let commands = define_initial_state(init_file, &mut state);
 
-    let _r = rocket::build()
+    let rocket = rocket::build()
         .manage(models::CommandList { commands: Mutex::new(commands) })
         .manage(Mutex::new(state)) 
         .mount("/", FileServer::from("panicvtt_server/static"))
         .mount("/", routes![routes::index, 
             routes::add_command, 
             routes::connect,
+            routes::create_scene,
+            routes::activate_scene,
+            routes::entity,
             routes::login_get,
             routes::login_post, 
             routes::disconnect, 
@@ -110,6 +119,13 @@ async fn main() -> Result<(), rocket::Error> {
         .launch()
         .await?;
 
+    // Anything changed since the last save would otherwise be lost on shutdown 
+    if let Some(state) = rocket.state::<Mutex<PanicState>>() {
+        if let Err(e) = state.lock().unwrap().engine.save() {
+            eprintln!("Failed to save on shutdown: {}!", e);
+        }
+    }
+
     Ok(())        
 }
 
@@ -4,8 +4,11 @@ use panicvtt_engine::engine::Engine;
 
 pub(super) struct PanicState {
     pub(super) engine: Engine,
+    #[allow(dead_code)]     // Populated once entity commands are ported to the new engine API
     pub(super) entities: HashMap<String, u128>, 
     pub(super) active_campaign: u128,
+    /// The player who loaded the first campaign. They run every campaign after it and see the whole map 
+    pub(super) gm: Option<String>,
 }
 
 impl PanicState {
@@ -13,7 +16,8 @@ impl PanicState {
         Self {
             engine, 
             entities: HashMap::new(),
-            active_campaign: 0
+            active_campaign: 0,
+            gm: None,
         }
     }
 }
@@ -2,6 +2,7 @@ use std::fmt::Display;
 
 
 #[derive(Debug)]
+#[allow(dead_code)]     // Constructed once entity commands are ported to the new engine API
 pub(super) enum ParseErrorKind {
     WrongNumArgs { expected_num: u8, actual_num: u8 },
     SyntaxError { bad_token: String }, 
@@ -36,6 +37,7 @@ impl Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+#[allow(dead_code)]     // Used once entity commands are ported to the new engine API
 impl ParseError {
     fn new(all_tokens: &[&str], error_kind: ParseErrorKind) -> Self {
         Self {
@@ -1,8 +1,9 @@
 use std::sync::Mutex;
 
-use panicvtt_engine::assets::load_asset_result::LoadAssetResult;
-use rocket::{form::Form, response::Redirect, State};
+use panicvtt_engine::{assets::{asset::Asset, load_asset_result::LoadAssetResult}, campaigns::encounter::EncounterPrompt};
+use rocket::{form::Form, http::CookieJar, response::Redirect, State};
 use rocket_dyn_templates::{Template, context};
+use serde::Serialize;
 
 //use crate::{panic_state::PanicState, parse_command::{command_delete_entity, command_get_entity_abilities, command_get_entity_ability, command_list_entities, command_new_entity}, parse_error::ParseError};
 
@@ -38,13 +39,21 @@ pub struct Login {
     password: String
 }
 
+/// The cookie holding the name of the player logged in from this browser 
+const SESSION_COOKIE: &str = "player";
+
 #[post("/login", data = "<form_data>")]
-pub fn login_post(form_data: Form<Login>) -> Redirect {
-    // !TODO 
+pub fn login_post(form_data: Form<Login>, cookies: &CookieJar<'_>) -> Redirect {
+    // !TODO check the password 
     println!("Login pair: {} {}", form_data.username, form_data.password);
+    cookies.add((SESSION_COOKIE, form_data.username.clone()));
     Redirect::to("/load_campaign")
 }
 
+fn get_session_player(cookies: &CookieJar<'_>) -> Option<String> {
+    cookies.get(SESSION_COOKIE).map(|c| String::from(c.value()))
+}
+
 #[get("/load_campaign")]
 pub fn load_campaign_get(state: &State<Mutex<PanicState>>) -> Template {
     let lock = state.lock().unwrap();
@@ -64,18 +73,42 @@ pub struct LoadCampaign {
 }
 
 #[post("/load_campaign", data = "<form_data>")]
-pub fn load_campaign_put(form_data: Form<LoadCampaign>, state: &State<Mutex<PanicState>>) -> Redirect {
+pub fn load_campaign_put(form_data: Form<LoadCampaign>, cookies: &CookieJar<'_>, state: &State<Mutex<PanicState>>) -> Redirect {
     println!("\t{}", form_data.uuid);
+
+    // Whoever picks the first campaign runs the table, and only they may change campaigns after that 
+    let Some(player) = get_session_player(cookies) else {
+        return Redirect::to("/login");
+    };
     
     match u128::from_str_radix(&form_data.uuid, 16) {
         Ok(u) => {
             let mut lock = state.lock().unwrap();
+            if lock.gm.as_ref().is_some_and(|gm| *gm != player) {
+                return Redirect::to("/vtt");
+            }
+
+            // Campaigns that are already loaded only need switching to 
+            if lock.engine.set_active_campaign(Some(u)) {
+                lock.active_campaign = u;
+                return Redirect::to("/vtt");
+            }
+
+            // Only report the duplicates this Campaign adds 
+            let known_duplicates = lock.engine.get_duplicates().len();
             let l = lock.engine.load_campaign(u);
             
             match l {
                 LoadAssetResult::Ok { asset } => {
                     println!("Loaded campaign with name {}!", asset.get_name());
                     lock.active_campaign = u;
+                    lock.gm.get_or_insert(player);
+                    for reference in lock.engine.validate_assets() {
+                        eprintln!("Dangling reference: {}", reference);
+                    }
+                    for duplicate in &lock.engine.get_duplicates()[known_duplicates..] {
+                        eprintln!("Duplicate asset: {}", duplicate);
+                    }
                     Redirect::to("/vtt")
                 },
                 LoadAssetResult::UuidNotFoundError => todo!(),
@@ -93,16 +126,146 @@ pub fn load_campaign_put(form_data: Form<LoadCampaign>, state: &State<Mutex<Pani
 }
 
 #[get("/vtt")]
-pub fn vtt(state: &State<Mutex<PanicState>>) -> Template { 
+pub fn vtt(cookies: &CookieJar<'_>, state: &State<Mutex<PanicState>>) -> Result<Template, Box<Redirect>> { 
+    let Some(player) = get_session_player(cookies) else {
+        return Err(Box::new(Redirect::to("/login")));
+    };
     let lock = state.lock().unwrap();
+    let campaign_id = lock.active_campaign;
+    let scene_id = lock.engine.get_campaign(campaign_id).unwrap().get_active_scene();
+
+    // Players only see the tokens their own tokens can see; the GM sees everything 
+    let is_gm = lock.gm.as_deref() == Some(player.as_str());
+    let tokens: Vec<String> = scene_id
+        .and_then(|s| match is_gm {
+            true => lock.engine.get_scene(campaign_id, s).map(|s| s.get_tokens().iter().collect()),
+            false => lock.engine.get_visible_tokens(campaign_id, s, &player),
+        })
+        .unwrap_or_default()
+        .iter()
+        .map(|t| format!("{} {}", lock.engine.get_entity(t.get_entity()).map_or("Unknown", |e| e.get_name()), t.get_position()))
+        .collect();
 
     // Render the current campaign 
     let c = lock.engine.get_campaign(lock.active_campaign).unwrap();
-    Template::render("vtt", context! {
+    let party = lock.engine.get_party_passives(lock.active_campaign).unwrap_or_default();
+
+    // Let the GM know about legendary and lair actions available in the active Scene's combat 
+    let prompts: Vec<String> = c.get_active_scene()
+        .and_then(|s| lock.engine.get_encounter(lock.active_campaign, s))
+        .map(|e| e.get_prompts().iter().map(|p| {
+            let name = lock.engine.get_entity(p.get_entity()).map_or("Unknown", |e| e.get_name());
+            match p {
+                EncounterPrompt::LegendaryActions { remaining, .. } => format!("{} may take a legendary action ({} left)", name, remaining),
+                EncounterPrompt::LairAction { .. } => format!("{} may take a lair action", name),
+            }
+        }).collect())
+        .unwrap_or_default();
+
+    // Scene UUIDs are too large for the template engine's numbers, so pass them as hex 
+    let scenes: Vec<SceneLink> = c.get_scenes().iter()
+        .map(|s| SceneLink {
+            uuid: format!("{:032x}", s.get_uuid()),
+            name: s.get_name(),
+            active: c.get_active_scene() == Some(s.get_uuid()),
+        })
+        .collect();
+
+    Ok(Template::render("vtt", context! {
         subtitle: format!("- {}", c.get_name()),
         version: panicvtt_engine::version(), 
-        campaign: c
-    })
+        scenes: scenes,
+        party: party,
+        prompts: prompts,
+        tokens: tokens,
+        player: player,
+        gm: is_gm
+    }))
+}
+
+#[derive(Serialize)]
+pub struct SceneLink<'a> {
+    uuid: String,
+    name: &'a str,
+    active: bool,
+}
+
+#[derive(FromForm)]
+pub struct CreateScene {
+    name: String
+}
+
+/// Write whatever a route changed to disk, so it isn't lost if the server stops 
+fn save(state: &mut PanicState) {
+    if let Err(e) = state.engine.save() {
+        eprintln!("Failed to save: {}!", e);
+    }
+}
+
+/// Only the GM may change what everyone sees. Anyone else is sent to log in, or back to the table 
+fn redirect_unless_gm(cookies: &CookieJar<'_>, state: &PanicState) -> Option<Redirect> {
+    match get_session_player(cookies) {
+        None => Some(Redirect::to("/login")),
+        Some(player) if state.gm.as_ref() != Some(&player) => Some(Redirect::to("/vtt")),
+        Some(_) => None,
+    }
+}
+
+#[post("/scene", data = "<form_data>")]
+pub fn create_scene(form_data: Form<CreateScene>, cookies: &CookieJar<'_>, state: &State<Mutex<PanicState>>) -> Redirect {
+    let mut lock = state.lock().unwrap();
+    if let Some(redirect) = redirect_unless_gm(cookies, &lock) {
+        return redirect;
+    }
+    let campaign_id = lock.active_campaign;
+
+    // The first Scene made becomes the active one 
+    let name = match form_data.name.trim() {
+        "" => String::from("New Scene"),
+        name => String::from(name),
+    };
+    if let Some(scene_id) = lock.engine.create_scene(campaign_id, name).map(|s| s.get_uuid()) {
+        if lock.engine.get_campaign(campaign_id).is_some_and(|c| c.get_active_scene().is_none()) {
+            lock.engine.set_active_scene(campaign_id, Some(scene_id));
+        }
+    }
+    save(&mut lock);
+
+    Redirect::to("/vtt")
+}
+
+#[post("/scene/<uuid>/activate")]
+pub fn activate_scene(uuid: &str, cookies: &CookieJar<'_>, state: &State<Mutex<PanicState>>) -> Redirect {
+    let mut lock = state.lock().unwrap();
+    if let Some(redirect) = redirect_unless_gm(cookies, &lock) {
+        return redirect;
+    }
+    let campaign_id = lock.active_campaign;
+
+    if let Ok(scene_id) = u128::from_str_radix(uuid, 16) {
+        lock.engine.set_active_scene(campaign_id, Some(scene_id));
+    }
+    save(&mut lock);
+
+    Redirect::to("/vtt")
+}
+
+#[get("/entity/<uuid>")]
+pub fn entity(uuid: &str, state: &State<Mutex<PanicState>>) -> Option<Template> {
+    let lock = state.lock().unwrap();
+
+    // Render the character sheet of one Entity, or 404 if there's no such Entity 
+    let uuid = u128::from_str_radix(uuid, 16).ok()?;
+    let e = lock.engine.get_entity(uuid)?;
+    Some(Template::render("entity", context! {
+        subtitle: format!("- {}", e.get_name()),
+        version: panicvtt_engine::version(),
+        name: e.get_name(),
+        level: e.get_level(),
+        size: e.get_size().to_string(),
+        alignment: e.get_biography().get_alignment().map(|a| a.to_string()),
+        biography: e.get_biography(),
+    }))
 }
 
 #[post("/vtt", data = "<form_data>")]
@@ -116,6 +279,7 @@ pub fn add_command(form_data: Form<Command<'_>>, command_list: &State<CommandLis
             format!("Failed to parse command: {}!", e)
         }
     }; 
+    save(&mut lock);
                 
     let mut command_lock = command_list.commands.lock().expect("Lock shared data");
     command_lock.push(message);